@@ -1,3 +1,6 @@
+use std::io::Read;
+
+use anyhow::ensure;
 use chrono::{DateTime, Utc};
 
 use serde::{Deserialize, Serialize};
@@ -12,7 +15,11 @@ mod sqlite;
 #[cfg(feature = "sqlite")]
 pub use sqlite::SqliteDataProvide;
 
-pub const TRANSFER_DATA_VERSION: u16 = 100;
+mod transfer;
+pub use transfer::MIN_TRANSFER_DATA_VERSION;
+
+/// Current version of the transfer format, written to the exported transfer files.
+pub const TRANSFER_DATA_VERSION: u16 = 101;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ModifyEntryError {
@@ -32,10 +39,15 @@ pub trait DataProvider {
     async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()>;
     async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError>;
     async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO>;
+    /// Adds the entries of the transfer data which must be converted to the current version
+    /// already, like the data read with [`EntriesDTO::from_reader()`].
     async fn import_entries(&self, entries_dto: EntriesDTO) -> anyhow::Result<()> {
-        debug_assert_eq!(
-            TRANSFER_DATA_VERSION, entries_dto.version,
-            "Version mismatches check if there is a need to do a converting to the data"
+        transfer::check_version(entries_dto.version.into())?;
+        ensure!(
+            entries_dto.version == TRANSFER_DATA_VERSION,
+            "Transfer data version {} must be converted to the current version \
+            {TRANSFER_DATA_VERSION} before importing",
+            entries_dto.version
         );
 
         for entry_draft in entries_dto.entries {
@@ -149,6 +161,14 @@ impl EntriesDTO {
             entries,
         }
     }
+
+    /// Reads transfer data in JSON format of any supported version, converting it to the
+    /// current version.
+    pub fn from_reader(reader: impl Read) -> anyhow::Result<Self> {
+        let value = serde_json::from_reader(reader)?;
+
+        transfer::parse_versioned(value)
+    }
 }
 
 #[cfg(test)]
@@ -330,4 +350,31 @@ mod tests {
         let added_entries = provider.added_entries.lock().unwrap().clone();
         assert_eq!(added_entries, entries[..2].to_vec());
     }
+
+    #[tokio::test]
+    async fn import_entries_rejects_other_versions() {
+        let provider = ImportStubProvider::new(None);
+
+        for version in [TRANSFER_DATA_VERSION + 1, MIN_TRANSFER_DATA_VERSION] {
+            let dto = EntriesDTO {
+                version,
+                entries: vec![sample_draft()],
+            };
+
+            assert!(provider.import_entries(dto).await.is_err());
+        }
+
+        assert!(provider.added_entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn export_includes_version() {
+        let dto = EntriesDTO::new(vec![sample_draft()]);
+
+        let json = serde_json::to_string(&dto).unwrap();
+        let read = EntriesDTO::from_reader(json.as_bytes()).unwrap();
+
+        assert!(json.contains(&format!("\"version\":{TRANSFER_DATA_VERSION}")));
+        assert_eq!(read, dto);
+    }
 }
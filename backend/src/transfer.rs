@@ -0,0 +1,197 @@
+//! Versions of the transfer format with the conversions of the older ones to the current
+//! version. Each version is converted to the next one only, so adding a new version requires
+//! a conversion from the previous version only.
+
+use anyhow::{anyhow, bail};
+use serde_json::Value;
+
+use crate::{EntriesDTO, TRANSFER_DATA_VERSION};
+
+/// Oldest version of the transfer format which can be converted to the current one.
+pub const MIN_TRANSFER_DATA_VERSION: u16 = 100;
+
+/// Transfer data in one of the supported versions.
+enum VersionedDTO {
+    V100(v100::EntriesDTO),
+    V101(EntriesDTO),
+}
+
+impl VersionedDTO {
+    fn parse(version: u16, value: Value) -> serde_json::Result<Self> {
+        match version {
+            100 => serde_json::from_value(value).map(VersionedDTO::V100),
+            _ => serde_json::from_value(value).map(VersionedDTO::V101),
+        }
+    }
+
+    /// Converts the data to the next version. Data of the current version is returned as it is.
+    fn upgrade(self) -> Self {
+        match self {
+            VersionedDTO::V100(dto) => VersionedDTO::V101(dto.into()),
+            VersionedDTO::V101(dto) => VersionedDTO::V101(dto),
+        }
+    }
+}
+
+/// Checks the version of transfer data, rejecting versions from newer releases of the app and
+/// unknown older versions.
+pub fn check_version(version: u64) -> anyhow::Result<()> {
+    if version > TRANSFER_DATA_VERSION as u64 {
+        bail!(
+            "Transfer data version {version} is newer than the supported version \
+            {TRANSFER_DATA_VERSION}. Please update the app to import it"
+        );
+    }
+
+    if version < MIN_TRANSFER_DATA_VERSION as u64 {
+        bail!(
+            "Transfer data version {version} is unknown. Supported versions: \
+            {MIN_TRANSFER_DATA_VERSION} to {TRANSFER_DATA_VERSION}"
+        );
+    }
+
+    Ok(())
+}
+
+/// Parses the transfer data of any supported version, converting it step by step to the
+/// current version.
+pub fn parse_versioned(value: Value) -> anyhow::Result<EntriesDTO> {
+    let version = value
+        .get("version")
+        .ok_or_else(|| anyhow!("Transfer data version is missing"))?
+        .as_u64()
+        .ok_or_else(|| anyhow!("Transfer data version must be a positive number"))?;
+
+    check_version(version)?;
+
+    let mut dto = VersionedDTO::parse(version as u16, value)?;
+    loop {
+        match dto {
+            VersionedDTO::V101(dto) => return Ok(dto),
+            older => dto = older.upgrade(),
+        }
+    }
+}
+
+/// The first version of the transfer format. Tags and priority were added to the journals
+/// later without changing the version, so they can be missing in files of this version.
+mod v100 {
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    pub struct EntriesDTO {
+        pub entries: Vec<EntryDraft>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct EntryDraft {
+        pub date: DateTime<Utc>,
+        pub title: String,
+        pub content: String,
+        #[serde(default)]
+        pub tags: Vec<String>,
+        #[serde(default)]
+        pub priority: Option<u32>,
+    }
+
+    impl From<EntriesDTO> for crate::EntriesDTO {
+        fn from(dto: EntriesDTO) -> Self {
+            let entries = dto
+                .entries
+                .into_iter()
+                .map(|draft| {
+                    crate::EntryDraft::new(draft.date, draft.title, draft.tags, draft.priority)
+                        .with_content(draft.content)
+                })
+                .collect();
+
+            Self {
+                version: 101,
+                entries,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use crate::EntryDraft;
+
+    use super::*;
+
+    #[test]
+    fn parse_current_version() {
+        let draft = EntryDraft::new(
+            Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+            String::from("Title"),
+            vec![String::from("tag")],
+            Some(1),
+        )
+        .with_content(String::from("Content"));
+        let dto = EntriesDTO::new(vec![draft]);
+
+        let parsed = parse_versioned(serde_json::to_value(&dto).unwrap()).unwrap();
+
+        assert_eq!(parsed, dto);
+    }
+
+    #[test]
+    fn upgrade_version_100() {
+        let value = json!({
+            "version": 100,
+            "entries": [
+                {
+                    "date": "2023-06-11T10:00:00Z",
+                    "title": "Without tags",
+                    "content": "Content 1"
+                },
+                {
+                    "date": "2024-01-16T10:00:00Z",
+                    "title": "With tags and priority",
+                    "content": "Content 2",
+                    "tags": ["work"],
+                    "priority": 2
+                }
+            ]
+        });
+
+        let dto = parse_versioned(value).unwrap();
+
+        assert_eq!(dto.version, TRANSFER_DATA_VERSION);
+        assert_eq!(
+            dto.entries,
+            [
+                EntryDraft::new(
+                    Utc.with_ymd_and_hms(2023, 6, 11, 10, 0, 0).unwrap(),
+                    String::from("Without tags"),
+                    Vec::new(),
+                    None,
+                )
+                .with_content(String::from("Content 1")),
+                EntryDraft::new(
+                    Utc.with_ymd_and_hms(2024, 1, 16, 10, 0, 0).unwrap(),
+                    String::from("With tags and priority"),
+                    vec![String::from("work")],
+                    Some(2),
+                )
+                .with_content(String::from("Content 2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_unsupported_versions() {
+        let err = parse_versioned(json!({ "version": 999, "entries": [] })).unwrap_err();
+        assert!(err.to_string().contains("Please update the app"));
+
+        let err = parse_versioned(json!({ "version": 99, "entries": [] })).unwrap_err();
+        assert!(err.to_string().contains("version 99 is unknown"));
+
+        let err = parse_versioned(json!({ "entries": [] })).unwrap_err();
+        assert!(err.to_string().contains("version is missing"));
+    }
+}
@@ -190,6 +190,31 @@ async fn export_import() {
     assert_eq!(dto_source, dto_dist);
 }
 
+#[tokio::test]
+async fn import_version_100() {
+    let temp_file = Builder::new()
+        .prefix("json_import_version_100")
+        .tempfile()
+        .unwrap();
+    let provider = JsonDataProvide::new(temp_file.path().to_path_buf());
+
+    let file_content = r#"{
+        "version": 100,
+        "entries": [
+            { "date": "2023-06-11T10:00:00Z", "title": "Old journal", "content": "Old content" }
+        ]
+    }"#;
+    let dto = EntriesDTO::from_reader(file_content.as_bytes()).unwrap();
+    provider.import_entries(dto).await.unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].title, "Old journal");
+    assert_eq!(entries[0].content, "Old content");
+    assert!(entries[0].tags.is_empty());
+    assert_eq!(entries[0].priority, None);
+}
+
 #[tokio::test]
 async fn assign_priority() {
     let temp_file = Builder::new()
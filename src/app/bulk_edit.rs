@@ -0,0 +1,138 @@
+//! Edits applied to the attributes of all selected journals at once in multi-select mode.
+
+use chrono::Duration;
+
+use super::history::EntryAttributes;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkEdit {
+    /// Adds the tags to the journals missing them.
+    AddTags(Vec<String>),
+    /// Removes the tags from the journals.
+    RemoveTags(Vec<String>),
+    /// Replaces the tag with the new one, keeping the tags of each journal unique.
+    RenameTag { from: String, to: String },
+    /// Sets the priority of the journals, clearing it with `None`.
+    SetPriority(Option<u32>),
+    /// Moves the dates of the journals by the given days, backwards for negative values.
+    ShiftDates(i64),
+}
+
+impl BulkEdit {
+    /// Applies the edit on the given attributes, returning whether they are changed.
+    pub(super) fn apply(&self, attributes: &mut EntryAttributes) -> bool {
+        let before = attributes.clone();
+
+        match self {
+            BulkEdit::AddTags(tags) => {
+                for tag in tags {
+                    if !attributes.tags.contains(tag) {
+                        attributes.tags.push(tag.to_owned());
+                    }
+                }
+            }
+            BulkEdit::RemoveTags(tags) => attributes.tags.retain(|tag| !tags.contains(tag)),
+            BulkEdit::RenameTag { from, to } => {
+                if let Some(index) = attributes.tags.iter().position(|tag| tag == from) {
+                    if attributes.tags.contains(to) {
+                        attributes.tags.remove(index);
+                    } else {
+                        attributes.tags[index] = to.to_owned();
+                    }
+                }
+            }
+            BulkEdit::SetPriority(priority) => attributes.priority = *priority,
+            BulkEdit::ShiftDates(days) => {
+                if let Some(date) = Duration::try_days(*days)
+                    .and_then(|duration| attributes.date.checked_add_signed(duration))
+                {
+                    attributes.date = date;
+                }
+            }
+        }
+
+        *attributes != before
+    }
+
+    /// Describes the edit applied on the given count of journals for the status messages.
+    pub fn describe(&self, count: usize) -> String {
+        match self {
+            BulkEdit::AddTags(tags) => {
+                format!("Added tags '{}' to {count} journals", tags.join(", "))
+            }
+            BulkEdit::RemoveTags(tags) => {
+                format!("Removed tags '{}' from {count} journals", tags.join(", "))
+            }
+            BulkEdit::RenameTag { from, to } => {
+                format!("Renamed tag '{from}' to '{to}' in {count} journals")
+            }
+            BulkEdit::SetPriority(Some(priority)) => {
+                format!("Set priority {priority} on {count} journals")
+            }
+            BulkEdit::SetPriority(None) => format!("Cleared the priority of {count} journals"),
+            BulkEdit::ShiftDates(days) => {
+                format!("Shifted the dates of {count} journals by {days:+} days")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn attributes(tags: &[&str]) -> EntryAttributes {
+        EntryAttributes {
+            id: 1,
+            date: Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap(),
+            title: String::from("Title"),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            priority: Some(1),
+        }
+    }
+
+    #[test]
+    fn tags_edits() {
+        let mut attr = attributes(&["work"]);
+        let add = BulkEdit::AddTags(vec![String::from("work"), String::from("q2")]);
+        assert!(add.apply(&mut attr));
+        assert_eq!(attr.tags, ["work", "q2"]);
+        assert!(!add.apply(&mut attr));
+
+        let remove = BulkEdit::RemoveTags(vec![String::from("work"), String::from("other")]);
+        assert!(remove.apply(&mut attr));
+        assert_eq!(attr.tags, ["q2"]);
+
+        let rename = BulkEdit::RenameTag {
+            from: String::from("q2"),
+            to: String::from("Q2"),
+        };
+        assert!(rename.apply(&mut attr));
+        assert_eq!(attr.tags, ["Q2"]);
+
+        // Renaming to an existing tag merges them.
+        let mut attr = attributes(&["a", "b"]);
+        let rename = BulkEdit::RenameTag {
+            from: String::from("a"),
+            to: String::from("b"),
+        };
+        assert!(rename.apply(&mut attr));
+        assert_eq!(attr.tags, ["b"]);
+    }
+
+    #[test]
+    fn priority_and_date_edits() {
+        let mut attr = attributes(&[]);
+        assert!(!BulkEdit::SetPriority(Some(1)).apply(&mut attr));
+        assert!(BulkEdit::SetPriority(None).apply(&mut attr));
+        assert_eq!(attr.priority, None);
+
+        assert!(BulkEdit::ShiftDates(-2).apply(&mut attr));
+        assert_eq!(
+            attr.date,
+            Utc.with_ymd_and_hms(2024, 2, 28, 9, 30, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Drafts of the unsaved changes in the editor, persisted in the state directory to recover them
+//! if the app doesn't exit normally.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+use super::state::AppState;
+
+const DRAFTS_DIR_NAME: &str = "drafts";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Draft {
+    pub entry_id: u32,
+    pub content: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Persists the drafts as a file for each journal, keeping track of the last saved one to write
+/// the files only when the content changes.
+pub struct DraftsStore {
+    dir: PathBuf,
+    /// Journal ID and content of the draft written last.
+    last_saved: Option<(u32, String)>,
+}
+
+impl DraftsStore {
+    pub fn new(settings: &Settings) -> anyhow::Result<Self> {
+        let dir = AppState::get_persist_dir(settings)?.join(DRAFTS_DIR_NAME);
+
+        Ok(Self::with_dir(dir))
+    }
+
+    fn with_dir(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            last_saved: None,
+        }
+    }
+
+    fn draft_path(&self, entry_id: u32) -> PathBuf {
+        self.dir.join(format!("{entry_id}.json"))
+    }
+
+    /// Writes the draft of the unsaved content if it's changed since the last call, or removes the
+    /// last draft if there are no unsaved changes anymore.
+    pub fn sync(&mut self, unsaved: Option<(u32, &str)>) -> anyhow::Result<()> {
+        match (self.last_saved.as_ref(), unsaved) {
+            (Some((last_id, last_content)), Some((entry_id, content)))
+                if *last_id == entry_id && last_content == content =>
+            {
+                Ok(())
+            }
+            (last_saved, Some((entry_id, content))) => {
+                if let Some((last_id, _)) = last_saved
+                    && *last_id != entry_id
+                {
+                    self.remove(*last_id)?;
+                }
+
+                self.save(&Draft {
+                    entry_id,
+                    content: content.to_owned(),
+                    saved_at: Utc::now(),
+                })?;
+                self.last_saved = Some((entry_id, content.to_owned()));
+
+                Ok(())
+            }
+            (Some((last_id, _)), None) => {
+                self.remove(*last_id)?;
+                self.last_saved = None;
+
+                Ok(())
+            }
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn save(&self, draft: &Draft) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|err| anyhow!("Failed to create drafts directory. Error info: {err}"))?;
+
+        // Write to a temporary file first so a crash while writing doesn't corrupt the draft.
+        let path = self.draft_path(draft.entry_id);
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec(draft)?)
+            .map_err(|err| anyhow!("Failed to write draft. Error info: {err}"))?;
+        fs::rename(temp_path, path)
+            .map_err(|err| anyhow!("Failed to write draft. Error info: {err}"))?;
+
+        Ok(())
+    }
+
+    pub fn remove(&self, entry_id: u32) -> anyhow::Result<()> {
+        let path = self.draft_path(entry_id);
+        if path.exists() {
+            fs::remove_file(path)
+                .map_err(|err| anyhow!("Failed to remove draft. Error info: {err}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads the drafts left from previous sessions ordered by their journal IDs. Unreadable
+    /// drafts are skipped.
+    pub fn load_all(&self) -> anyhow::Result<Vec<Draft>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut drafts: Vec<Draft> = fs::read_dir(&self.dir)
+            .map_err(|err| anyhow!("Failed to read drafts directory. Error info: {err}"))?
+            .filter_map(|dir_entry| dir_entry.ok().map(|dir_entry| dir_entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let draft = fs::read(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|bytes| serde_json::from_slice(&bytes).map_err(Into::into));
+                match draft {
+                    Ok(draft) => Some(draft),
+                    Err(err) => {
+                        log::error!(
+                            "Reading draft failed. Path: {}, Error {err}",
+                            path.display()
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        drafts.sort_by_key(|draft| draft.entry_id);
+
+        Ok(drafts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (tempfile::TempDir, DraftsStore) {
+        let dir = tempfile::Builder::new().prefix("drafts").tempdir().unwrap();
+        let store = DraftsStore::with_dir(dir.path().join(DRAFTS_DIR_NAME));
+        (dir, store)
+    }
+
+    #[test]
+    fn sync_saves_and_removes_drafts() {
+        let (_dir, mut store) = store();
+
+        store.sync(Some((1, "first"))).unwrap();
+        store.sync(Some((1, "first changed"))).unwrap();
+        let drafts = store.load_all().unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].entry_id, 1);
+        assert_eq!(drafts[0].content, "first changed");
+
+        // Moving to another journal replaces the draft of the previous one.
+        store.sync(Some((2, "second"))).unwrap();
+        let ids: Vec<u32> = store
+            .load_all()
+            .unwrap()
+            .iter()
+            .map(|draft| draft.entry_id)
+            .collect();
+        assert_eq!(ids, [2]);
+
+        store.sync(None).unwrap();
+        assert!(store.load_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn leftover_drafts_are_kept_until_removed() {
+        let (_dir, mut store) = store();
+        store.sync(Some((3, "three"))).unwrap();
+        store.sync(Some((3, "three"))).unwrap();
+
+        // A new session doesn't remove the drafts it didn't write.
+        let mut new_session = DraftsStore::with_dir(store.dir.clone());
+        new_session.sync(None).unwrap();
+        new_session.sync(Some((4, "four"))).unwrap();
+        new_session.sync(None).unwrap();
+
+        let drafts = new_session.load_all().unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].content, "three");
+
+        new_session.remove(3).unwrap();
+        assert!(new_session.load_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn skip_invalid_drafts() {
+        let (_dir, mut store) = store();
+        store.sync(Some((5, "five"))).unwrap();
+        fs::write(store.dir.join("6.json"), "not json").unwrap();
+
+        let drafts = store.load_all().unwrap();
+
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].entry_id, 5);
+    }
+}
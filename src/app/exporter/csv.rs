@@ -0,0 +1,64 @@
+use anyhow::anyhow;
+use backend::Entry;
+
+use super::Exporter;
+
+/// Columns of the CSV transfer format. They match the default columns of the CSV importer.
+const CSV_HEADER: [&str; 6] = ["id", "date", "title", "tags", "priority", "content"];
+
+/// Format of the dates keeping their time, so they are imported again without changes.
+const CSV_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Writes the journals as CSV rows with their IDs to be analyzed in spreadsheets and imported
+/// again. Tags are separated with commas inside their column.
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String> {
+        let mut writer = ::csv::Writer::from_writer(Vec::new());
+
+        writer.write_record(CSV_HEADER)?;
+
+        for entry in entries {
+            writer.write_record([
+                entry.id.to_string(),
+                entry.date.format(CSV_DATE_FORMAT).to_string(),
+                entry.title.to_owned(),
+                entry.tags.join(", "),
+                entry
+                    .priority
+                    .map(|priority| priority.to_string())
+                    .unwrap_or_default(),
+                entry.content.to_owned(),
+            ])?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|err| anyhow!("Error while writing CSV. Error: {err}"))?;
+
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::exporter::sample_entries;
+
+    use super::*;
+
+    #[test]
+    fn rows_with_quoted_fields() {
+        let entries = sample_entries();
+        let entries: Vec<&Entry> = entries.iter().collect();
+
+        let exported = CsvExporter.export(&entries).unwrap();
+
+        assert_eq!(
+            exported,
+            "id,date,title,tags,priority,content\n\
+            1,2024-03-01 09:30:00,\"Planning \"\"Q2\"\"\",\"work, plans\",2,\"# Goals\n- Ship <export>\"\n\
+            2,2024-03-02 18:00:00,Evening,,,Quiet day.\n"
+        );
+    }
+}
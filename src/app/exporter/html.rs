@@ -0,0 +1,99 @@
+use backend::Entry;
+
+use super::{Exporter, format_date};
+
+const STYLE: &str =
+    "body { font-family: sans-serif; max-width: 50em; margin: 2em auto; padding: 0 1em; }
+nav li { margin: 0.2em 0; }
+.meta { color: #666; font-size: 0.9em; }
+.tag { background: #eee; border-radius: 0.3em; padding: 0 0.3em; margin-right: 0.3em; }
+.content { white-space: pre-wrap; }";
+
+/// Writes the journals as a standalone HTML document with a table of contents linking to them.
+pub struct HtmlExporter;
+
+impl Exporter for HtmlExporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String> {
+        let mut output =
+            String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        output.push_str("<title>Journals</title>\n");
+        output.push_str(&format!("<style>\n{STYLE}\n</style>\n"));
+        output.push_str("</head>\n<body>\n");
+
+        output.push_str("<nav>\n<h1>Table of Contents</h1>\n<ul>\n");
+        for entry in entries {
+            output.push_str(&format!(
+                "<li><a href=\"#journal-{}\">{}</a> <span class=\"meta\">{}</span></li>\n",
+                entry.id,
+                escape(&entry.title),
+                format_date(entry)
+            ));
+        }
+        output.push_str("</ul>\n</nav>\n");
+
+        for entry in entries {
+            output.push_str(&format!("<article id=\"journal-{}\">\n", entry.id));
+            output.push_str(&format!("<h2>{}</h2>\n", escape(&entry.title)));
+
+            output.push_str(&format!("<p class=\"meta\">{}", format_date(entry)));
+            if let Some(priority) = entry.priority {
+                output.push_str(&format!(" | Priority: {priority}"));
+            }
+            for tag in entry.tags.iter() {
+                output.push_str(&format!(" <span class=\"tag\">{}</span>", escape(tag)));
+            }
+            output.push_str("</p>\n");
+
+            output.push_str(&format!(
+                "<div class=\"content\">{}</div>\n",
+                escape(&entry.content)
+            ));
+            output.push_str("</article>\n");
+        }
+
+        output.push_str("</body>\n</html>\n");
+
+        Ok(output)
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::exporter::sample_entries;
+
+    use super::*;
+
+    #[test]
+    fn table_of_contents_links_escaped_journals() {
+        let entries = sample_entries();
+        let entries: Vec<&Entry> = entries.iter().collect();
+
+        let exported = HtmlExporter.export(&entries).unwrap();
+
+        assert!(exported.starts_with("<!DOCTYPE html>"));
+        assert!(exported.contains(
+            "<li><a href=\"#journal-1\">Planning &quot;Q2&quot;</a> <span class=\"meta\">2024-03-01</span></li>"
+        ));
+        assert!(exported.contains("<li><a href=\"#journal-2\">Evening</a>"));
+        assert!(exported.contains("<article id=\"journal-1\">"));
+        assert!(exported.contains("<span class=\"tag\">work</span>"));
+        assert!(exported.contains("- Ship &lt;export&gt;"));
+        assert!(exported.ends_with("</html>\n"));
+    }
+}
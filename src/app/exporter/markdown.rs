@@ -0,0 +1,77 @@
+use backend::Entry;
+
+use super::Exporter;
+
+/// Writes each journal as a Markdown document with its attributes in a YAML front matter.
+pub struct MarkdownExporter;
+
+impl Exporter for MarkdownExporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String> {
+        let mut output = String::new();
+
+        for entry in entries {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+
+            output.push_str("---\n");
+            output.push_str(&format!("title: {}\n", quote(&entry.title)));
+            output.push_str(&format!("date: {}\n", entry.date.to_rfc3339()));
+
+            let tags: Vec<String> = entry.tags.iter().map(|tag| quote(tag)).collect();
+            output.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+
+            if let Some(priority) = entry.priority {
+                output.push_str(&format!("priority: {priority}\n"));
+            }
+            output.push_str("---\n\n");
+
+            output.push_str(&entry.content);
+            if !entry.content.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Quotes the text as a YAML double-quoted string.
+fn quote(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::exporter::sample_entries;
+
+    use super::*;
+
+    #[test]
+    fn front_matter_and_body() {
+        let entries = sample_entries();
+        let entries: Vec<&Entry> = entries.iter().collect();
+
+        let exported = MarkdownExporter.export(&entries).unwrap();
+
+        assert_eq!(
+            exported,
+            "---\n\
+            title: \"Planning \\\"Q2\\\"\"\n\
+            date: 2024-03-01T09:30:00+00:00\n\
+            tags: [\"work\", \"plans\"]\n\
+            priority: 2\n\
+            ---\n\n\
+            # Goals\n\
+            - Ship <export>\n\
+            \n\
+            ---\n\
+            title: \"Evening\"\n\
+            date: 2024-03-02T18:00:00+00:00\n\
+            tags: []\n\
+            ---\n\n\
+            Quiet day.\n"
+        );
+    }
+}
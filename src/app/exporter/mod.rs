@@ -0,0 +1,187 @@
+use std::{borrow::Cow, path::Path};
+
+use backend::Entry;
+
+mod csv;
+mod html;
+mod markdown;
+mod template;
+mod text;
+
+use self::csv::CsvExporter;
+use html::HtmlExporter;
+use markdown::MarkdownExporter;
+use template::TemplateExporter;
+pub use template::{ExportTemplate, find_template, load_templates};
+use text::TextExporter;
+
+/// Converts journals into the content of an export file.
+pub trait Exporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String>;
+}
+
+/// Formats journals can be exported to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The content of the journals only.
+    Content,
+    /// The transfer format of the back-ends which can be imported again.
+    Json,
+    Markdown,
+    Html,
+    Text,
+    /// Rows of the journals with their IDs which can be imported again.
+    Csv,
+    /// User defined template from the templates directory.
+    Template(ExportTemplate),
+}
+
+impl ExportFormat {
+    /// The built-in formats.
+    pub const ALL: [ExportFormat; 6] = [
+        ExportFormat::Content,
+        ExportFormat::Json,
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+        ExportFormat::Text,
+        ExportFormat::Csv,
+    ];
+
+    pub fn name(&self) -> Cow<'static, str> {
+        match self {
+            ExportFormat::Content => "Journal content".into(),
+            ExportFormat::Json => "JSON transfer file".into(),
+            ExportFormat::Markdown => "Markdown with front matter".into(),
+            ExportFormat::Html => "HTML with table of contents".into(),
+            ExportFormat::Text => "Plain-text digest".into(),
+            ExportFormat::Csv => "CSV transfer file".into(),
+            ExportFormat::Template(template) => format!("Template '{}'", template.name).into(),
+        }
+    }
+
+    pub fn extension(&self) -> &str {
+        match self {
+            ExportFormat::Content | ExportFormat::Text => "txt",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Template(template) => template.extension.as_str(),
+        }
+    }
+
+    /// Infers the format from the extension of the file. Text files get the content of a single
+    /// journal or a digest of multiple journals.
+    pub fn from_path(path: &Path, multiple: bool) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(ExportFormat::Json),
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            "html" | "htm" => Some(ExportFormat::Html),
+            "csv" => Some(ExportFormat::Csv),
+            "txt" if multiple => Some(ExportFormat::Text),
+            "txt" => Some(ExportFormat::Content),
+            _ => None,
+        }
+    }
+
+    /// Format used when it can't be inferred from the file extension.
+    pub fn default_for(multiple: bool) -> Self {
+        if multiple {
+            ExportFormat::Json
+        } else {
+            ExportFormat::Content
+        }
+    }
+
+    /// Gets the exporter of the format. The JSON transfer format is provided by the back-ends
+    /// instead.
+    pub fn exporter(&self) -> Option<Box<dyn Exporter>> {
+        match self {
+            ExportFormat::Content => Some(Box::new(ContentExporter)),
+            ExportFormat::Json => None,
+            ExportFormat::Markdown => Some(Box::new(MarkdownExporter)),
+            ExportFormat::Html => Some(Box::new(HtmlExporter)),
+            ExportFormat::Text => Some(Box::new(TextExporter)),
+            ExportFormat::Csv => Some(Box::new(CsvExporter)),
+            ExportFormat::Template(template) => {
+                Some(Box::new(TemplateExporter::new(template.to_owned())))
+            }
+        }
+    }
+}
+
+/// Writes the content of the journals separated with empty lines.
+struct ContentExporter;
+
+impl Exporter for ContentExporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String> {
+        let contents: Vec<&str> = entries.iter().map(|entry| entry.content.as_str()).collect();
+
+        Ok(contents.join("\n\n"))
+    }
+}
+
+#[inline]
+fn format_date(entry: &Entry) -> String {
+    entry.date.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+pub(crate) fn sample_entries() -> Vec<Entry> {
+    use chrono::{TimeZone, Utc};
+
+    vec![
+        Entry::new(
+            1,
+            Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap(),
+            String::from("Planning \"Q2\""),
+            String::from("# Goals\n- Ship <export>"),
+            vec![String::from("work"), String::from("plans")],
+            Some(2),
+        ),
+        Entry::new(
+            2,
+            Utc.with_ymd_and_hms(2024, 3, 2, 18, 0, 0).unwrap(),
+            String::from("Evening"),
+            String::from("Quiet day."),
+            Vec::new(),
+            None,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn infer_format_from_extension() {
+        let format = |path: &str, multiple| ExportFormat::from_path(&PathBuf::from(path), multiple);
+
+        assert_eq!(format("out.JSON", true), Some(ExportFormat::Json));
+        assert_eq!(format("out.markdown", false), Some(ExportFormat::Markdown));
+        assert_eq!(format("out.htm", true), Some(ExportFormat::Html));
+        assert_eq!(format("out.csv", false), Some(ExportFormat::Csv));
+        assert_eq!(format("out.txt", true), Some(ExportFormat::Text));
+        assert_eq!(format("out.txt", false), Some(ExportFormat::Content));
+        assert_eq!(format("out.pdf", true), None);
+        assert_eq!(format("out", false), None);
+    }
+
+    #[test]
+    fn export_contents() {
+        let entries = sample_entries();
+        let entries: Vec<&Entry> = entries.iter().collect();
+
+        let exported = ExportFormat::Content
+            .exporter()
+            .unwrap()
+            .export(&entries[1..])
+            .unwrap();
+
+        assert_eq!(exported, "Quiet day.");
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, anyhow};
+use backend::Entry;
+use chrono::{Datelike, Utc};
+use minijinja::{Environment, context};
+use serde::Serialize;
+
+use super::{Exporter, format_date};
+
+const TEMPLATE_EXTENSION: &str = "jinja";
+const DEFAULT_OUTPUT_EXTENSION: &str = "txt";
+
+/// User defined template in the templates directory. The file `weekly.md.jinja` defines the
+/// template `weekly` which writes Markdown files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportTemplate {
+    pub name: String,
+    pub path: PathBuf,
+    /// Extension of the exported files.
+    pub extension: String,
+}
+
+impl ExportTemplate {
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        let stem = file_name.strip_suffix(&format!(".{TEMPLATE_EXTENSION}"))?;
+
+        let (name, extension) = match stem.split_once('.') {
+            Some((name, extension)) => (name, extension),
+            None => (stem, DEFAULT_OUTPUT_EXTENSION),
+        };
+
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name: name.to_owned(),
+            extension: extension.to_owned(),
+            path,
+        })
+    }
+}
+
+/// Loads the templates in the given directory ordered by their names.
+pub fn load_templates(dir: &Path) -> anyhow::Result<Vec<ExportTemplate>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut templates: Vec<ExportTemplate> = fs::read_dir(dir)
+        .map_err(|err| anyhow!("Failed to read templates directory. Error info: {err}"))?
+        .filter_map(|dir_entry| dir_entry.ok().map(|dir_entry| dir_entry.path()))
+        .filter(|path| path.is_file())
+        .filter_map(ExportTemplate::from_path)
+        .collect();
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(templates)
+}
+
+/// Finds the template with the given name in the directory.
+pub fn find_template(dir: &Path, name: &str) -> anyhow::Result<ExportTemplate> {
+    let templates = load_templates(dir)?;
+    let available: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+
+    templates
+        .iter()
+        .find(|template| template.name == name)
+        .cloned()
+        .with_context(|| {
+            format!(
+                "Template '{name}' couldn't be found in {}. Available templates: {}",
+                dir.display(),
+                if available.is_empty() {
+                    String::from("None")
+                } else {
+                    available.join(", ")
+                }
+            )
+        })
+}
+
+/// Journal attributes accessible in the templates.
+#[derive(Debug, Serialize)]
+struct TemplateEntry<'a> {
+    id: u32,
+    title: &'a str,
+    /// Date in RFC 3339 format.
+    datetime: String,
+    /// Date in `YYYY-MM-DD` format.
+    date: String,
+    content: &'a str,
+    tags: &'a [String],
+    priority: Option<u32>,
+}
+
+impl<'a> From<&'a Entry> for TemplateEntry<'a> {
+    fn from(entry: &'a Entry) -> Self {
+        Self {
+            id: entry.id,
+            title: &entry.title,
+            datetime: entry.date.to_rfc3339(),
+            date: format_date(entry),
+            content: &entry.content,
+            tags: &entry.tags,
+            priority: entry.priority,
+        }
+    }
+}
+
+/// Renders the journals with a minijinja template. Besides the `entries` list, the templates get
+/// the journals grouped in `by_tag`, `by_date`, `by_week` (`2024-W09`) and `by_month`
+/// (`2024-03`) maps, the journals without tags in `untagged` and the export date in `today`.
+pub struct TemplateExporter {
+    template: ExportTemplate,
+}
+
+impl TemplateExporter {
+    pub fn new(template: ExportTemplate) -> Self {
+        Self { template }
+    }
+}
+
+impl Exporter for TemplateExporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String> {
+        let source = fs::read_to_string(&self.template.path).map_err(|err| {
+            anyhow!(
+                "Failed to read template {}. Error info: {err}",
+                self.template.path.display()
+            )
+        })?;
+
+        // The extension enables the auto escaping for HTML templates.
+        let name = format!("{}.{}", self.template.name, self.template.extension);
+        render(&name, &source, entries)
+    }
+}
+
+fn render(name: &str, source: &str, entries: &[&Entry]) -> anyhow::Result<String> {
+    let template_entries: Vec<TemplateEntry> = entries
+        .iter()
+        .map(|entry| TemplateEntry::from(*entry))
+        .collect();
+
+    let mut by_tag: BTreeMap<&str, Vec<&TemplateEntry>> = BTreeMap::new();
+    let mut by_date: BTreeMap<&str, Vec<&TemplateEntry>> = BTreeMap::new();
+    let mut by_week: BTreeMap<String, Vec<&TemplateEntry>> = BTreeMap::new();
+    let mut by_month: BTreeMap<String, Vec<&TemplateEntry>> = BTreeMap::new();
+    let mut untagged = Vec::new();
+
+    for (entry, template_entry) in entries.iter().zip(template_entries.iter()) {
+        for tag in entry.tags.iter() {
+            by_tag.entry(tag).or_default().push(template_entry);
+        }
+        if entry.tags.is_empty() {
+            untagged.push(template_entry);
+        }
+
+        by_date
+            .entry(template_entry.date.as_str())
+            .or_default()
+            .push(template_entry);
+
+        let week = entry.date.iso_week();
+        by_week
+            .entry(format!("{}-W{:02}", week.year(), week.week()))
+            .or_default()
+            .push(template_entry);
+
+        by_month
+            .entry(entry.date.format("%Y-%m").to_string())
+            .or_default()
+            .push(template_entry);
+    }
+
+    let mut env = Environment::new();
+    env.add_template(name, source)
+        .map_err(|err| anyhow!("Failed to parse template '{name}'. Error info: {err}"))?;
+
+    let template = env.get_template(name)?;
+
+    template
+        .render(context! {
+            entries => template_entries,
+            by_tag => by_tag,
+            by_date => by_date,
+            by_week => by_week,
+            by_month => by_month,
+            untagged => untagged,
+            today => Utc::now().date_naive().to_string(),
+        })
+        .map_err(|err| anyhow!("Failed to render template '{name}'. Error info: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::exporter::sample_entries;
+
+    use super::*;
+
+    #[test]
+    fn template_names_and_extensions() {
+        let dir = tempfile::Builder::new()
+            .prefix("templates")
+            .tempdir()
+            .unwrap();
+        fs::write(dir.path().join("weekly.md.jinja"), "").unwrap();
+        fs::write(dir.path().join("standup.jinja"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let templates = load_templates(dir.path()).unwrap();
+
+        let found: Vec<(&str, &str)> = templates
+            .iter()
+            .map(|t| (t.name.as_str(), t.extension.as_str()))
+            .collect();
+        assert_eq!(found, [("standup", "txt"), ("weekly", "md")]);
+
+        let err = find_template(dir.path(), "monthly").unwrap_err();
+        assert!(err.to_string().contains("standup, weekly"));
+    }
+
+    #[test]
+    fn render_grouped_entries() {
+        let entries = sample_entries();
+        let entries: Vec<&Entry> = entries.iter().collect();
+        let source = "{% for tag, items in by_tag|items %}{{ tag }}: {% for e in items %}{{ e.title }}{% endfor %}\n{% endfor %}\
+            {% for week, items in by_week|items %}{{ week }} {{ items|length }}\n{% endfor %}\
+            untagged: {{ untagged|map(attribute='title')|join(', ') }}\n\
+            {{ entries[0].date }} {{ entries[0].priority }}";
+
+        let rendered = render("test", source, &entries).unwrap();
+
+        assert_eq!(
+            rendered,
+            "plans: Planning \"Q2\"\nwork: Planning \"Q2\"\n2024-W09 2\nuntagged: Evening\n2024-03-01 2"
+        );
+    }
+
+    #[test]
+    fn invalid_template_errors() {
+        let err = render("broken", "{% for %}", &[]).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("Failed to parse template 'broken'")
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use backend::Entry;
+
+use super::{Exporter, format_date};
+
+/// Writes the journals as a readable digest with underlined titles followed by their attributes
+/// and content.
+pub struct TextExporter;
+
+impl Exporter for TextExporter {
+    fn export(&self, entries: &[&Entry]) -> anyhow::Result<String> {
+        let mut output = String::new();
+
+        for entry in entries {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+
+            output.push_str(&entry.title);
+            output.push('\n');
+            output.push_str(&"=".repeat(entry.title.chars().count().max(1)));
+            output.push('\n');
+
+            let mut attributes = vec![format!("Date: {}", format_date(entry))];
+            if !entry.tags.is_empty() {
+                attributes.push(format!("Tags: {}", entry.tags.join(", ")));
+            }
+            if let Some(priority) = entry.priority {
+                attributes.push(format!("Priority: {priority}"));
+            }
+            output.push_str(&attributes.join(" | "));
+            output.push_str("\n\n");
+
+            output.push_str(&entry.content);
+            if !entry.content.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::exporter::sample_entries;
+
+    use super::*;
+
+    #[test]
+    fn digest_with_attributes() {
+        let entries = sample_entries();
+        let entries: Vec<&Entry> = entries.iter().collect();
+
+        let exported = TextExporter.export(&entries).unwrap();
+
+        assert_eq!(
+            exported,
+            "Planning \"Q2\"\n\
+            =============\n\
+            Date: 2024-03-01 | Tags: work, plans | Priority: 2\n\n\
+            # Goals\n\
+            - Ship <export>\n\
+            \n\
+            Evening\n\
+            =======\n\
+            Date: 2024-03-02\n\n\
+            Quiet day.\n"
+        );
+    }
+}
@@ -9,11 +9,40 @@ use crossterm::{
 use scopeguard::defer;
 use tokio::process::Command;
 
+use tempfile::NamedTempFile;
+
 use crate::settings::Settings;
 
 const ENV_EDITOR_OPTIONS: [&str; 2] = ["VISUAL", "EDITOR"];
+const TEMP_FILENAME: &str = "tui_journal";
+
+/// Creates the temporary file to edit the journal content in, with the configured extension.
+pub fn create_temp_file(settings: &Settings) -> anyhow::Result<NamedTempFile> {
+    let temp_extension = &settings.external_editor.temp_file_extension;
+
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(TEMP_FILENAME);
+
+    if !temp_extension.is_empty() {
+        builder.suffix(temp_extension);
+    };
 
+    Ok(builder.tempfile_in(env::temp_dir())?)
+}
+
+/// Opens the file in the editor while the TUI is running, leaving the alternate screen until
+/// the editor is closed.
 pub async fn open_editor(file_path: &Path, settings: &Settings) -> anyhow::Result<()> {
+    io::stdout().execute(LeaveAlternateScreen)?;
+    defer! {
+        io::stdout().execute(EnterAlternateScreen).unwrap();
+    }
+
+    run_editor(file_path, settings).await
+}
+
+/// Opens the file in the configured editor, waiting until it's closed.
+pub async fn run_editor(file_path: &Path, settings: &Settings) -> anyhow::Result<()> {
     if !file_path.exists() {
         bail!("file doesn't exist: {}", file_path.display());
     }
@@ -58,11 +87,6 @@ pub async fn open_editor(file_path: &Path, settings: &Settings) -> anyhow::Resul
 
     args.push(file_path.as_os_str());
 
-    io::stdout().execute(LeaveAlternateScreen)?;
-    defer! {
-        io::stdout().execute(EnterAlternateScreen).unwrap();
-    }
-
     Command::new(editor_cmd.clone())
         .args(args)
         .status()
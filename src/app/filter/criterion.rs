@@ -1,23 +1,81 @@
+use std::fmt::Display;
+
 use aho_corasick::AhoCorasick;
 use backend::Entry;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{date_range::RelativeDateRange, pattern::TextPattern};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FilterCriterion {
     Tag(TagFilterOption),
     Title(String),
     Content(String),
+    /// Matches the title with a whole word or regex pattern.
+    TitlePattern(TextPattern),
+    /// Matches the content with a whole word or regex pattern.
+    ContentPattern(TextPattern),
     Priority(u32),
+    /// Compares the priority of the entries with the given value. Entries without priority never
+    /// match.
+    PriorityComparison {
+        op: CompareOp,
+        value: u32,
+    },
+    /// Inclusive range of dates where any of the sides can be open.
+    DateRange {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    RelativeDate(RelativeDateRange),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Display for CompareOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompareOp::Equal => write!(f, "="),
+            CompareOp::NotEqual => write!(f, "!="),
+            CompareOp::Less => write!(f, "<"),
+            CompareOp::LessOrEqual => write!(f, "<="),
+            CompareOp::Greater => write!(f, ">"),
+            CompareOp::GreaterOrEqual => write!(f, ">="),
+        }
+    }
+}
+
+impl CompareOp {
+    pub fn compare<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            CompareOp::Equal => left == right,
+            CompareOp::NotEqual => left != right,
+            CompareOp::Less => left < right,
+            CompareOp::LessOrEqual => left <= right,
+            CompareOp::Greater => left > right,
+            CompareOp::GreaterOrEqual => left >= right,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TagFilterOption {
     Tag(String),
     NoTags,
 }
 
 impl FilterCriterion {
-    /// Checks if the entry meets the criterion
-    pub fn check_entry(&self, entry: &Entry) -> bool {
+    /// Checks if the entry meets the criterion, evaluating the relative dates from the given day.
+    pub fn check_entry(&self, entry: &Entry, today: NaiveDate) -> bool {
         match self {
             FilterCriterion::Tag(TagFilterOption::Tag(tag)) => entry.tags.contains(tag),
             FilterCriterion::Tag(TagFilterOption::NoTags) => entry.tags.is_empty(),
@@ -51,7 +109,20 @@ impl FilterCriterion {
                     ac.find(&entry.content).is_some()
                 }
             }
+            FilterCriterion::TitlePattern(pattern) => pattern.is_match(&entry.title),
+            FilterCriterion::ContentPattern(pattern) => pattern.is_match(&entry.content),
             FilterCriterion::Priority(prio) => entry.priority.is_some_and(|pr| pr == *prio),
+            FilterCriterion::PriorityComparison { op, value } => {
+                entry.priority.is_some_and(|pr| op.compare(pr, *value))
+            }
+            FilterCriterion::DateRange { from, to } => {
+                let date = entry.date.date_naive();
+                from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to)
+            }
+            FilterCriterion::RelativeDate(range) => {
+                let (from, to) = range.to_dates(today);
+                (from..=to).contains(&entry.date.date_naive())
+            }
         }
     }
 }
@@ -62,6 +133,10 @@ mod tests {
 
     use super::*;
 
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+    }
+
     fn sample_entry(tags: Vec<&str>, priority: Option<u32>) -> Entry {
         Entry::new(
             1,
@@ -78,10 +153,12 @@ mod tests {
         let entry = sample_entry(vec!["rust", "tests"], Some(2));
 
         assert!(
-            FilterCriterion::Tag(TagFilterOption::Tag(String::from("rust"))).check_entry(&entry)
+            FilterCriterion::Tag(TagFilterOption::Tag(String::from("rust")))
+                .check_entry(&entry, today())
         );
         assert!(
-            !FilterCriterion::Tag(TagFilterOption::Tag(String::from("Rust"))).check_entry(&entry)
+            !FilterCriterion::Tag(TagFilterOption::Tag(String::from("Rust")))
+                .check_entry(&entry, today())
         );
     }
 
@@ -90,34 +167,62 @@ mod tests {
         let entry = sample_entry(vec![], Some(1));
         let tagged_entry = sample_entry(vec!["tag"], Some(1));
 
-        assert!(FilterCriterion::Tag(TagFilterOption::NoTags).check_entry(&entry));
-        assert!(!FilterCriterion::Tag(TagFilterOption::NoTags).check_entry(&tagged_entry));
+        assert!(FilterCriterion::Tag(TagFilterOption::NoTags).check_entry(&entry, today()));
+        assert!(!FilterCriterion::Tag(TagFilterOption::NoTags).check_entry(&tagged_entry, today()));
     }
 
     #[test]
     fn title_search_uses_smart_case() {
         let entry = sample_entry(vec!["tag"], Some(4));
 
-        assert!(FilterCriterion::Title(String::from("rust")).check_entry(&entry));
-        assert!(FilterCriterion::Title(String::from("Rust")).check_entry(&entry));
-        assert!(!FilterCriterion::Title(String::from("SEARCH")).check_entry(&entry));
+        assert!(FilterCriterion::Title(String::from("rust")).check_entry(&entry, today()));
+        assert!(FilterCriterion::Title(String::from("Rust")).check_entry(&entry, today()));
+        assert!(!FilterCriterion::Title(String::from("SEARCH")).check_entry(&entry, today()));
     }
 
     #[test]
     fn content_search_uses_smart_case() {
         let entry = sample_entry(vec!["tag"], Some(4));
 
-        assert!(FilterCriterion::Content(String::from("content")).check_entry(&entry));
-        assert!(FilterCriterion::Content(String::from("Mixed")).check_entry(&entry));
+        assert!(FilterCriterion::Content(String::from("content")).check_entry(&entry, today()));
+        assert!(FilterCriterion::Content(String::from("Mixed")).check_entry(&entry, today()));
         assert!(
-            !FilterCriterion::Content(String::from("mixed")).check_entry(&Entry::new(
-                2,
-                entry.date,
-                entry.title.clone(),
-                String::from("UPPERCASE ONLY"),
-                entry.tags.clone(),
-                entry.priority
-            ))
+            !FilterCriterion::Content(String::from("mixed")).check_entry(
+                &Entry::new(
+                    2,
+                    entry.date,
+                    entry.title.clone(),
+                    String::from("UPPERCASE ONLY"),
+                    entry.tags.clone(),
+                    entry.priority
+                ),
+                today()
+            )
+        );
+    }
+
+    #[test]
+    fn patterns_match_title_and_content() {
+        use crate::app::filter::pattern::PatternMode;
+
+        let entry = sample_entry(vec!["tag"], Some(4));
+        let pattern = |text: &str, mode| TextPattern::new(text.to_owned(), mode).unwrap();
+
+        assert!(
+            FilterCriterion::TitlePattern(pattern("rust", PatternMode::WholeWord))
+                .check_entry(&entry, today())
+        );
+        assert!(
+            !FilterCriterion::TitlePattern(pattern("sear", PatternMode::WholeWord))
+                .check_entry(&entry, today())
+        );
+        assert!(
+            FilterCriterion::ContentPattern(pattern(r"^search\w+ content", PatternMode::Regex))
+                .check_entry(&entry, today())
+        );
+        assert!(
+            !FilterCriterion::ContentPattern(pattern(r"case$\s", PatternMode::Regex))
+                .check_entry(&entry, today())
         );
     }
 
@@ -125,6 +230,59 @@ mod tests {
     fn priority_none_never_matches() {
         let entry = sample_entry(vec!["tag"], None);
 
-        assert!(!FilterCriterion::Priority(3).check_entry(&entry));
+        assert!(!FilterCriterion::Priority(3).check_entry(&entry, today()));
+    }
+
+    #[test]
+    fn priority_comparison() {
+        let entry = sample_entry(vec!["tag"], Some(2));
+        let no_prio = sample_entry(vec!["tag"], None);
+        let cmp = |op, value| FilterCriterion::PriorityComparison { op, value };
+
+        assert!(cmp(CompareOp::GreaterOrEqual, 2).check_entry(&entry, today()));
+        assert!(!cmp(CompareOp::Greater, 2).check_entry(&entry, today()));
+        assert!(cmp(CompareOp::Less, 3).check_entry(&entry, today()));
+        assert!(cmp(CompareOp::NotEqual, 3).check_entry(&entry, today()));
+        assert!(!cmp(CompareOp::NotEqual, 3).check_entry(&no_prio, today()));
+    }
+
+    #[test]
+    fn date_range_is_inclusive() {
+        let entry = sample_entry(vec!["tag"], None);
+
+        let same_day = NaiveDate::from_ymd_opt(2024, 1, 2);
+        let next_day = NaiveDate::from_ymd_opt(2024, 1, 3);
+
+        let single_day = FilterCriterion::DateRange {
+            from: same_day,
+            to: same_day,
+        };
+        let open_start = FilterCriterion::DateRange {
+            from: None,
+            to: same_day,
+        };
+        let after = FilterCriterion::DateRange {
+            from: next_day,
+            to: None,
+        };
+
+        assert!(single_day.check_entry(&entry, today()));
+        assert!(open_start.check_entry(&entry, today()));
+        assert!(!after.check_entry(&entry, today()));
+    }
+
+    #[test]
+    fn relative_date_uses_given_day() {
+        let entry = sample_entry(vec!["tag"], None);
+        let today = entry.date.date_naive();
+
+        assert!(FilterCriterion::RelativeDate(RelativeDateRange::Today).check_entry(&entry, today));
+        assert!(
+            !FilterCriterion::RelativeDate(RelativeDateRange::Yesterday).check_entry(&entry, today)
+        );
+        assert!(
+            !FilterCriterion::RelativeDate(RelativeDateRange::Today)
+                .check_entry(&entry, today.succ_opt().unwrap())
+        );
     }
 }
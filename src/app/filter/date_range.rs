@@ -0,0 +1,363 @@
+use std::fmt::Display;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::FilterCriterion;
+
+/// Date formats accepted in date filters. The first one is used when formatting dates.
+const DATE_FORMATS: [&str; 2] = ["%d-%m-%Y", "%Y-%m-%d"];
+/// Separator between the start and the end of a date range.
+const RANGE_SEPARATOR: &str = "..";
+
+/// Date ranges relative to the current day which are evaluated each time they are checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelativeDateRange {
+    Today,
+    Yesterday,
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+    LastWeek,
+    LastMonth,
+    LastYear,
+    /// The given count of days including today.
+    LastDays(u32),
+    /// The given count of weeks counted back from today.
+    LastWeeks(u32),
+    /// The given count of months counted back from today.
+    LastMonths(u32),
+}
+
+impl RelativeDateRange {
+    /// Returns the inclusive start and end dates of the range relative to the given day. Ranges
+    /// reaching before the earliest supported date start from that date.
+    pub fn to_dates(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let days_before = |date: NaiveDate, days: u64| {
+            date.checked_sub_days(Days::new(days))
+                .unwrap_or(NaiveDate::MIN)
+        };
+        let months_before = |date: NaiveDate, months: u32| {
+            date.checked_sub_months(Months::new(months))
+                .unwrap_or(NaiveDate::MIN)
+        };
+        let day_before = |date: NaiveDate| days_before(date, 1);
+
+        let week_start = days_before(today, today.weekday().num_days_from_monday().into());
+        let month_start = today.with_day(1).expect("First day exists in all months");
+        let year_start = today
+            .with_ordinal(1)
+            .expect("First day exists in all years");
+
+        match self {
+            RelativeDateRange::Today => (today, today),
+            RelativeDateRange::Yesterday => {
+                let yesterday = day_before(today);
+                (yesterday, yesterday)
+            }
+            RelativeDateRange::ThisWeek => (week_start, week_start + Days::new(6)),
+            RelativeDateRange::ThisMonth => (month_start, day_before(month_start + Months::new(1))),
+            RelativeDateRange::ThisYear => (year_start, day_before(year_start + Months::new(12))),
+            RelativeDateRange::LastWeek => (days_before(week_start, 7), day_before(week_start)),
+            RelativeDateRange::LastMonth => {
+                (months_before(month_start, 1), day_before(month_start))
+            }
+            RelativeDateRange::LastYear => (months_before(year_start, 12), day_before(year_start)),
+            RelativeDateRange::LastDays(days) => {
+                (days_before(today, days.saturating_sub(1).into()), today)
+            }
+            RelativeDateRange::LastWeeks(weeks) => {
+                (days_before(today, u64::from(weeks) * 7), today)
+            }
+            RelativeDateRange::LastMonths(months) => (months_before(today, months), today),
+        }
+    }
+}
+
+impl Display for RelativeDateRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelativeDateRange::Today => write!(f, "today"),
+            RelativeDateRange::Yesterday => write!(f, "yesterday"),
+            RelativeDateRange::ThisWeek => write!(f, "this week"),
+            RelativeDateRange::ThisMonth => write!(f, "this month"),
+            RelativeDateRange::ThisYear => write!(f, "this year"),
+            RelativeDateRange::LastWeek => write!(f, "last week"),
+            RelativeDateRange::LastMonth => write!(f, "last month"),
+            RelativeDateRange::LastYear => write!(f, "last year"),
+            RelativeDateRange::LastDays(days) => write!(f, "last {days} days"),
+            RelativeDateRange::LastWeeks(weeks) => write!(f, "last {weeks} weeks"),
+            RelativeDateRange::LastMonths(months) => write!(f, "last {months} months"),
+        }
+    }
+}
+
+/// Parses the text of a date filter into a date criterion.
+///
+/// Supported forms are:
+/// - Single day: `dd-mm-yyyy` or `yyyy-mm-dd`
+/// - Ranges: `<date>..<date>` where any of the sides can be omitted for open ranges. The forms
+///   `since <date>` and `until <date>` are accepted too.
+/// - Relative expressions: `today`, `yesterday`, `this week|month|year`,
+///   `last week|month|year` and `last <N> days|weeks|months`
+pub fn parse_date_filter(text: &str) -> Result<FilterCriterion, String> {
+    let text = text.trim().to_lowercase();
+
+    if let Some(relative) = parse_relative(&text)? {
+        return Ok(FilterCriterion::RelativeDate(relative));
+    }
+
+    if let Some(date) = text.strip_prefix("since ") {
+        return Ok(FilterCriterion::DateRange {
+            from: Some(parse_date(date)?),
+            to: None,
+        });
+    }
+
+    if let Some(date) = text.strip_prefix("until ") {
+        return Ok(FilterCriterion::DateRange {
+            from: None,
+            to: Some(parse_date(date)?),
+        });
+    }
+
+    let (from, to) = match text.split_once(RANGE_SEPARATOR) {
+        Some((from, to)) => {
+            let from = parse_optional_date(from)?;
+            let to = parse_optional_date(to)?;
+            if from.is_none() && to.is_none() {
+                return Err(String::from("At least one side of the range must be set"));
+            }
+            (from, to)
+        }
+        None => {
+            let date = parse_date(&text)?;
+            (Some(date), Some(date))
+        }
+    };
+
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err(String::from("Range start can't be after its end"));
+    }
+
+    Ok(FilterCriterion::DateRange { from, to })
+}
+
+/// Formats the date criterion to text which can be parsed again with [`parse_date_filter()`].
+pub fn format_date_filter(from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    let format = |date: Option<NaiveDate>| {
+        date.map(|d| d.format(DATE_FORMATS[0]).to_string())
+            .unwrap_or_default()
+    };
+
+    match (from, to) {
+        (Some(from), Some(to)) if from == to => format(Some(from)),
+        _ => format!("{}{RANGE_SEPARATOR}{}", format(from), format(to)),
+    }
+}
+
+fn parse_relative(text: &str) -> Result<Option<RelativeDateRange>, String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    let range = match words.as_slice() {
+        ["today"] => RelativeDateRange::Today,
+        ["yesterday"] => RelativeDateRange::Yesterday,
+        ["this", "week"] => RelativeDateRange::ThisWeek,
+        ["this", "month"] => RelativeDateRange::ThisMonth,
+        ["this", "year"] => RelativeDateRange::ThisYear,
+        ["last", "week"] => RelativeDateRange::LastWeek,
+        ["last", "month"] => RelativeDateRange::LastMonth,
+        ["last", "year"] => RelativeDateRange::LastYear,
+        ["last", count, unit] => {
+            let count: u32 = count
+                .parse()
+                .map_err(|_| format!("Invalid count '{count}'"))?;
+            if count == 0 {
+                return Err(String::from("Count must be greater than zero"));
+            }
+            match *unit {
+                "day" | "days" => RelativeDateRange::LastDays(count),
+                "week" | "weeks" => RelativeDateRange::LastWeeks(count),
+                "month" | "months" => RelativeDateRange::LastMonths(count),
+                unit => return Err(format!("Unknown unit '{unit}'")),
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(range))
+}
+
+fn parse_optional_date(text: &str) -> Result<Option<NaiveDate>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        Ok(None)
+    } else {
+        parse_date(text).map(Some)
+    }
+}
+
+/// Parses the date in one of the formats accepted in date filters.
+pub fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    let text = text.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        .ok_or_else(|| format!("Invalid date '{text}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parse_single_day() {
+        let expected = FilterCriterion::DateRange {
+            from: Some(date(2024, 3, 5)),
+            to: Some(date(2024, 3, 5)),
+        };
+
+        assert_eq!(parse_date_filter("05-03-2024"), Ok(expected.clone()));
+        assert_eq!(parse_date_filter(" 2024-03-05 "), Ok(expected));
+    }
+
+    #[test]
+    fn parse_ranges() {
+        assert_eq!(
+            parse_date_filter("01-03-2024..2024-03-31"),
+            Ok(FilterCriterion::DateRange {
+                from: Some(date(2024, 3, 1)),
+                to: Some(date(2024, 3, 31)),
+            })
+        );
+        assert_eq!(
+            parse_date_filter("since 01-03-2024"),
+            Ok(FilterCriterion::DateRange {
+                from: Some(date(2024, 3, 1)),
+                to: None,
+            })
+        );
+        assert_eq!(
+            parse_date_filter("..01-03-2024"),
+            Ok(FilterCriterion::DateRange {
+                from: None,
+                to: Some(date(2024, 3, 1)),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_relative_expressions() {
+        assert_eq!(
+            parse_date_filter("Last 7 days"),
+            Ok(FilterCriterion::RelativeDate(RelativeDateRange::LastDays(
+                7
+            )))
+        );
+        assert_eq!(
+            parse_date_filter("this month"),
+            Ok(FilterCriterion::RelativeDate(RelativeDateRange::ThisMonth))
+        );
+        assert_eq!(
+            parse_date_filter("last 2 weeks"),
+            Ok(FilterCriterion::RelativeDate(RelativeDateRange::LastWeeks(
+                2
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_inputs() {
+        assert!(parse_date_filter("32-01-2024").is_err());
+        assert!(parse_date_filter("..").is_err());
+        assert!(parse_date_filter("02-01-2024..01-01-2024").is_err());
+        assert!(parse_date_filter("last 0 days").is_err());
+        assert!(parse_date_filter("last 3 decades").is_err());
+        assert!(parse_date_filter("next week").is_err());
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let inputs = [
+            (Some(date(2024, 3, 1)), Some(date(2024, 3, 1))),
+            (Some(date(2024, 3, 1)), Some(date(2024, 4, 1))),
+            (None, Some(date(2024, 4, 1))),
+            (Some(date(2024, 3, 1)), None),
+        ];
+
+        for (from, to) in inputs {
+            let text = format_date_filter(from, to);
+            assert_eq!(
+                parse_date_filter(&text),
+                Ok(FilterCriterion::DateRange { from, to })
+            );
+        }
+
+        for relative in [RelativeDateRange::LastDays(7), RelativeDateRange::ThisWeek] {
+            assert_eq!(
+                parse_date_filter(&relative.to_string()),
+                Ok(FilterCriterion::RelativeDate(relative))
+            );
+        }
+    }
+
+    #[test]
+    fn relative_ranges_dates() {
+        // Wednesday
+        let today = date(2024, 3, 13);
+
+        assert_eq!(
+            RelativeDateRange::LastDays(7).to_dates(today),
+            (date(2024, 3, 7), today)
+        );
+        assert_eq!(
+            RelativeDateRange::ThisWeek.to_dates(today),
+            (date(2024, 3, 11), date(2024, 3, 17))
+        );
+        assert_eq!(
+            RelativeDateRange::LastWeek.to_dates(today),
+            (date(2024, 3, 4), date(2024, 3, 10))
+        );
+        assert_eq!(
+            RelativeDateRange::ThisMonth.to_dates(today),
+            (date(2024, 3, 1), date(2024, 3, 31))
+        );
+        assert_eq!(
+            RelativeDateRange::LastMonth.to_dates(today),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+        assert_eq!(
+            RelativeDateRange::LastYear.to_dates(today),
+            (date(2023, 1, 1), date(2023, 12, 31))
+        );
+        assert_eq!(
+            RelativeDateRange::Yesterday.to_dates(today),
+            (date(2024, 3, 12), date(2024, 3, 12))
+        );
+    }
+
+    #[test]
+    fn huge_relative_ranges_start_at_min_date() {
+        let today = date(2024, 3, 13);
+        let criterion = parse_date_filter("last 999999999 days").unwrap();
+        let FilterCriterion::RelativeDate(range) = criterion else {
+            panic!("Relative range expected");
+        };
+
+        assert_eq!(range.to_dates(today), (NaiveDate::MIN, today));
+        assert_eq!(
+            RelativeDateRange::LastWeeks(u32::MAX).to_dates(today),
+            (NaiveDate::MIN, today)
+        );
+        assert_eq!(
+            RelativeDateRange::LastMonths(u32::MAX).to_dates(today),
+            (NaiveDate::MIN, today)
+        );
+    }
+}
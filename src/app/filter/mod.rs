@@ -1,20 +1,29 @@
 use backend::Entry;
+use chrono::NaiveDate;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub mod criterion;
+pub mod date_range;
+pub mod pattern;
+pub mod query;
 
 pub use criterion::FilterCriterion;
+pub use query::FilterQuery;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CriteriaRelation {
     And,
     Or,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Filter {
     pub relation: CriteriaRelation,
     pub criteria: Vec<FilterCriterion>,
+    /// Query expression which must be met on top of the criteria.
+    #[serde(default)]
+    pub query: Option<FilterQuery>,
 }
 
 impl Default for Filter {
@@ -22,16 +31,44 @@ impl Default for Filter {
         Filter {
             relation: CriteriaRelation::And,
             criteria: Vec::new(),
+            query: None,
         }
     }
 }
 
 impl Filter {
-    /// Checks if the entry meets the filter criteria
-    pub fn check_entry(&self, entry: &Entry) -> bool {
-        match self.relation {
-            CriteriaRelation::And => self.criteria.par_iter().all(|cr| cr.check_entry(entry)),
-            CriteriaRelation::Or => self.criteria.par_iter().any(|cr| cr.check_entry(entry)),
-        }
+    /// Checks if the entry meets the filter criteria, evaluating the relative dates from the given
+    /// day, which is computed once for all entries when the filter is applied.
+    pub fn check_entry(&self, entry: &Entry, today: NaiveDate) -> bool {
+        let meets_criteria = self.criteria.is_empty()
+            || match self.relation {
+                CriteriaRelation::And => self
+                    .criteria
+                    .par_iter()
+                    .all(|cr| cr.check_entry(entry, today)),
+                CriteriaRelation::Or => self
+                    .criteria
+                    .par_iter()
+                    .any(|cr| cr.check_entry(entry, today)),
+            };
+
+        meets_criteria
+            && self
+                .query
+                .as_ref()
+                .is_none_or(|q| q.check_entry(entry, today))
+    }
+}
+
+/// Named filter saved by the user to be applied again later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub filter: Filter,
+}
+
+impl FilterPreset {
+    pub fn new(name: String, filter: Filter) -> Self {
+        Self { name, filter }
     }
 }
@@ -0,0 +1,216 @@
+use std::fmt::Display;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Modes to match text in title and content in addition to the default smart-case substring
+/// search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PatternMode {
+    /// Matches the text as whole words only.
+    WholeWord,
+    /// Matches the text as regular expression.
+    Regex,
+}
+
+impl Display for PatternMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternMode::WholeWord => write!(f, "Whole word"),
+            PatternMode::Regex => write!(f, "Regex"),
+        }
+    }
+}
+
+/// Text pattern compiled once on creation to be matched against many entries.
+/// Matching is smart-case: it's case-insensitive unless the pattern has uppercase characters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "PatternSpec", into = "PatternSpec")]
+pub struct TextPattern {
+    pattern: String,
+    mode: PatternMode,
+    regex: Regex,
+}
+
+/// Serialized form of [`TextPattern`] without the compiled expression.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PatternSpec {
+    pattern: String,
+    mode: PatternMode,
+}
+
+impl TextPattern {
+    pub fn new(pattern: String, mode: PatternMode) -> Result<Self, String> {
+        let expression = match mode {
+            PatternMode::WholeWord => {
+                // Word boundaries can only be matched next to word characters.
+                let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+                let boundary = |c: Option<char>| {
+                    if c.is_some_and(is_word_char) {
+                        r"\b"
+                    } else {
+                        ""
+                    }
+                };
+                format!(
+                    "{}{}{}",
+                    boundary(pattern.chars().next()),
+                    regex::escape(&pattern),
+                    boundary(pattern.chars().last())
+                )
+            }
+            PatternMode::Regex => pattern.clone(),
+        };
+
+        let case_insensitive = match mode {
+            PatternMode::WholeWord => !pattern.chars().any(|c| c.is_uppercase()),
+            PatternMode::Regex => !regex_has_uppercase(&pattern),
+        };
+
+        let regex = RegexBuilder::new(&expression)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|err| format_regex_error(&err))?;
+
+        Ok(Self {
+            pattern,
+            mode,
+            regex,
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn mode(&self) -> PatternMode {
+        self.mode
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+/// Checks the regular expression for uppercase characters ignoring the escape sequences like `\W`
+/// or `\p{Lu}`, so they don't turn off the case-insensitive matching.
+pub(crate) fn regex_has_uppercase(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            // Skip the escaped char, and the braces of the unicode classes like `\p{Lu}`.
+            if matches!(chars.next(), Some('p' | 'P')) && chars.next() == Some('{') {
+                chars.by_ref().find(|c| *c == '}');
+            }
+        } else if c.is_uppercase() {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Formats regex errors to fit in one line.
+pub fn format_regex_error(err: &regex::Error) -> String {
+    match err {
+        regex::Error::Syntax(msg) => {
+            // Syntax errors are multi-line with the pattern and a pointer to the error
+            // position. Keep the description line only.
+            let description = msg.lines().last().unwrap_or_default().trim();
+            format!("Invalid pattern: {description}")
+        }
+        err => format!("Invalid pattern: {err}"),
+    }
+}
+
+impl PartialEq for TextPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern && self.mode == other.mode
+    }
+}
+
+impl Eq for TextPattern {}
+
+impl TryFrom<PatternSpec> for TextPattern {
+    type Error = String;
+
+    fn try_from(spec: PatternSpec) -> Result<Self, Self::Error> {
+        TextPattern::new(spec.pattern, spec.mode)
+    }
+}
+
+impl From<TextPattern> for PatternSpec {
+    fn from(pattern: TextPattern) -> Self {
+        PatternSpec {
+            pattern: pattern.pattern,
+            mode: pattern.mode,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_word_matches_words_only() {
+        let pattern = TextPattern::new(String::from("rust"), PatternMode::WholeWord).unwrap();
+
+        assert!(pattern.is_match("Learning Rust today"));
+        assert!(pattern.is_match("rust."));
+        assert!(!pattern.is_match("Rusty tools"));
+        assert!(!pattern.is_match("trust"));
+    }
+
+    #[test]
+    fn whole_word_escapes_special_chars() {
+        let pattern = TextPattern::new(String::from("c++"), PatternMode::WholeWord).unwrap();
+
+        assert!(pattern.is_match("Using c++ here"));
+        assert!(!pattern.is_match("Using cpp here"));
+    }
+
+    #[test]
+    fn regex_uses_smart_case() {
+        let lower = TextPattern::new(String::from(r"inc\w+ \d+"), PatternMode::Regex).unwrap();
+        assert!(lower.is_match("INCIDENT 42"));
+
+        let upper = TextPattern::new(String::from(r"Inc\w+"), PatternMode::Regex).unwrap();
+        assert!(upper.is_match("Incident"));
+        assert!(!upper.is_match("incident"));
+    }
+
+    #[test]
+    fn regex_escapes_keep_smart_case_insensitive() {
+        let pattern =
+            TextPattern::new(String::from(r"fix\W\p{Lu}\pL"), PatternMode::Regex).unwrap();
+        assert!(pattern.is_match("fix Bug"));
+        assert!(pattern.is_match("FIX BUG"));
+
+        let escaped_upper = TextPattern::new(String::from(r"\.Net"), PatternMode::Regex).unwrap();
+        assert!(escaped_upper.is_match(".Net"));
+        assert!(!escaped_upper.is_match(".net"));
+    }
+
+    #[test]
+    fn invalid_regex_returns_error() {
+        let err = TextPattern::new(String::from("(unclosed"), PatternMode::Regex).unwrap_err();
+
+        assert!(err.starts_with("Invalid pattern"));
+        assert!(!err.contains('\n'));
+    }
+
+    #[test]
+    fn serde_round_trip_compiles_pattern() {
+        let pattern = TextPattern::new(String::from(r"\d+"), PatternMode::Regex).unwrap();
+
+        let json = serde_json::to_string(&pattern).unwrap();
+        let loaded: TextPattern = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, pattern);
+        assert!(loaded.is_match("42"));
+
+        let invalid = r#"{"pattern": "(", "mode": "Regex"}"#;
+        assert!(serde_json::from_str::<TextPattern>(invalid).is_err());
+    }
+}
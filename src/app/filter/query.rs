@@ -0,0 +1,560 @@
+use std::{fmt::Display, iter::Peekable, str::CharIndices, str::FromStr};
+
+use backend::Entry;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    FilterCriterion,
+    criterion::{CompareOp, TagFilterOption},
+    date_range::parse_date_filter,
+};
+
+/// Filter query parsed from a text like
+/// `(tag:work OR tag:oncall) AND NOT tag:done AND priority>=2 AND title:"incident"`.
+///
+/// Supported fields are `tag`, `title`, `content`, `priority` and `date`, where `priority` and
+/// `date` support the comparison operators `=`, `!=`, `<`, `<=`, `>` and `>=` too.
+/// Terms without a field are searched in both title and content.
+/// Terms can be combined with `AND`, `OR`, `NOT` and grouped with parentheses, where `AND` is
+/// implied between terms if no operator is given.
+///
+/// Queries are serialized as their text and parsed again on deserializing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FilterQuery {
+    text: String,
+    expression: FilterExpr,
+}
+
+/// Expression tree of filter criteria.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterExpr {
+    Criterion(FilterCriterion),
+    Not(Box<FilterExpr>),
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+}
+
+impl FilterExpr {
+    /// Checks if the entry meets the expression, evaluating the relative dates from the given day.
+    pub fn check_entry(&self, entry: &Entry, today: NaiveDate) -> bool {
+        match self {
+            FilterExpr::Criterion(criterion) => criterion.check_entry(entry, today),
+            FilterExpr::Not(expr) => !expr.check_entry(entry, today),
+            FilterExpr::And(exprs) => exprs.iter().all(|expr| expr.check_entry(entry, today)),
+            FilterExpr::Or(exprs) => exprs.iter().any(|expr| expr.check_entry(entry, today)),
+        }
+    }
+}
+
+impl FilterQuery {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Checks if the entry meets the query, evaluating the relative dates from the given day.
+    pub fn check_entry(&self, entry: &Entry, today: NaiveDate) -> bool {
+        self.expression.check_entry(entry, today)
+    }
+}
+
+impl FromStr for FilterQuery {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            return Err(String::from("Query is empty"));
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let expression = parser.parse_or()?;
+
+        if let Some(token) = parser.peek() {
+            return Err(format!("Unexpected {token}"));
+        }
+
+        Ok(Self {
+            text: text.trim().to_owned(),
+            expression,
+        })
+    }
+}
+
+impl TryFrom<String> for FilterQuery {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<FilterQuery> for String {
+    fn from(query: FilterQuery) -> Self {
+        query.text
+    }
+}
+
+impl Display for FilterQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Parses the given query text. This is used as value parser for the CLI arguments.
+pub fn parse_filter_query(text: &str) -> Result<FilterQuery, String> {
+    text.parse()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    And,
+    Or,
+    Not,
+    /// A field with comparison to a value, like `priority>=2`
+    Field {
+        name: String,
+        op: Option<CompareOp>,
+        value: String,
+    },
+    /// Free text without a field name.
+    Text(String),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::OpenParen => write!(f, "'('"),
+            Token::CloseParen => write!(f, "')'"),
+            Token::And => write!(f, "'AND'"),
+            Token::Or => write!(f, "'OR'"),
+            Token::Not => write!(f, "'NOT'"),
+            Token::Field { name, value, .. } => write!(f, "'{name}' with value '{value}'"),
+            Token::Text(text) => write!(f, "'{text}'"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(_, ch)) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParen);
+            }
+            '"' => tokens.push(Token::Text(read_value(&mut chars)?)),
+            _ => {
+                let name: String = read_while(&mut chars, |c| c.is_alphanumeric() || c == '_');
+                let op = read_operator(&mut chars);
+
+                let token = match op {
+                    Some(op) => Token::Field {
+                        name: name.to_lowercase(),
+                        op,
+                        value: read_value(&mut chars)?,
+                    },
+                    None => {
+                        // Words can contain other characters like `-` or `.` too.
+                        let rest =
+                            read_while(&mut chars, |c| !c.is_whitespace() && c != '(' && c != ')');
+                        let word = format!("{name}{rest}");
+                        match word.to_uppercase().as_str() {
+                            "AND" => Token::And,
+                            "OR" => Token::Or,
+                            "NOT" => Token::Not,
+                            _ => Token::Text(word),
+                        }
+                    }
+                };
+
+                tokens.push(token);
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn read_while(chars: &mut Peekable<CharIndices>, predicate: impl Fn(char) -> bool) -> String {
+    let mut result = String::new();
+    while let Some(&(_, c)) = chars.peek() {
+        if !predicate(c) {
+            break;
+        }
+        result.push(c);
+        chars.next();
+    }
+
+    result
+}
+
+/// Reads field operator returning `Some(None)` for the field separator `:`.
+/// Nothing is consumed if there is no operator, keeping a lone `!` as part of the word.
+fn read_operator(chars: &mut Peekable<CharIndices>) -> Option<Option<CompareOp>> {
+    let (_, first) = *chars.peek()?;
+    let op = match first {
+        ':' => None,
+        '=' => Some(CompareOp::Equal),
+        '!' => {
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            if lookahead.peek().is_none_or(|(_, c)| *c != '=') {
+                return None;
+            }
+            Some(CompareOp::NotEqual)
+        }
+        '<' => Some(CompareOp::Less),
+        '>' => Some(CompareOp::Greater),
+        _ => return None,
+    };
+    chars.next();
+
+    let op = match (op, chars.peek().map(|(_, c)| *c)) {
+        (Some(CompareOp::Less), Some('=')) => Some(CompareOp::LessOrEqual),
+        (Some(CompareOp::Greater), Some('=')) => Some(CompareOp::GreaterOrEqual),
+        (Some(CompareOp::NotEqual), Some('=')) => Some(CompareOp::NotEqual),
+        (op, _) => return Some(op),
+    };
+    chars.next();
+
+    Some(op)
+}
+
+/// Reads a value which could be quoted to contain white spaces and parentheses.
+fn read_value(chars: &mut Peekable<CharIndices>) -> Result<String, String> {
+    if chars.peek().is_some_and(|(_, c)| *c == '"') {
+        let (start, _) = chars.next().expect("Quote is peeked");
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, c)) => value.push(c),
+                    None => break,
+                },
+                Some((_, '"')) => return Ok(value),
+                Some((_, c)) => value.push(c),
+                None => break,
+            }
+        }
+        Err(format!("Unclosed quote starting at position {}", start + 1))
+    } else {
+        let value = read_while(chars, |c| !c.is_whitespace() && c != '(' && c != ')');
+        if value.is_empty() {
+            Err(String::from("Missing value after field"))
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, String> {
+        let mut exprs = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            exprs.push(self.parse_and()?);
+        }
+
+        Ok(Self::combine(exprs, FilterExpr::Or))
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, String> {
+        let mut exprs = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                    exprs.push(self.parse_unary()?);
+                }
+                // Implicit AND between terms.
+                Some(Token::Not | Token::OpenParen | Token::Field { .. } | Token::Text(_)) => {
+                    exprs.push(self.parse_unary()?);
+                }
+                _ => break,
+            }
+        }
+
+        Ok(Self::combine(exprs, FilterExpr::And))
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(FilterExpr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::OpenParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::CloseParen) => Ok(expr),
+                    _ => Err(String::from("Missing closing parenthesis")),
+                }
+            }
+            Some(Token::Field { name, op, value }) => parse_field(&name, op, value),
+            Some(Token::Text(text)) => Ok(FilterExpr::Or(vec![
+                FilterExpr::Criterion(FilterCriterion::Title(text.clone())),
+                FilterExpr::Criterion(FilterCriterion::Content(text)),
+            ])),
+            Some(token) => Err(format!("Unexpected {token}")),
+            None => Err(String::from("Unexpected end of query")),
+        }
+    }
+
+    fn combine(mut exprs: Vec<FilterExpr>, op: fn(Vec<FilterExpr>) -> FilterExpr) -> FilterExpr {
+        if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            op(exprs)
+        }
+    }
+}
+
+fn parse_field(name: &str, op: Option<CompareOp>, value: String) -> Result<FilterExpr, String> {
+    let criterion = match (name, op) {
+        ("tag" | "tags", None | Some(CompareOp::Equal)) => {
+            FilterCriterion::Tag(TagFilterOption::Tag(value))
+        }
+        ("tag" | "tags", Some(CompareOp::NotEqual)) => {
+            return Ok(FilterExpr::Not(Box::new(FilterExpr::Criterion(
+                FilterCriterion::Tag(TagFilterOption::Tag(value)),
+            ))));
+        }
+        ("title", None) => FilterCriterion::Title(value),
+        ("content", None) => FilterCriterion::Content(value),
+        ("priority" | "prio", op) => {
+            let priority = value
+                .parse()
+                .map_err(|_| format!("Priority must be a positive number. Found '{value}'"))?;
+            match op {
+                None | Some(CompareOp::Equal) => FilterCriterion::Priority(priority),
+                Some(op) => FilterCriterion::PriorityComparison {
+                    op,
+                    value: priority,
+                },
+            }
+        }
+        ("date", None | Some(CompareOp::Equal)) => parse_date_filter(&value)?,
+        ("date", Some(op)) => {
+            let FilterCriterion::DateRange {
+                from: Some(start),
+                to: Some(end),
+            } = parse_date_filter(&value)?
+            else {
+                return Err(format!(
+                    "Date comparisons need a single date. Found '{value}'"
+                ));
+            };
+            let (from, to) = match op {
+                CompareOp::Less => {
+                    let to = start
+                        .pred_opt()
+                        .ok_or_else(|| format!("There are no dates before '{value}'"))?;
+                    (None, Some(to))
+                }
+                CompareOp::LessOrEqual => (None, Some(end)),
+                CompareOp::Greater => {
+                    let from = end
+                        .succ_opt()
+                        .ok_or_else(|| format!("There are no dates after '{value}'"))?;
+                    (Some(from), None)
+                }
+                CompareOp::GreaterOrEqual => (Some(start), None),
+                CompareOp::NotEqual => {
+                    return Ok(FilterExpr::Not(Box::new(FilterExpr::Criterion(
+                        FilterCriterion::DateRange {
+                            from: Some(start),
+                            to: Some(end),
+                        },
+                    ))));
+                }
+                CompareOp::Equal => unreachable!("Equal is handled in the branch above"),
+            };
+            FilterCriterion::DateRange { from, to }
+        }
+        ("tag" | "tags" | "title" | "content", Some(_)) => {
+            return Err(format!("Field '{name}' supports ':' only"));
+        }
+        (name, _) => return Err(format!("Unknown field '{name}'")),
+    };
+
+    Ok(FilterExpr::Criterion(criterion))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    use super::*;
+
+    fn tag(tag: &str) -> FilterExpr {
+        FilterExpr::Criterion(FilterCriterion::Tag(TagFilterOption::Tag(tag.into())))
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+    }
+
+    fn entry(title: &str, tags: &[&str], priority: Option<u32>) -> Entry {
+        Entry::new(
+            1,
+            Utc.with_ymd_and_hms(2024, 3, 5, 10, 0, 0).unwrap(),
+            title.into(),
+            String::from("Some content"),
+            tags.iter().map(|t| t.to_string()).collect(),
+            priority,
+        )
+    }
+
+    #[test]
+    fn parse_full_example() {
+        let query: FilterQuery =
+            r#"(tag:work OR tag:oncall) AND NOT tag:done AND priority>=2 AND title:"incident""#
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            &query.expression,
+            &FilterExpr::And(vec![
+                FilterExpr::Or(vec![tag("work"), tag("oncall")]),
+                FilterExpr::Not(Box::new(tag("done"))),
+                FilterExpr::Criterion(FilterCriterion::PriorityComparison {
+                    op: CompareOp::GreaterOrEqual,
+                    value: 2
+                }),
+                FilterExpr::Criterion(FilterCriterion::Title(String::from("incident"))),
+            ])
+        );
+    }
+
+    #[test]
+    fn and_binds_stronger_than_or() {
+        let query: FilterQuery = "tag:a OR tag:b tag:c".parse().unwrap();
+
+        assert_eq!(
+            &query.expression,
+            &FilterExpr::Or(vec![tag("a"), FilterExpr::And(vec![tag("b"), tag("c")])])
+        );
+    }
+
+    #[test]
+    fn parse_quoted_values_and_free_text() {
+        let query: FilterQuery = r#"title:"Server (prod) \"down\"" outage"#.parse().unwrap();
+
+        assert_eq!(
+            &query.expression,
+            &FilterExpr::And(vec![
+                FilterExpr::Criterion(FilterCriterion::Title(String::from(
+                    r#"Server (prod) "down""#
+                ))),
+                FilterExpr::Or(vec![
+                    FilterExpr::Criterion(FilterCriterion::Title(String::from("outage"))),
+                    FilterExpr::Criterion(FilterCriterion::Content(String::from("outage"))),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn lone_exclamation_mark_stays_in_words() {
+        let query: FilterQuery = "wow! priority!=2".parse().unwrap();
+
+        assert_eq!(
+            &query.expression,
+            &FilterExpr::And(vec![
+                FilterExpr::Or(vec![
+                    FilterExpr::Criterion(FilterCriterion::Title(String::from("wow!"))),
+                    FilterExpr::Criterion(FilterCriterion::Content(String::from("wow!"))),
+                ]),
+                FilterExpr::Criterion(FilterCriterion::PriorityComparison {
+                    op: CompareOp::NotEqual,
+                    value: 2,
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_date_fields() {
+        let query: FilterQuery = r#"date>=01-03-2024 date:"last 7 days""#.parse().unwrap();
+
+        let FilterExpr::And(exprs) = &query.expression else {
+            panic!("Expected AND expression");
+        };
+        assert_eq!(
+            exprs[0],
+            FilterExpr::Criterion(FilterCriterion::DateRange {
+                from: NaiveDate::from_ymd_opt(2024, 3, 1),
+                to: None
+            })
+        );
+        assert!(matches!(
+            exprs[1],
+            FilterExpr::Criterion(FilterCriterion::RelativeDate(_))
+        ));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("".parse::<FilterQuery>().is_err());
+        assert!("(tag:a".parse::<FilterQuery>().is_err());
+        assert!("tag:a)".parse::<FilterQuery>().is_err());
+        assert!("tag:a AND".parse::<FilterQuery>().is_err());
+        assert!("priority>=high".parse::<FilterQuery>().is_err());
+        assert!("title>2".parse::<FilterQuery>().is_err());
+        assert!("author:me".parse::<FilterQuery>().is_err());
+        assert!(r#"title:"unclosed"#.parse::<FilterQuery>().is_err());
+
+        let min_date = NaiveDate::MIN.format("%Y-%m-%d");
+        let max_date = NaiveDate::MAX.format("%Y-%m-%d");
+        assert!(format!("date<{min_date}").parse::<FilterQuery>().is_err());
+        assert!(format!("date>{max_date}").parse::<FilterQuery>().is_err());
+        assert!(format!("date>={min_date}").parse::<FilterQuery>().is_ok());
+        assert!(format!("date<={max_date}").parse::<FilterQuery>().is_ok());
+    }
+
+    #[test]
+    fn check_entries() {
+        let query: FilterQuery = "(tag:work OR tag:oncall) AND NOT tag:done AND priority>=2"
+            .parse()
+            .unwrap();
+
+        assert!(query.check_entry(&entry("Incident", &["work"], Some(3)), today()));
+        assert!(!query.check_entry(&entry("Incident", &["work", "done"], Some(3)), today()));
+        assert!(!query.check_entry(&entry("Incident", &["oncall"], Some(1)), today()));
+        assert!(!query.check_entry(&entry("Incident", &["oncall"], None), today()));
+        assert!(!query.check_entry(&entry("Incident", &["home"], Some(3)), today()));
+    }
+
+    #[test]
+    fn keywords_are_case_insensitive() {
+        let upper: FilterQuery = "tag:a OR NOT tag:b".parse().unwrap();
+        let lower: FilterQuery = "tag:a or not tag:b".parse().unwrap();
+
+        assert_eq!(upper.expression, lower.expression);
+    }
+}
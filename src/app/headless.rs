@@ -0,0 +1,457 @@
+//! Execution of the CLI commands which use the back-end without starting the TUI.
+
+use std::{
+    io::{IsTerminal, Read, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, bail, ensure};
+use backend::{DataProvider, Entry};
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Utc};
+
+use crate::cli::{CliExportFormat, CliSortCriteria, HeadlessCliCommand, ImportSource, ListArgs};
+
+use super::{
+    App, HistoryStack,
+    exporter::{ExportFormat, find_template},
+    external_editor,
+    filter::{CriteriaRelation, Filter, FilterCriterion, FilterQuery, criterion::TagFilterOption},
+    importer::create_importer,
+    sorter::{SortCriteria, SortOrder, Sorter},
+};
+
+pub async fn exec<D: DataProvider>(mut app: App<D>, cmd: HeadlessCliCommand) -> anyhow::Result<()> {
+    app.load_entries().await?;
+
+    match cmd {
+        HeadlessCliCommand::Export {
+            template,
+            format,
+            output,
+            filter,
+        } => exec_export(&mut app, template, format, output, filter).await,
+        HeadlessCliCommand::PreviewImport(source) => exec_preview_import(&app, &source),
+        HeadlessCliCommand::AddEntry {
+            title,
+            tags,
+            priority,
+            date,
+            content,
+        } => exec_add_entry(&mut app, title, tags, priority, date, content).await,
+        HeadlessCliCommand::List { query, args } => exec_list(&mut app, query, &args),
+        HeadlessCliCommand::Show { id, json } => exec_show(&app, id, json),
+        HeadlessCliCommand::EditEntry(id) => exec_edit_entry(&mut app, id).await,
+        HeadlessCliCommand::SetAttributes {
+            id,
+            title,
+            tags_add,
+            tags_remove,
+            priority,
+        } => exec_set_attributes(&mut app, id, title, tags_add, tags_remove, priority).await,
+        HeadlessCliCommand::RemoveEntries { ids, confirmed } => {
+            exec_remove_entries(&mut app, ids, confirmed).await
+        }
+    }
+}
+
+impl From<CliExportFormat> for ExportFormat {
+    fn from(format: CliExportFormat) -> Self {
+        match format {
+            CliExportFormat::Json => ExportFormat::Json,
+            CliExportFormat::Markdown => ExportFormat::Markdown,
+            CliExportFormat::Html => ExportFormat::Html,
+            CliExportFormat::Text => ExportFormat::Text,
+            CliExportFormat::Csv => ExportFormat::Csv,
+            CliExportFormat::Content => ExportFormat::Content,
+        }
+    }
+}
+
+impl From<CliSortCriteria> for SortCriteria {
+    fn from(criteria: CliSortCriteria) -> Self {
+        match criteria {
+            CliSortCriteria::Date => SortCriteria::Date,
+            CliSortCriteria::Priority => SortCriteria::Priority,
+            CliSortCriteria::Title => SortCriteria::Title,
+        }
+    }
+}
+
+fn apply_filter_query<D: DataProvider>(app: &mut App<D>, query: Option<FilterQuery>) {
+    if let Some(query) = query {
+        app.apply_filter(Some(Filter {
+            query: Some(query),
+            ..Default::default()
+        }));
+    }
+}
+
+async fn exec_export<D: DataProvider>(
+    app: &mut App<D>,
+    template: Option<String>,
+    format: Option<CliExportFormat>,
+    output: Option<PathBuf>,
+    filter: Option<FilterQuery>,
+) -> anyhow::Result<()> {
+    apply_filter_query(app, filter);
+
+    let format = match (template, format) {
+        (Some(name), _) => {
+            let templates_dir = app.settings.export.get_templates_dir()?;
+            ExportFormat::Template(find_template(&templates_dir, &name)?)
+        }
+        (None, Some(format)) => format.into(),
+        (None, None) => output
+            .as_ref()
+            .and_then(|path| ExportFormat::from_path(path, true))
+            .unwrap_or(ExportFormat::default_for(true)),
+    };
+
+    let entries_ids: Vec<u32> = app.get_active_entries().map(|entry| entry.id).collect();
+    let exported = app.export_to_string(&entries_ids, &format).await?;
+
+    match output {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&path, exported).await?;
+
+            println!(
+                "{} journal(s) exported to file {}",
+                entries_ids.len(),
+                path.display()
+            );
+        }
+        None => print!("{exported}"),
+    }
+
+    Ok(())
+}
+
+fn exec_preview_import<D: DataProvider>(app: &App<D>, source: &ImportSource) -> anyhow::Result<()> {
+    let mut result = create_importer(source)?.import(&source.path)?;
+
+    let updated = result.apply_updates(&app.entries);
+
+    let drafts = result.entries.iter().map(|draft| {
+        (
+            String::from("new"),
+            &draft.date,
+            &draft.title,
+            &draft.tags,
+            draft.priority,
+        )
+    });
+    let updates = updated.iter().map(|entry| {
+        (
+            format!("#{}", entry.id),
+            &entry.date,
+            &entry.title,
+            &entry.tags,
+            entry.priority,
+        )
+    });
+
+    for (id, date, title, tags, priority) in drafts.chain(updates) {
+        let mut line = format!("  {id} {} {title}", date.format("%Y-%m-%d"));
+        if !tags.is_empty() {
+            line.push_str(&format!(" [{}]", tags.join(", ")));
+        }
+        if let Some(priority) = priority {
+            line.push_str(&format!(" (priority {priority})"));
+        }
+        println!("{line}");
+    }
+
+    println!("{}", result.report(true));
+
+    Ok(())
+}
+
+async fn exec_add_entry<D: DataProvider>(
+    app: &mut App<D>,
+    title: String,
+    tags: Vec<String>,
+    priority: Option<u32>,
+    date: Option<NaiveDate>,
+    content: Option<String>,
+) -> anyhow::Result<()> {
+    let (title, tags) = validate_attributes(&title, Vec::new(), &tags)?;
+
+    // Journals are created at the start of their day like in the TUI.
+    let date = date.unwrap_or_else(|| Local::now().date_naive());
+    let date = Utc
+        .with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
+        .unwrap();
+
+    let priority = priority.or(app.settings.default_journal_priority);
+
+    let content = match content {
+        Some(content) => content,
+        None => read_entry_content(app).await?,
+    };
+
+    let entry_id = app
+        .add_entry_intern(
+            title,
+            date,
+            tags,
+            priority,
+            Some(content),
+            HistoryStack::Undo,
+        )
+        .await?;
+
+    println!("{entry_id}");
+
+    Ok(())
+}
+
+/// Validates the attributes of a journal like the back-ends do, since not all of them check
+/// them. Returns the trimmed title and the tags with the added ones, skipping empty and
+/// duplicated tags.
+fn validate_attributes(
+    title: &str,
+    mut tags: Vec<String>,
+    added_tags: &[String],
+) -> anyhow::Result<(String, Vec<String>)> {
+    let title = title.trim().to_owned();
+    ensure!(!title.is_empty(), "Entry title can't be empty");
+
+    for tag in added_tags.iter().map(|tag| tag.trim()) {
+        if tag.contains(',') {
+            bail!("Tags can't contain commas: '{tag}'");
+        }
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_owned());
+        }
+    }
+
+    Ok((title, tags))
+}
+
+/// Reads the content of a new journal from the standard input if it's piped, otherwise from
+/// the external editor.
+async fn read_entry_content<D: DataProvider>(app: &App<D>) -> anyhow::Result<String> {
+    let mut stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        let mut content = String::new();
+        stdin
+            .read_to_string(&mut content)
+            .context("Error while reading journal content from the standard input")?;
+        return Ok(content);
+    }
+
+    let temp_file = external_editor::create_temp_file(&app.settings)?;
+    external_editor::run_editor(temp_file.path(), &app.settings).await?;
+
+    let content = tokio::fs::read_to_string(temp_file.path())
+        .await
+        .context("Error while reading journal content from the editor file")?;
+
+    Ok(content)
+}
+
+/// Applies the filter and the sort of the list options, returning the matching journals.
+pub fn list_entries<'a, D: DataProvider>(
+    app: &'a mut App<D>,
+    query: Option<FilterQuery>,
+    args: &ListArgs,
+) -> Vec<&'a Entry> {
+    let mut criteria: Vec<FilterCriterion> = args
+        .tags
+        .iter()
+        .map(|tag| FilterCriterion::Tag(TagFilterOption::Tag(tag.to_owned())))
+        .collect();
+    if let Some(priority) = args.priority {
+        criteria.push(FilterCriterion::Priority(priority));
+    }
+    if let Some(since) = args.since {
+        criteria.push(FilterCriterion::DateRange {
+            from: Some(since),
+            to: None,
+        });
+    }
+
+    if !criteria.is_empty() || query.is_some() {
+        app.apply_filter(Some(Filter {
+            relation: CriteriaRelation::And,
+            criteria,
+            query,
+        }));
+    }
+
+    if !args.sort.is_empty() || args.ascending {
+        let criteria = if args.sort.is_empty() {
+            Sorter::default().get_criteria().to_vec()
+        } else {
+            args.sort.iter().copied().map(SortCriteria::from).collect()
+        };
+        let order = if args.ascending {
+            SortOrder::Ascending
+        } else {
+            SortOrder::Descending
+        };
+        app.apply_sort(criteria, order);
+    }
+
+    app.get_active_entries().collect()
+}
+
+fn exec_list<D: DataProvider>(
+    app: &mut App<D>,
+    query: Option<FilterQuery>,
+    args: &ListArgs,
+) -> anyhow::Result<()> {
+    let entries = list_entries(app, query, args);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        eprintln!("No journals found");
+    }
+
+    for entry in entries {
+        println!("{}", format_entry_line(entry));
+    }
+
+    Ok(())
+}
+
+/// Formats the journal attributes in one line for the human-readable list.
+fn format_entry_line(entry: &Entry) -> String {
+    let mut line = format!(
+        "{:>4}  {}  {}",
+        entry.id,
+        entry.date.format("%Y-%m-%d"),
+        entry.title
+    );
+    if !entry.tags.is_empty() {
+        line.push_str(&format!(" [{}]", entry.tags.join(", ")));
+    }
+    if let Some(priority) = entry.priority {
+        line.push_str(&format!(" (priority {priority})"));
+    }
+
+    line
+}
+
+fn get_existing_entry<D: DataProvider>(app: &App<D>, id: u32) -> anyhow::Result<&Entry> {
+    app.get_entry(id)
+        .with_context(|| format!("Journal with ID {id} doesn't exist"))
+}
+
+fn exec_show<D: DataProvider>(app: &App<D>, id: u32, json: bool) -> anyhow::Result<()> {
+    let entry = get_existing_entry(app, id)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(entry)?);
+    } else {
+        println!("{}\n\n{}", format_entry_line(entry), entry.content);
+    }
+
+    Ok(())
+}
+
+async fn exec_edit_entry<D: DataProvider>(app: &mut App<D>, id: u32) -> anyhow::Result<()> {
+    let content = get_existing_entry(app, id)?.content.to_owned();
+
+    let temp_file = external_editor::create_temp_file(&app.settings)?;
+    tokio::fs::write(temp_file.path(), &content).await?;
+
+    external_editor::run_editor(temp_file.path(), &app.settings).await?;
+
+    let new_content = tokio::fs::read_to_string(temp_file.path())
+        .await
+        .context("Error while reading journal content from the editor file")?;
+
+    if new_content == content {
+        println!("Journal {id} has no changes");
+        return Ok(());
+    }
+
+    app.update_entry_content(id, new_content, HistoryStack::Undo)
+        .await?;
+
+    println!("Journal {id} updated");
+
+    Ok(())
+}
+
+async fn exec_set_attributes<D: DataProvider>(
+    app: &mut App<D>,
+    id: u32,
+    title: Option<String>,
+    tags_add: Vec<String>,
+    tags_remove: Vec<String>,
+    priority: Option<Option<u32>>,
+) -> anyhow::Result<()> {
+    let entry = get_existing_entry(app, id)?;
+
+    let title = title.as_deref().unwrap_or(&entry.title);
+    let date = entry.date;
+
+    let mut tags = entry.tags.clone();
+    tags.retain(|tag| !tags_remove.iter().any(|removed| removed.trim() == tag));
+    let (title, tags) = validate_attributes(title, tags, &tags_add)?;
+
+    let priority = priority.unwrap_or(entry.priority);
+
+    app.update_entry_attributes(id, title, date, tags, priority, HistoryStack::Undo)
+        .await?;
+
+    println!("Journal {id} updated");
+
+    Ok(())
+}
+
+async fn exec_remove_entries<D: DataProvider>(
+    app: &mut App<D>,
+    mut ids: Vec<u32>,
+    confirmed: bool,
+) -> anyhow::Result<()> {
+    ids.sort_unstable();
+    ids.dedup();
+
+    for id in ids.iter() {
+        get_existing_entry(app, *id)?;
+    }
+
+    if !confirmed && !confirm_removal(app, &ids)? {
+        println!("Deletion canceled");
+        return Ok(());
+    }
+
+    for id in ids.iter() {
+        app.delete_entry(*id).await?;
+        println!("Journal {id} deleted");
+    }
+
+    Ok(())
+}
+
+/// Asks the user to confirm deleting the journals on the terminal.
+fn confirm_removal<D: DataProvider>(app: &App<D>, ids: &[u32]) -> anyhow::Result<bool> {
+    let stdin = std::io::stdin();
+    ensure!(
+        stdin.is_terminal(),
+        "Deleting journals must be confirmed with --yes when the input isn't a terminal"
+    );
+
+    for id in ids {
+        if let Some(entry) = app.get_entry(*id) {
+            println!("{}", format_entry_line(entry));
+        }
+    }
+    print!("Delete {} journal(s)? [y/N] ", ids.len());
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    stdin.read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -10,6 +10,8 @@ pub struct HistoryManager {
     redo_stack: VecDeque<Change>,
     /// Sets the size limit of each stack
     stacks_limit: usize,
+    /// Changes collected while a group is open to be registered as one change on its stack.
+    open_group: Option<(HistoryStack, Vec<Change>)>,
 }
 
 impl HistoryManager {
@@ -18,6 +20,23 @@ impl HistoryManager {
             undo_stack: VecDeque::new(),
             redo_stack: VecDeque::new(),
             stacks_limit,
+            open_group: None,
+        }
+    }
+
+    /// Starts collecting the changes registered on the given [`HistoryStack`] to add them as one
+    /// [`Change::Group`] when the group is finished, so they are undone and redone at once.
+    pub fn start_group(&mut self, target: HistoryStack) {
+        debug_assert!(self.open_group.is_none(), "History groups can't be nested");
+        self.open_group = Some((target, Vec::new()));
+    }
+
+    /// Adds the changes collected since starting the group to their stack if there are any.
+    pub fn finish_group(&mut self) {
+        if let Some((target, changes)) = self.open_group.take()
+            && !changes.is_empty()
+        {
+            self.add_to_stack(Change::Group(changes), target);
         }
     }
 
@@ -25,6 +44,13 @@ impl HistoryManager {
     /// and keeping the stack within its allowed limit by dropping changes from the bottom if
     /// needed.
     fn add_to_stack(&mut self, change: Change, target: HistoryStack) {
+        if let Some((group_target, changes)) = self.open_group.as_mut()
+            && *group_target == target
+        {
+            changes.push(change);
+            return;
+        }
+
         let stack = match target {
             HistoryStack::Undo => &mut self.undo_stack,
             HistoryStack::Redo => &mut self.redo_stack,
@@ -85,7 +111,7 @@ impl HistoryManager {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Represents the types of history targets within the [`HistoryManager`]
 pub enum HistoryStack {
     Undo,
@@ -103,9 +129,11 @@ pub enum Change {
     EntryAttribute(Box<EntryAttributes>),
     /// Entry content changed. It contains the content before the change.
     EntryContent { id: u32, content: String },
+    /// Changes applied together. They are in the order of their registration.
+    Group(Vec<Change>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// Contains the changes of attributes on an [`Entry`] to be saved in the history stacks
 pub struct EntryAttributes {
     pub id: u32,
@@ -217,4 +245,38 @@ mod tests {
             change => panic!("unexpected change: {change:?}"),
         }
     }
+
+    #[test]
+    fn group_is_one_change() {
+        let mut history = HistoryManager::new(2);
+
+        history.start_group(HistoryStack::Undo);
+        history.register_change_attributes(HistoryStack::Undo, &sample_entry(1));
+        history.register_change_attributes(HistoryStack::Undo, &sample_entry(2));
+        history.register_change_attributes(HistoryStack::Undo, &sample_entry(3));
+        // Changes on the other stack aren't part of the group.
+        history.register_add(HistoryStack::Redo, &sample_entry(4));
+        history.finish_group();
+
+        match history.pop_undo().unwrap() {
+            Change::Group(changes) => {
+                let ids: Vec<u32> = changes
+                    .iter()
+                    .map(|change| match change {
+                        Change::EntryAttribute(attributes) => attributes.id,
+                        change => panic!("unexpected change: {change:?}"),
+                    })
+                    .collect();
+                assert_eq!(ids, [1, 2, 3]);
+            }
+            change => panic!("unexpected change: {change:?}"),
+        }
+        assert!(history.pop_undo().is_none());
+        assert!(history.pop_redo().is_some());
+
+        // Empty groups aren't registered.
+        history.start_group(HistoryStack::Undo);
+        history.finish_group();
+        assert!(history.pop_undo().is_none());
+    }
 }
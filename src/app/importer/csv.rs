@@ -0,0 +1,328 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail};
+use backend::EntryDraft;
+
+use super::{EntryUpdate, ImportResult, Importer, clean_tags, parse_date, parse_priority};
+
+/// Journal fields which can be mapped to CSV columns.
+const FIELDS: [&str; 6] = ["id", "title", "date", "content", "tags", "priority"];
+
+/// Names of the CSV columns of each journal field. Columns are matched case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvColumns {
+    pub id: String,
+    pub title: String,
+    pub date: String,
+    pub content: String,
+    pub tags: String,
+    pub priority: String,
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        Self {
+            id: String::from("id"),
+            title: String::from("title"),
+            date: String::from("date"),
+            content: String::from("content"),
+            tags: String::from("tags"),
+            priority: String::from("priority"),
+        }
+    }
+}
+
+impl CsvColumns {
+    /// Creates the columns from the default ones, replacing them with the given mapping of
+    /// journal fields to column names.
+    pub fn from_mapping<'a>(
+        mapping: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> anyhow::Result<Self> {
+        let mut columns = Self::default();
+
+        for (field, column) in mapping {
+            let target = match field.trim().to_lowercase().as_str() {
+                "id" => &mut columns.id,
+                "title" => &mut columns.title,
+                "date" => &mut columns.date,
+                "content" => &mut columns.content,
+                "tags" => &mut columns.tags,
+                "priority" => &mut columns.priority,
+                _ => bail!(
+                    "Unknown journal field '{field}' in column mapping. Available fields: {}",
+                    FIELDS.join(", ")
+                ),
+            };
+            *target = column.trim().to_owned();
+        }
+
+        Ok(columns)
+    }
+}
+
+/// Imports CSV files with a header row. Title and date columns are required while content,
+/// tags and priority columns are optional. Tags are separated with commas or semicolons.
+pub struct CsvImporter {
+    columns: CsvColumns,
+    /// Updates the journals with the IDs of the rows instead of adding them again.
+    update: bool,
+}
+
+impl CsvImporter {
+    pub fn new(columns: CsvColumns, update: bool) -> Self {
+        Self { columns, update }
+    }
+}
+
+impl Importer for CsvImporter {
+    fn import(&self, path: &Path) -> anyhow::Result<ImportResult> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_path(path)
+            .map_err(|err| anyhow!("Error while opening import file: Error: {err}"))?;
+
+        let headers = reader
+            .headers()
+            .map_err(|err| anyhow!("Error while reading CSV header. Error: {err}"))?
+            .clone();
+        let column_idx = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(column))
+        };
+
+        let required_idx = |column: &str| {
+            column_idx(column).ok_or_else(|| {
+                anyhow!(
+                    "Column '{column}' couldn't be found in CSV header. Available columns: {}",
+                    headers.iter().collect::<Vec<_>>().join(", ")
+                )
+            })
+        };
+
+        let id_idx = if self.update {
+            Some(required_idx(&self.columns.id)?)
+        } else {
+            None
+        };
+        let title_idx = required_idx(&self.columns.title)?;
+        let date_idx = required_idx(&self.columns.date)?;
+        let content_idx = column_idx(&self.columns.content);
+        let tags_idx = column_idx(&self.columns.tags);
+        let priority_idx = column_idx(&self.columns.priority);
+
+        let mut result = ImportResult::default();
+
+        for record in reader.records() {
+            let record = match record {
+                Ok(record) => record,
+                Err(err) => {
+                    let row = err
+                        .position()
+                        .map(|pos| format!("Row {}", pos.line()))
+                        .unwrap_or_else(|| String::from("Unknown row"));
+                    result.skip(row, format!("Invalid CSV record: {err}"));
+                    continue;
+                }
+            };
+
+            let row = record
+                .position()
+                .map(|pos| format!("Row {}", pos.line()))
+                .unwrap_or_else(|| String::from("Unknown row"));
+            let field = |idx: Option<usize>| idx.and_then(|idx| record.get(idx)).unwrap_or("");
+
+            let id_text = field(id_idx).trim();
+            let id = match id_text {
+                "" => None,
+                text => match text.parse::<u32>() {
+                    Ok(id) => Some(id),
+                    Err(_) => {
+                        result.skip(row, format!("Invalid ID '{text}'"));
+                        continue;
+                    }
+                },
+            };
+
+            let title = field(Some(title_idx)).trim();
+            if title.is_empty() {
+                result.skip(row, "Missing title");
+                continue;
+            }
+
+            let date_text = field(Some(date_idx));
+            let Some(date) = parse_date(date_text) else {
+                result.skip(row, format!("Invalid date '{date_text}'"));
+                continue;
+            };
+
+            let priority = match parse_priority(field(priority_idx)) {
+                Ok(priority) => priority,
+                Err(reason) => {
+                    result.skip(row, reason);
+                    continue;
+                }
+            };
+
+            let tags = clean_tags(field(tags_idx).split([',', ';']));
+            let content = field(content_idx).to_owned();
+
+            match id {
+                // Only the attributes with columns in the file are updated.
+                Some(id) => result.updates.push(EntryUpdate {
+                    id,
+                    date,
+                    title: title.to_owned(),
+                    content: content_idx.map(|_| content),
+                    tags: tags_idx.map(|_| tags),
+                    priority: priority_idx.map(|_| priority),
+                }),
+                None => result.entries.push(
+                    EntryDraft::new(date, title.to_owned(), tags, priority).with_content(content),
+                ),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::app::exporter::{ExportFormat, sample_entries};
+
+    use super::*;
+
+    #[test]
+    fn import_with_column_mapping() {
+        let dir = tempfile::Builder::new()
+            .prefix("csv-import")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("notes.csv");
+        fs::write(
+            &path,
+            "Subject,Created,Body,Labels,Priority\n\
+            Planning,2024-03-01 09:30,\"Line 1\nLine 2\",\"work; plans\",2\n\
+            ,2024-03-02,No title,,\n\
+            Bad priority,2024-03-03,,,high\n",
+        )
+        .unwrap();
+
+        let columns = CsvColumns::from_mapping([
+            ("title", "subject"),
+            ("date", "Created"),
+            ("content", "Body"),
+            ("tags", "Labels"),
+        ])
+        .unwrap();
+        let result = CsvImporter::new(columns, false).import(&path).unwrap();
+
+        assert_eq!(result.entries.len(), 1);
+        let entry = &result.entries[0];
+        assert_eq!(entry.title, "Planning");
+        assert_eq!(
+            entry.date,
+            Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap()
+        );
+        assert_eq!(entry.content, "Line 1\nLine 2");
+        assert_eq!(entry.tags, [String::from("work"), String::from("plans")]);
+        assert_eq!(entry.priority, Some(2));
+
+        let skipped: Vec<(&str, &str)> = result
+            .skipped
+            .iter()
+            .map(|s| (s.record.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("Row 4", "Missing title"),
+                ("Row 5", "Invalid priority 'high'")
+            ]
+        );
+    }
+
+    #[test]
+    fn mapping_errors() {
+        let err = CsvColumns::from_mapping([("subject", "Title")]).unwrap_err();
+        assert!(err.to_string().contains("Unknown journal field 'subject'"));
+
+        let dir = tempfile::Builder::new()
+            .prefix("csv-missing")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("notes.csv");
+        fs::write(&path, "name,created\nA,2024-03-01\n").unwrap();
+
+        let err = CsvImporter::new(CsvColumns::default(), false)
+            .import(&path)
+            .unwrap_err();
+        assert!(err.to_string().contains("Column 'title' couldn't be found"));
+    }
+
+    #[test]
+    fn update_exported_journals() {
+        let entries = sample_entries();
+        let entries_refs: Vec<_> = entries.iter().collect();
+        let exported = ExportFormat::Csv
+            .exporter()
+            .unwrap()
+            .export(&entries_refs)
+            .unwrap();
+
+        let dir = tempfile::Builder::new()
+            .prefix("csv-roundtrip")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("journals.csv");
+        let new_row = "\n,2024-03-04,New journal,,,\n";
+        fs::write(&path, format!("{}{new_row}", exported.trim_end())).unwrap();
+
+        // Without updating, all the rows are added as new journals.
+        let result = CsvImporter::new(CsvColumns::default(), false)
+            .import(&path)
+            .unwrap();
+        assert_eq!(result.entries.len(), 3);
+        assert!(result.updates.is_empty());
+
+        let mut result = CsvImporter::new(CsvColumns::default(), true)
+            .import(&path)
+            .unwrap();
+        assert_eq!(result.apply_updates(&entries), entries);
+        assert!(result.skipped.is_empty());
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].title, "New journal");
+    }
+
+    #[test]
+    fn update_keeps_attributes_without_columns() {
+        let dir = tempfile::Builder::new()
+            .prefix("csv-partial")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("journals.csv");
+        fs::write(&path, "id,title,date\n1,Renamed,2024-03-05\n").unwrap();
+
+        let mut result = CsvImporter::new(CsvColumns::default(), true)
+            .import(&path)
+            .unwrap();
+        let existing = sample_entries();
+        let updated = result.apply_updates(&existing);
+
+        assert_eq!(updated.len(), 1);
+        let original = existing.iter().find(|entry| entry.id == 1).unwrap();
+        assert_eq!(updated[0].title, "Renamed");
+        assert_eq!(
+            updated[0].date,
+            Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap()
+        );
+        assert_eq!(updated[0].content, original.content);
+        assert_eq!(updated[0].tags, original.tags);
+        assert_eq!(updated[0].priority, original.priority);
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fs, path::Path};
+
+use anyhow::anyhow;
+use backend::EntryDraft;
+use serde::Deserialize;
+
+use super::{ImportResult, Importer, clean_tags, parse_date, split_title};
+
+#[derive(Debug, Deserialize)]
+struct DayOneExport {
+    entries: Vec<DayOneEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayOneEntry {
+    #[serde(default)]
+    uuid: Option<String>,
+    #[serde(default)]
+    creation_date: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Imports the JSON file of a Day One JSON export. The first line of the text is used as title.
+pub struct DayOneImporter;
+
+impl Importer for DayOneImporter {
+    fn import(&self, path: &Path) -> anyhow::Result<ImportResult> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Error while opening import file: Error: {err}"))?;
+        let export: DayOneExport = serde_json::from_str(&text)
+            .map_err(|err| anyhow!("Error while parsing Day One export file. Error: {err}"))?;
+
+        let mut result = ImportResult::default();
+
+        for (idx, entry) in export.entries.into_iter().enumerate() {
+            let record = match entry.uuid.as_ref() {
+                Some(uuid) => format!("Entry {} ({uuid})", idx + 1),
+                None => format!("Entry {}", idx + 1),
+            };
+
+            let Some(date) = parse_date(&entry.creation_date) else {
+                result.skip(
+                    record,
+                    format!("Invalid creation date '{}'", entry.creation_date),
+                );
+                continue;
+            };
+
+            let (title, content) = split_title(&unescape_markdown(&entry.text));
+            if title.is_empty() {
+                result.skip(record, "Missing text");
+                continue;
+            }
+
+            let tags = clean_tags(entry.tags.iter().map(String::as_str));
+
+            result.entries.push(
+                EntryDraft::new(date, title, tags, None)
+                    .with_content(content.trim_end().to_owned()),
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+/// Removes the backslashes Day One adds before the Markdown punctuation in its exports.
+fn unescape_markdown(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\\'
+            && let Some(next) = chars.peek()
+            && next.is_ascii_punctuation()
+        {
+            continue;
+        }
+        unescaped.push(ch);
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn import_day_one_export() {
+        let dir = tempfile::Builder::new()
+            .prefix("dayone-import")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("Journal.json");
+        fs::write(
+            &path,
+            r##"{
+  "metadata": {"version": "1.0"},
+  "entries": [
+    {"uuid": "A1", "creationDate": "2024-03-01T09:30:00Z", "text": "# Trip to the lake\\!\n\nSwam 2\\.5 km\\.", "tags": ["travel"], "starred": false},
+    {"uuid": "B2", "creationDate": "2024-03-02T09:30:00Z", "text": "   "},
+    {"uuid": "C3", "creationDate": "", "text": "No date"}
+  ]
+}"##,
+        )
+        .unwrap();
+
+        let result = DayOneImporter.import(&path).unwrap();
+
+        assert_eq!(result.entries.len(), 1);
+        let entry = &result.entries[0];
+        assert_eq!(entry.title, "Trip to the lake!");
+        assert_eq!(entry.content, "Swam 2.5 km.");
+        assert_eq!(
+            entry.date,
+            Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap()
+        );
+        assert_eq!(entry.tags, [String::from("travel")]);
+
+        let skipped: Vec<&str> = result.skipped.iter().map(|s| s.record.as_str()).collect();
+        assert_eq!(skipped, ["Entry 2 (B2)", "Entry 3 (C3)"]);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fs, path::Path};
+
+use anyhow::anyhow;
+use backend::EntryDraft;
+use serde::Deserialize;
+
+use super::{ImportResult, Importer, clean_tags, parse_date};
+
+#[derive(Debug, Deserialize)]
+struct JrnlExport {
+    entries: Vec<JrnlEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JrnlEntry {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    date: String,
+    #[serde(default)]
+    time: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Imports the JSON export of jrnl (`jrnl --export json`).
+pub struct JrnlImporter;
+
+impl Importer for JrnlImporter {
+    fn import(&self, path: &Path) -> anyhow::Result<ImportResult> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Error while opening import file: Error: {err}"))?;
+        let export: JrnlExport = serde_json::from_str(&text)
+            .map_err(|err| anyhow!("Error while parsing jrnl export file. Error: {err}"))?;
+
+        let mut result = ImportResult::default();
+
+        for (idx, entry) in export.entries.into_iter().enumerate() {
+            let record = format!("Entry {}", idx + 1);
+
+            let title = entry.title.trim();
+            if title.is_empty() {
+                result.skip(record, "Missing title");
+                continue;
+            }
+
+            let date_text = format!("{} {}", entry.date, entry.time);
+            let Some(date) = parse_date(&date_text).or_else(|| parse_date(&entry.date)) else {
+                result.skip(record, format!("Invalid date '{}'", date_text.trim()));
+                continue;
+            };
+
+            let tags = clean_tags(entry.tags.iter().map(String::as_str));
+
+            result.entries.push(
+                EntryDraft::new(date, title.to_owned(), tags, None)
+                    .with_content(entry.body.trim().to_owned()),
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn import_jrnl_export() {
+        let dir = tempfile::Builder::new()
+            .prefix("jrnl-import")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("jrnl.json");
+        fs::write(
+            &path,
+            r#"{
+  "tags": {"@work": 1},
+  "entries": [
+    {"title": "Standup.", "body": "Talked about @work things.\n", "date": "2024-03-01", "time": "09:30", "tags": ["@work"], "starred": true},
+    {"title": "", "body": "No title", "date": "2024-03-02", "time": "10:00", "tags": []},
+    {"title": "Bad date", "body": "", "date": "someday", "time": "", "tags": []}
+  ]
+}"#,
+        )
+        .unwrap();
+
+        let result = JrnlImporter.import(&path).unwrap();
+
+        assert_eq!(result.entries.len(), 1);
+        let entry = &result.entries[0];
+        assert_eq!(entry.title, "Standup.");
+        assert_eq!(entry.content, "Talked about @work things.");
+        assert_eq!(
+            entry.date,
+            Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap()
+        );
+        assert_eq!(entry.tags, [String::from("work")]);
+
+        let skipped: Vec<(&str, &str)> = result
+            .skipped
+            .iter()
+            .map(|s| (s.record.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("Entry 2", "Missing title"),
+                ("Entry 3", "Invalid date 'someday'")
+            ]
+        );
+    }
+}
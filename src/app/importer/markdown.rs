@@ -0,0 +1,441 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, ensure};
+use backend::EntryDraft;
+use chrono::{DateTime, Utc};
+
+use super::{ImportResult, Importer, clean_tags, parse_date, parse_priority, split_title};
+
+const FRONT_MATTER_DELIMITER: &str = "---";
+
+/// Imports the Markdown files in a directory and its sub directories. The attributes of the
+/// journals are read from the YAML front matter with the keys `title`, `date`, `tags` and
+/// `priority`. Files can contain multiple journals, each starting with its front matter, as
+/// written by the Markdown exporter.
+pub struct MarkdownImporter;
+
+impl Importer for MarkdownImporter {
+    fn import(&self, path: &Path) -> anyhow::Result<ImportResult> {
+        ensure!(
+            path.is_dir(),
+            "Markdown import needs a directory: path {}",
+            path.display()
+        );
+
+        let mut files = Vec::new();
+        collect_markdown_files(path, &mut files)?;
+        files.sort();
+
+        let mut result = ImportResult::default();
+
+        for file in files {
+            let record = file
+                .strip_prefix(path)
+                .unwrap_or(&file)
+                .display()
+                .to_string();
+
+            let text = match fs::read_to_string(&file) {
+                Ok(text) => text,
+                Err(err) => {
+                    result.skip(record, format!("Reading file failed: {err}"));
+                    continue;
+                }
+            };
+
+            let modified: Option<DateTime<Utc>> = fs::metadata(&file)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(DateTime::from);
+
+            let file_stem = file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let documents = split_documents(&text);
+            let multiple = documents.len() > 1;
+            for (idx, document) in documents.into_iter().enumerate() {
+                let record = if multiple {
+                    format!("{record} (journal {})", idx + 1)
+                } else {
+                    record.clone()
+                };
+
+                match parse_document(document, &file_stem, modified) {
+                    Ok(entry) => result.entries.push(entry),
+                    Err(reason) => result.skip(record, reason),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let read_dir = fs::read_dir(dir)
+        .map_err(|err| anyhow!("Failed to read directory {}. Error: {err}", dir.display()))?;
+
+    for dir_entry in read_dir.filter_map(Result::ok) {
+        let path = dir_entry.path();
+        // The file type doesn't follow symlinks, which avoids endless recursion on their cycles.
+        if dir_entry
+            .file_type()
+            .is_ok_and(|file_type| file_type.is_dir())
+        {
+            collect_markdown_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "md" || ext == "markdown")
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parsed document with its front matter if it has one.
+#[derive(Debug, PartialEq, Eq)]
+struct Document<'a> {
+    front_matter: Option<Vec<&'a str>>,
+    body: Vec<&'a str>,
+}
+
+/// Splits the text into documents, starting a new document on each front matter block.
+fn split_documents(text: &str) -> Vec<Document<'_>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut documents = Vec::new();
+    let mut current = Document {
+        front_matter: None,
+        body: Vec::new(),
+    };
+
+    let mut idx = 0;
+    while idx < lines.len() {
+        if lines[idx].trim_end() == FRONT_MATTER_DELIMITER
+            && let Some(end) = front_matter_end(&lines, idx)
+        {
+            // Only a front matter at the start or after an empty line starts a new document, so
+            // thematic breaks in the content aren't confused with it.
+            let at_start = current.front_matter.is_none() && current.body.is_empty();
+            let after_empty_line = idx > 0 && lines[idx - 1].trim().is_empty();
+            if at_start || after_empty_line {
+                if !at_start {
+                    documents.push(current);
+                }
+                current = Document {
+                    front_matter: Some(lines[idx + 1..end].to_vec()),
+                    body: Vec::new(),
+                };
+                idx = end + 1;
+                continue;
+            }
+        }
+
+        current.body.push(lines[idx]);
+        idx += 1;
+    }
+
+    documents.push(current);
+
+    documents
+}
+
+/// Gets the index of the closing delimiter if the lines after the opening one form a front
+/// matter block with `key: value` pairs and list items only.
+fn front_matter_end(lines: &[&str], start: usize) -> Option<usize> {
+    let mut has_key = false;
+    for (idx, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim();
+        if trimmed == FRONT_MATTER_DELIMITER {
+            return has_key.then_some(idx);
+        }
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if trimmed.starts_with("- ") && has_key {
+            continue;
+        }
+
+        let (key, _) = trimmed.split_once(':')?;
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '-')
+        {
+            return None;
+        }
+        has_key = true;
+    }
+
+    None
+}
+
+/// Parses the front matter lines into values with lower case keys. Lists are kept as multiple
+/// values.
+fn parse_front_matter(lines: &[&str]) -> HashMap<String, Vec<String>> {
+    let mut values: HashMap<String, Vec<String>> = HashMap::new();
+    let mut current_key = None;
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if let Some(key) = current_key.as_ref() {
+                values
+                    .entry(String::clone(key))
+                    .or_default()
+                    .push(unquote(item));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+
+        let parsed = if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            split_inline_list(list)
+        } else if value.is_empty() {
+            Vec::new()
+        } else {
+            vec![unquote(value)]
+        };
+
+        values.insert(key.clone(), parsed);
+        current_key = Some(key);
+    }
+
+    values
+}
+
+/// Splits the items of an inline YAML list respecting the quotes.
+fn split_inline_list(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+
+    for ch in list.chars() {
+        current.push(ch);
+        match (quote, ch) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), ch) if ch == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, ',') => {
+                current.pop();
+                items.push(unquote(current.trim()));
+                current.clear();
+            }
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    if !current.trim().is_empty() {
+        items.push(unquote(current.trim()));
+    }
+
+    items
+}
+
+/// Removes the quotes of YAML strings, resolving the escaped characters of double quotes.
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        let mut unquoted = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next() {
+                    Some('n') => unquoted.push('\n'),
+                    Some('t') => unquoted.push('\t'),
+                    Some(other) => unquoted.push(other),
+                    None => unquoted.push('\\'),
+                },
+                ch => unquoted.push(ch),
+            }
+        }
+        unquoted
+    } else if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        inner.replace("''", "'")
+    } else {
+        value.to_owned()
+    }
+}
+
+fn parse_document(
+    document: Document,
+    file_stem: &str,
+    modified: Option<DateTime<Utc>>,
+) -> Result<EntryDraft, String> {
+    let front_matter = document
+        .front_matter
+        .as_deref()
+        .map(parse_front_matter)
+        .unwrap_or_default();
+    let first_value = |key: &str| {
+        front_matter
+            .get(key)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    };
+
+    let mut body = document.body.join("\n");
+    // Remove the empty line between the front matter and the body.
+    if document.front_matter.is_some() {
+        body = body.trim_start_matches('\n').to_owned();
+    }
+
+    let title = match first_value("title") {
+        Some(title) if !title.trim().is_empty() => title.trim().to_owned(),
+        _ if body.trim_start().starts_with("# ") => {
+            let (title, content) = split_title(&body);
+            body = content;
+            title
+        }
+        _ => file_stem.to_owned(),
+    };
+
+    if title.is_empty() {
+        return Err(String::from("Missing title"));
+    }
+
+    let date = match first_value("date") {
+        Some(date) => parse_date(date).ok_or_else(|| format!("Invalid date '{date}'"))?,
+        None => modified.ok_or_else(|| String::from("Missing date"))?,
+    };
+
+    let tags = front_matter
+        .get("tags")
+        .map(|tags| {
+            // Tags can be written as a comma separated text too.
+            let tags = tags.iter().flat_map(|tag| tag.split(','));
+            clean_tags(tags)
+        })
+        .unwrap_or_default();
+
+    let priority = first_value("priority")
+        .map(parse_priority)
+        .transpose()?
+        .flatten();
+
+    Ok(EntryDraft::new(date, title, tags, priority).with_content(body.trim_end().to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use crate::app::exporter::{ExportFormat, sample_entries};
+
+    use super::*;
+
+    #[test]
+    fn import_files_with_front_matter() {
+        let dir = tempfile::Builder::new()
+            .prefix("md-import")
+            .tempdir()
+            .unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(
+            dir.path().join("first.md"),
+            "---\ntitle: 'It''s first'\ndate: 2024-03-01\ntags:\n  - \"@work\"\n  - home\npriority: 2\n---\n\nBody\n\n---\n\nAfter break\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("nested").join("second.md"),
+            "# Heading title\n\nSecond body",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("invalid.md"),
+            "---\ntitle: Invalid\ndate: yesterday\n---\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("ignored.txt"), "Not markdown").unwrap();
+
+        let result = MarkdownImporter.import(dir.path()).unwrap();
+
+        assert_eq!(result.entries.len(), 2);
+        let first = &result.entries[0];
+        assert_eq!(first.title, "It's first");
+        assert_eq!(
+            first.date,
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(first.tags, [String::from("work"), String::from("home")]);
+        assert_eq!(first.priority, Some(2));
+        assert_eq!(first.content, "Body\n\n---\n\nAfter break");
+
+        let second = &result.entries[1];
+        assert_eq!(second.title, "Heading title");
+        assert_eq!(second.content, "Second body");
+
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].record, "invalid.md");
+        assert_eq!(result.skipped[0].reason, "Invalid date 'yesterday'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skip_symlinked_directories() {
+        let dir = tempfile::Builder::new()
+            .prefix("md-symlink")
+            .tempdir()
+            .unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("nested").join("note.md"), "# Note").unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("nested").join("cycle")).unwrap();
+
+        let result = MarkdownImporter.import(dir.path()).unwrap();
+
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].title, "Note");
+    }
+
+    #[test]
+    fn import_markdown_export() {
+        let entries = sample_entries();
+        let entries_refs: Vec<_> = entries.iter().collect();
+        let exported = ExportFormat::Markdown
+            .exporter()
+            .unwrap()
+            .export(&entries_refs)
+            .unwrap();
+
+        let dir = tempfile::Builder::new()
+            .prefix("md-roundtrip")
+            .tempdir()
+            .unwrap();
+        fs::write(dir.path().join("export.md"), exported).unwrap();
+
+        let result = MarkdownImporter.import(dir.path()).unwrap();
+
+        assert!(result.skipped.is_empty());
+        let imported: Vec<EntryDraft> = entries.into_iter().map(EntryDraft::from_entry).collect();
+        assert_eq!(result.entries, imported);
+    }
+}
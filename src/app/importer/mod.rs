@@ -0,0 +1,298 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use backend::{Entry, EntryDraft};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use crate::cli::{CliImportFormat, ImportSource};
+
+mod csv;
+mod day_one;
+mod jrnl;
+mod markdown;
+mod transfer;
+
+use self::csv::{CsvColumns, CsvImporter};
+use day_one::DayOneImporter;
+use jrnl::JrnlImporter;
+use markdown::MarkdownImporter;
+use transfer::TransferImporter;
+
+/// Reads journals from the files of other tools, converting them into entries to import.
+pub trait Importer {
+    fn import(&self, path: &Path) -> anyhow::Result<ImportResult>;
+}
+
+/// Creates the importer of the source format.
+pub fn create_importer(source: &ImportSource) -> anyhow::Result<Box<dyn Importer>> {
+    let importer: Box<dyn Importer> = match source.format {
+        CliImportFormat::Json => Box::new(TransferImporter),
+        CliImportFormat::Markdown => Box::new(MarkdownImporter),
+        CliImportFormat::Jrnl => Box::new(JrnlImporter),
+        CliImportFormat::DayOne => Box::new(DayOneImporter),
+        CliImportFormat::Csv => {
+            let mapping = source
+                .columns
+                .iter()
+                .map(|(field, column)| (field.as_str(), column.as_str()));
+            let columns = CsvColumns::from_mapping(mapping)?;
+            Box::new(CsvImporter::new(columns, source.update))
+        }
+    };
+
+    Ok(importer)
+}
+
+/// Entries read by an importer with the records which couldn't be converted.
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub entries: Vec<EntryDraft>,
+    /// Existing journals to update with the imported attributes.
+    pub updates: Vec<EntryUpdate>,
+    pub skipped: Vec<SkippedRecord>,
+}
+
+/// Imported attributes of an existing journal. Attributes which aren't in the source are `None`
+/// to keep their current values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryUpdate {
+    pub id: u32,
+    pub date: DateTime<Utc>,
+    pub title: String,
+    pub content: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub priority: Option<Option<u32>>,
+}
+
+impl EntryUpdate {
+    /// Applies the imported attributes on the journal, keeping the ones missing in the source.
+    pub fn apply(&self, entry: &Entry) -> Entry {
+        Entry {
+            id: entry.id,
+            date: self.date,
+            title: self.title.to_owned(),
+            content: self
+                .content
+                .clone()
+                .unwrap_or_else(|| entry.content.to_owned()),
+            tags: self.tags.clone().unwrap_or_else(|| entry.tags.clone()),
+            priority: self.priority.unwrap_or(entry.priority),
+        }
+    }
+}
+
+impl ImportResult {
+    fn skip(&mut self, record: impl Into<String>, reason: impl Into<String>) {
+        self.skipped.push(SkippedRecord {
+            record: record.into(),
+            reason: reason.into(),
+        });
+    }
+
+    /// Applies the updates on the existing journals, returning the updated journals. Updates of
+    /// journals which don't exist, or which are updated by an earlier record, are skipped.
+    pub fn apply_updates(&mut self, existing: &[Entry]) -> Vec<Entry> {
+        let existing: HashMap<u32, &Entry> =
+            existing.iter().map(|entry| (entry.id, entry)).collect();
+        let mut updated_ids = HashSet::new();
+        let mut updated = Vec::with_capacity(self.updates.len());
+
+        for update in std::mem::take(&mut self.updates) {
+            let reason = match existing.get(&update.id) {
+                None => "Journal doesn't exist",
+                Some(_) if !updated_ids.insert(update.id) => {
+                    "Journal is updated by an earlier record"
+                }
+                Some(entry) => {
+                    updated.push(update.apply(entry));
+                    self.updates.push(update);
+                    continue;
+                }
+            };
+
+            self.skip(format!("ID {}", update.id), reason);
+        }
+
+        updated
+    }
+
+    /// Gets the summary of the result with the skipped records.
+    pub fn report(&self, dry_run: bool) -> String {
+        let (import_msg, update_msg) = if dry_run {
+            ("Dry run: would import", "Dry run: would update")
+        } else {
+            ("Imported", "Updated")
+        };
+
+        let mut report = format!("{import_msg} {} journal(s).", self.entries.len());
+        if !self.updates.is_empty() {
+            report.push_str(&format!(
+                "\n{update_msg} {} journal(s).",
+                self.updates.len()
+            ));
+        }
+
+        if !self.skipped.is_empty() {
+            report.push_str(&format!("\nSkipped {} record(s):", self.skipped.len()));
+            for skipped in self.skipped.iter() {
+                report.push_str(&format!("\n  {}: {}", skipped.record, skipped.reason));
+            }
+        }
+
+        report
+    }
+}
+
+/// Record which couldn't be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedRecord {
+    /// Identifies the record in the source, like the file name or the record number.
+    pub record: String,
+    pub reason: String,
+}
+
+/// Parses dates in RFC 3339 format, or as date with optional time in UTC like the dates in the
+/// app.
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    const DATE_TIME_FORMATS: [&str; 4] = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ];
+
+    let naive = DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+
+    Some(Utc.from_utc_datetime(&naive))
+}
+
+/// Parses the priority as a positive number.
+fn parse_priority(text: &str) -> Result<Option<u32>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    text.parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid priority '{text}'"))
+}
+
+/// Cleans the tags from other tools, removing their tag markers like `@work` or `#work`.
+fn clean_tags<'a>(tags: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().trim_start_matches(['@', '#']).trim();
+        if !tag.is_empty() && !cleaned.iter().any(|t| t == tag) {
+            cleaned.push(tag.to_owned());
+        }
+    }
+
+    cleaned
+}
+
+/// Splits the text into a title from its first line and the content from the remaining lines.
+fn split_title(text: &str) -> (String, String) {
+    let text = text.trim_start();
+    let (first_line, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let title = first_line.trim().trim_start_matches('#').trim().to_owned();
+
+    (title, rest.trim_start_matches(['\n', '\r']).to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dates() {
+        let expected = Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+
+        assert_eq!(parse_date("2024-03-01T09:30:00Z"), Some(expected));
+        assert_eq!(parse_date("2024-03-01T11:30:00+02:00"), Some(expected));
+        assert_eq!(parse_date("2024-03-01 09:30"), Some(expected));
+        assert_eq!(
+            parse_date("2024-03-01"),
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(parse_date("01.03.2024"), None);
+    }
+
+    #[test]
+    fn clean_and_deduplicate_tags() {
+        assert_eq!(
+            clean_tags(["@work", " #home ", "work", ""]),
+            [String::from("work"), String::from("home")]
+        );
+    }
+
+    #[test]
+    fn report_skipped_records() {
+        let mut result = ImportResult::default();
+        result.entries.push(EntryDraft::new(
+            Utc::now(),
+            String::from("Title"),
+            Vec::new(),
+            None,
+        ));
+        result.skip("notes.md", "Missing title");
+
+        assert_eq!(
+            result.report(false),
+            "Imported 1 journal(s).\nSkipped 1 record(s):\n  notes.md: Missing title"
+        );
+    }
+
+    #[test]
+    fn skip_updates_of_missing_journals() {
+        let mut result = ImportResult::default();
+        for id in [1, 2, 1] {
+            result.updates.push(EntryUpdate {
+                id,
+                date: Utc::now(),
+                title: String::from("Title"),
+                content: None,
+                tags: None,
+                priority: Some(None),
+            });
+        }
+        let existing = Entry::new(
+            1,
+            Utc::now(),
+            String::from("Old title"),
+            String::from("Content"),
+            vec![String::from("work")],
+            Some(2),
+        );
+
+        let updated = result.apply_updates(&[existing]);
+
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].title, "Title");
+        assert_eq!(updated[0].content, "Content");
+        assert_eq!(updated[0].tags, [String::from("work")]);
+        assert_eq!(updated[0].priority, None);
+        assert_eq!(result.updates.len(), 1);
+        assert_eq!(
+            result.report(true),
+            "Dry run: would import 0 journal(s).\nDry run: would update 1 journal(s).\n\
+            Skipped 2 record(s):\n  ID 2: Journal doesn't exist\n  \
+            ID 1: Journal is updated by an earlier record"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use std::{fs::File, path::Path};
+
+use anyhow::{anyhow, bail};
+use backend::EntriesDTO;
+
+use super::{ImportResult, Importer};
+
+/// Reads the transfer JSON files exported from the app.
+pub struct TransferImporter;
+
+impl Importer for TransferImporter {
+    fn import(&self, path: &Path) -> anyhow::Result<ImportResult> {
+        if !path.exists() {
+            bail!("Import file doesn't exist: path {}", path.display())
+        }
+
+        let file = File::open(path)
+            .map_err(|err| anyhow!("Error while opening import file: Error: {err}"))?;
+
+        let entries_dto = EntriesDTO::from_reader(&file)
+            .map_err(|err| anyhow!("Error while parsing import file. Error: {err}"))?;
+
+        Ok(ImportResult {
+            entries: entries_dto.entries,
+            ..Default::default()
+        })
+    }
+}
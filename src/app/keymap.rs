@@ -248,7 +248,57 @@ pub fn get_entries_list_keymaps() -> Vec<Keymap> {
             Input::new(KeyCode::PageDown, KeyModifiers::NONE),
             UICommand::PageDownEntries,
         ),
+        Keymap::new(
+            Input::new(KeyCode::Char('+'), KeyModifiers::NONE),
+            UICommand::IncreaseEntriesListSize,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('-'), KeyModifiers::NONE),
+            UICommand::DecreaseEntriesListSize,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('L'), KeyModifiers::SHIFT),
+            UICommand::TogglePanesOrientation,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('p'), KeyModifiers::NONE),
+            UICommand::CycleContentPaneMode,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('c'), KeyModifiers::NONE),
+            UICommand::ShowCalendar,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('S'), KeyModifiers::SHIFT),
+            UICommand::ShowStatistics,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('P'), KeyModifiers::SHIFT),
+            UICommand::ShowFilterPresets,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('/'), KeyModifiers::NONE),
+            UICommand::SearchAllEntries,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('m'), KeyModifiers::NONE),
+            UICommand::ToggleMarkdownPreview,
+        ),
     ]
+    .into_iter()
+    .chain(get_filter_presets_keymaps())
+    .collect()
+}
+
+/// Keymaps to apply the first nine filter presets with the number keys.
+fn get_filter_presets_keymaps() -> impl Iterator<Item = Keymap> {
+    (1..=9).map(|number| {
+        let key = char::from_digit(number, 10).expect("Numbers are single digits");
+        Keymap::new(
+            Input::new(KeyCode::Char(key), KeyModifiers::NONE),
+            UICommand::ApplyFilterPreset(number as u8),
+        )
+    })
 }
 
 pub(crate) fn get_editor_mode_keymaps() -> Vec<Keymap> {
@@ -289,6 +339,18 @@ pub(crate) fn get_editor_mode_keymaps() -> Vec<Keymap> {
             Input::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
             UICommand::PasteOsClipboard,
         ),
+        Keymap::new(
+            Input::new(KeyCode::Char('g'), KeyModifiers::CONTROL),
+            UICommand::NextSpellingError,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('o'), KeyModifiers::CONTROL),
+            UICommand::ShowSpellingSuggestions,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('g'), KeyModifiers::ALT),
+            UICommand::AddWordToDictionary,
+        ),
     ]
 }
 
@@ -362,6 +424,34 @@ pub fn get_multi_select_keymaps() -> Vec<Keymap> {
             Input::new(KeyCode::Char('>'), KeyModifiers::NONE),
             UICommand::MulSelExportEntries,
         ),
+        Keymap::new(
+            Input::new(KeyCode::Char('t'), KeyModifiers::NONE),
+            UICommand::MulSelAddTags,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('T'), KeyModifiers::SHIFT),
+            UICommand::MulSelRemoveTags,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('r'), KeyModifiers::NONE),
+            UICommand::MulSelRenameTag,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('p'), KeyModifiers::NONE),
+            UICommand::MulSelSetPriority,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('s'), KeyModifiers::NONE),
+            UICommand::MulSelShiftDates,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('u'), KeyModifiers::NONE),
+            UICommand::Undo,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('U'), KeyModifiers::SHIFT),
+            UICommand::Redo,
+        ),
         // Char '?' isn't recognized on windows
         #[cfg(not(target_os = "windows"))]
         Keymap::new(
@@ -1,29 +1,43 @@
 use self::{
-    filter::{Filter, FilterCriterion},
+    filter::{Filter, FilterCriterion, FilterPreset},
     sorter::{SortCriteria, SortOrder, Sorter},
-    state::AppState,
+    state::{AppState, ContentPaneMode},
 };
-use crate::settings::Settings;
-use anyhow::{Context, anyhow, bail};
+use crate::cli::ImportSource;
+use crate::settings::{
+    Settings,
+    layout::{MAX_ENTRIES_LIST_PERCENTAGE, MIN_ENTRIES_LIST_PERCENTAGE, PanesOrientation},
+};
+use anyhow::{Context, anyhow, bail, ensure};
 use backend::{DataProvider, EntriesDTO, Entry, EntryDraft};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use colored_tags::ColoredTagsManager;
+use drafts::{Draft, DraftsStore};
+use exporter::ExportFormat;
 use filter::criterion::TagFilterOption;
-use history::{Change, HistoryManager, HistoryStack};
+use history::{Change, EntryAttributes, HistoryManager, HistoryStack};
+use importer::ImportResult;
 use rayon::prelude::*;
+use spell_check::SpellChecker;
 use std::{
     collections::{BTreeSet, HashSet},
     fs::File,
     path::PathBuf,
 };
 
+mod bulk_edit;
 mod colored_tags;
+mod drafts;
+mod exporter;
 mod external_editor;
-mod filter;
+pub mod filter;
+mod headless;
 mod history;
+mod importer;
 mod keymap;
 mod runner;
 mod sorter;
+mod spell_check;
 pub mod state;
 #[cfg(test)]
 mod test;
@@ -31,8 +45,10 @@ pub mod ui;
 
 pub use runner::HandleInputReturnType;
 pub use runner::run;
+pub use runner::run_headless;
 pub use ui::UIComponents;
 
+pub use bulk_edit::BulkEdit;
 pub use colored_tags::TagColors;
 
 pub struct App<D>
@@ -53,6 +69,10 @@ where
     /// Keeps history of the changes on entries, enabling undo & redo operations
     history: HistoryManager,
     colored_tags: Option<ColoredTagsManager>,
+    /// Checks the spelling of the content in the editor if it's enabled.
+    pub spell_checker: Option<SpellChecker>,
+    /// Persists drafts of the unsaved changes in the editor if they are enabled.
+    drafts: Option<DraftsStore>,
 }
 
 impl<D> App<D>
@@ -78,6 +98,8 @@ where
             state: Default::default(),
             history,
             colored_tags,
+            spell_checker: None,
+            drafts: None,
         }
     }
 
@@ -235,11 +257,9 @@ where
     ) -> anyhow::Result<()> {
         log::trace!("Updating entry");
 
-        assert!(self.current_entry_id.is_some());
-
         let entry = self
             .get_entry_mut(entry_id, EntryEditPart::Attributes, history_target)
-            .expect("Current entry must have value when updating entry attributes");
+            .expect("Entry must exist when updating entry attributes");
 
         entry.title = title;
         entry.date = date;
@@ -271,6 +291,50 @@ where
             .await
     }
 
+    /// Applies the given [`BulkEdit`] on the selected entries, registering their changes as one
+    /// group in the history. Returns the count of the changed entries.
+    pub async fn apply_bulk_edit(&mut self, edit: &BulkEdit) -> anyhow::Result<usize> {
+        let mut selected_ids: Vec<u32> = self.selected_entries.iter().copied().collect();
+        selected_ids.sort_unstable();
+
+        self.history.start_group(HistoryStack::Undo);
+        let result = self.apply_bulk_edit_intern(edit, &selected_ids).await;
+        self.history.finish_group();
+
+        result
+    }
+
+    async fn apply_bulk_edit_intern(
+        &mut self,
+        edit: &BulkEdit,
+        entry_ids: &[u32],
+    ) -> anyhow::Result<usize> {
+        let mut changed_count = 0;
+        for &entry_id in entry_ids {
+            let Some(entry) = self.entries.iter().find(|entry| entry.id == entry_id) else {
+                continue;
+            };
+
+            let mut attributes = EntryAttributes::from(entry);
+            if !edit.apply(&mut attributes) {
+                continue;
+            }
+
+            self.update_entry_attributes(
+                entry_id,
+                attributes.title,
+                attributes.date,
+                attributes.tags,
+                attributes.priority,
+                HistoryStack::Undo,
+            )
+            .await?;
+            changed_count += 1;
+        }
+
+        Ok(changed_count)
+    }
+
     /// Update the content of the given [`Entry`], registering its previous content to the given
     /// [`HistoryStack`]
     pub async fn update_entry_content(
@@ -325,31 +389,72 @@ where
         Ok(())
     }
 
-    async fn export_entry_content(&self, entry_id: u32, path: PathBuf) -> anyhow::Result<()> {
-        if let Some(parent) = path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
+    async fn export_entry_content(
+        &self,
+        entry_id: u32,
+        path: PathBuf,
+        format: ExportFormat,
+    ) -> anyhow::Result<()> {
+        if self.get_entry(entry_id).is_none() {
+            bail!("Entry with ID {entry_id} doesn't exist");
         }
 
-        let entry = self.get_entry(entry_id).expect("Entry should exist");
+        self.export_entries_intern(&[entry_id], path, format).await
+    }
 
-        tokio::fs::write(path, entry.content.to_owned()).await?;
+    /// Exports the selected entries, or all the entries meeting the filter criteria if there
+    /// are no selected entries.
+    async fn export_entries(&self, path: PathBuf, format: ExportFormat) -> anyhow::Result<()> {
+        let entries_ids: Vec<u32> = if self.selected_entries.is_empty() {
+            self.get_active_entries().map(|entry| entry.id).collect()
+        } else {
+            self.selected_entries.iter().cloned().collect()
+        };
 
-        Ok(())
+        self.export_entries_intern(&entries_ids, path, format).await
     }
 
-    async fn export_entries(&self, path: PathBuf) -> anyhow::Result<()> {
+    async fn export_entries_intern(
+        &self,
+        entries_ids: &[u32],
+        path: PathBuf,
+        format: ExportFormat,
+    ) -> anyhow::Result<()> {
+        let exported = self.export_to_string(entries_ids, &format).await?;
+
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let selected_ids: Vec<u32> = self.selected_entries.iter().cloned().collect();
+        tokio::fs::write(path, exported).await?;
 
-        let entries_dto = self.data_provide.get_export_object(&selected_ids).await?;
+        Ok(())
+    }
 
-        let file = File::create(path)?;
-        serde_json::to_writer_pretty(&file, &entries_dto)?;
+    /// Converts the entries with the given IDs to the given format.
+    pub async fn export_to_string(
+        &self,
+        entries_ids: &[u32],
+        format: &ExportFormat,
+    ) -> anyhow::Result<String> {
+        match format.exporter() {
+            Some(exporter) => {
+                // Keep the order of the entries as they are shown in the app.
+                let ids: HashSet<u32> = entries_ids.iter().copied().collect();
+                let entries: Vec<&Entry> = self
+                    .entries
+                    .iter()
+                    .filter(|entry| ids.contains(&entry.id))
+                    .collect();
+
+                exporter.export(&entries)
+            }
+            None => {
+                let entries_dto = self.data_provide.get_export_object(entries_ids).await?;
 
-        Ok(())
+                Ok(serde_json::to_string_pretty(&entries_dto)?)
+            }
+        }
     }
 
     async fn import_entries(&self, file_path: PathBuf) -> anyhow::Result<()> {
@@ -360,7 +465,7 @@ where
         let file = File::open(file_path)
             .map_err(|err| anyhow!("Error while opening import file: Error: {err}"))?;
 
-        let entries_dto: EntriesDTO = serde_json::from_reader(&file)
+        let entries_dto = EntriesDTO::from_reader(&file)
             .map_err(|err| anyhow!("Error while parsing import file. Error: {err}"))?;
 
         self.data_provide
@@ -371,6 +476,46 @@ where
         Ok(())
     }
 
+    /// Imports the journals from the source with the importer of its format, returning the
+    /// result with the skipped records. All journals are validated before writing any of them to
+    /// avoid applying the file partly.
+    async fn import_from(&self, source: &ImportSource) -> anyhow::Result<ImportResult> {
+        let mut result = importer::create_importer(source)?.import(&source.path)?;
+
+        let updated = if result.updates.is_empty() {
+            Vec::new()
+        } else {
+            let existing = self.data_provide.load_all_entries().await?;
+            result.apply_updates(&existing)
+        };
+
+        let titles = result
+            .entries
+            .iter()
+            .map(|draft| &draft.title)
+            .chain(updated.iter().map(|entry| &entry.title));
+        for title in titles {
+            ensure!(
+                !title.trim().is_empty(),
+                "Imported journals must have titles. Nothing is imported"
+            );
+        }
+
+        self.data_provide
+            .import_entries(EntriesDTO::new(result.entries.clone()))
+            .await
+            .map_err(|err| anyhow!("Error while importing the entry. Error: {err}"))?;
+
+        for entry in updated {
+            self.data_provide
+                .update_entry(entry)
+                .await
+                .map_err(|err| anyhow!("Error while updating the entry. Error: {err}"))?;
+        }
+
+        Ok(result)
+    }
+
     pub fn get_all_tags(&self) -> Vec<String> {
         let mut tags = BTreeSet::new();
 
@@ -381,6 +526,15 @@ where
         tags.into_iter().map(String::from).collect()
     }
 
+    /// Gets the id of the entry with the closest date to the given one among all entries
+    /// regardless of the filter.
+    pub fn get_closest_entry_id(&self, date: NaiveDate) -> Option<u32> {
+        self.entries
+            .iter()
+            .min_by_key(|entry| (entry.date.date_naive() - date).num_days().abs())
+            .map(|entry| entry.id)
+    }
+
     /// Sets and applies the given filter on the entries
     pub fn apply_filter(&mut self, filter: Option<Filter>) {
         self.filter = filter;
@@ -398,10 +552,15 @@ where
                 FilterCriterion::Tag(TagFilterOption::NoTags) => !all_tags.is_empty(),
                 FilterCriterion::Title(_) => true,
                 FilterCriterion::Content(_) => true,
+                FilterCriterion::TitlePattern(_) => true,
+                FilterCriterion::ContentPattern(_) => true,
                 FilterCriterion::Priority(_) => true,
+                FilterCriterion::PriorityComparison { .. } => true,
+                FilterCriterion::DateRange { .. } => true,
+                FilterCriterion::RelativeDate(_) => true,
             });
 
-            if filter.criteria.is_empty() {
+            if filter.criteria.is_empty() && filter.query.is_none() {
                 self.filter = None;
             }
         }
@@ -410,10 +569,11 @@ where
     /// Applies filter on the entries and filter out the ones who don't meet the filter's criteria
     fn update_filtered_out_entries(&mut self) {
         if let Some(filter) = self.filter.as_ref() {
+            let today = Utc::now().date_naive();
             self.filtered_out_entries = self
                 .entries
                 .par_iter()
-                .filter(|entry| !filter.check_entry(entry))
+                .filter(|entry| !filter.check_entry(entry, today))
                 .map(|entry| entry.id)
                 .collect();
         } else {
@@ -553,12 +713,175 @@ where
         self.state = state;
     }
 
+    /// Loads the dictionaries for spell checking if it's enabled in the settings.
+    pub fn load_spell_checker(&mut self, ui_components: &mut UIComponents) {
+        if !self.settings.spell_check.enabled {
+            return;
+        }
+
+        match SpellChecker::load(&self.settings.spell_check) {
+            Ok(checker) => self.spell_checker = Some(checker),
+            Err(err) => ui_components.show_err_msg(format!(
+                "Loading spell checker failed. Spell checking is disabled\n\rError Info: {err}"
+            )),
+        }
+    }
+
+    /// Loads the drafts store if drafts are enabled in the settings, returning the drafts left
+    /// from previous sessions that differ from the content of their journals. The other drafts
+    /// are removed.
+    pub fn load_drafts(&mut self, ui_components: &mut UIComponents) -> Vec<Draft> {
+        if !self.settings.auto_save.drafts {
+            return Vec::new();
+        }
+
+        let leftover_drafts = DraftsStore::new(&self.settings).and_then(|store| {
+            let drafts = store.load_all()?;
+            self.drafts = Some(store);
+            Ok(drafts)
+        });
+
+        let leftover_drafts = match leftover_drafts {
+            Ok(drafts) => drafts,
+            Err(err) => {
+                ui_components.show_err_msg(format!(
+                    "Loading drafts failed. Drafts are disabled\n\rError Info: {err}"
+                ));
+                return Vec::new();
+            }
+        };
+
+        let (recoverable, outdated): (Vec<_>, Vec<_>) =
+            leftover_drafts.into_iter().partition(|draft| {
+                self.entries
+                    .iter()
+                    .any(|entry| entry.id == draft.entry_id && entry.content != draft.content)
+            });
+
+        for draft in outdated {
+            if let Err(err) = self.remove_draft(draft.entry_id) {
+                log::error!("Removing outdated draft failed: Error info {err}");
+            }
+        }
+
+        recoverable
+    }
+
+    /// Persists the draft of the unsaved content of the current entry, removing the last draft
+    /// if there is no unsaved content. Drafts are disabled for the rest of the session on errors.
+    pub fn sync_draft(&mut self, unsaved_content: Option<&str>) -> anyhow::Result<()> {
+        let Some(store) = self.drafts.as_mut() else {
+            return Ok(());
+        };
+
+        let unsaved = self.current_entry_id.zip(unsaved_content);
+        if let Err(err) = store.sync(unsaved) {
+            self.drafts = None;
+            bail!("Saving draft failed. Drafts are disabled\n\rError Info: {err}");
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_draft(&self, entry_id: u32) -> anyhow::Result<()> {
+        match self.drafts.as_ref() {
+            Some(store) => store.remove(entry_id),
+            None => Ok(()),
+        }
+    }
+
     pub fn persist_state(&self) -> anyhow::Result<()> {
         self.state.save(&self.settings)?;
 
         Ok(())
     }
 
+    /// Gets the orientation of the panes, preferring the one changed at runtime over the settings.
+    pub fn get_panes_orientation(&self) -> PanesOrientation {
+        self.state
+            .panes_orientation
+            .unwrap_or(self.settings.layout.orientation)
+    }
+
+    pub fn toggle_panes_orientation(&mut self) {
+        self.state.panes_orientation = Some(self.get_panes_orientation().toggle());
+    }
+
+    /// Gets the percentage of the main area taken by the entries list, preferring the one
+    /// changed at runtime over the settings.
+    pub fn get_entries_list_percentage(&self) -> u16 {
+        self.state
+            .entries_list_percentage
+            .unwrap_or(self.settings.layout.entries_list_percentage)
+            .clamp(MIN_ENTRIES_LIST_PERCENTAGE, MAX_ENTRIES_LIST_PERCENTAGE)
+    }
+
+    /// Resizes the entries list by the given percentage keeping it within the allowed bounds.
+    pub fn resize_entries_list(&mut self, delta: i16) {
+        let percentage = (self.get_entries_list_percentage() as i16 + delta).clamp(
+            MIN_ENTRIES_LIST_PERCENTAGE as i16,
+            MAX_ENTRIES_LIST_PERCENTAGE as i16,
+        );
+
+        self.state.entries_list_percentage = Some(percentage as u16);
+    }
+
+    pub fn get_content_pane_mode(&self) -> ContentPaneMode {
+        self.state.content_pane
+    }
+
+    pub fn set_content_pane_mode(&mut self, mode: ContentPaneMode) {
+        self.state.content_pane = mode;
+    }
+
+    pub fn is_markdown_rendered(&self) -> bool {
+        self.state.render_markdown
+    }
+
+    pub fn set_markdown_rendered(&mut self, rendered: bool) {
+        self.state.render_markdown = rendered;
+    }
+
+    pub fn get_filter_presets(&self) -> &[FilterPreset] {
+        &self.state.filter_presets
+    }
+
+    /// Saves the given filter as a preset, replacing the preset with the same name if exists.
+    pub fn save_filter_preset(&mut self, name: String, filter: Filter) {
+        let preset = FilterPreset::new(name, filter);
+        match self
+            .state
+            .filter_presets
+            .iter_mut()
+            .find(|p| p.name == preset.name)
+        {
+            Some(existing) => *existing = preset,
+            None => self.state.filter_presets.push(preset),
+        }
+    }
+
+    pub fn rename_filter_preset(&mut self, index: usize, name: String) {
+        if let Some(preset) = self.state.filter_presets.get_mut(index) {
+            preset.name = name;
+        }
+    }
+
+    pub fn delete_filter_preset(&mut self, index: usize) {
+        if index < self.state.filter_presets.len() {
+            self.state.filter_presets.remove(index);
+        }
+    }
+
+    /// Applies the filter of the preset with the given index returning if the preset exists.
+    pub fn apply_filter_preset(&mut self, index: usize) -> bool {
+        let Some(preset) = self.state.filter_presets.get(index) else {
+            return false;
+        };
+
+        self.apply_filter(Some(preset.filter.clone()));
+        true
+    }
+
     /// Apply undo on entries returning the id of the effected entry.
     pub async fn undo(&mut self) -> anyhow::Result<Option<u32>> {
         match self.history.pop_undo() {
@@ -612,6 +935,21 @@ where
                     .await?;
                 Ok(Some(id))
             }
+            Change::Group(changes) => {
+                log::trace!("History Apply: Group of {} changes", changes.len());
+                // The changes are reverted in the opposite order and registered as a group again.
+                self.history.start_group(history_target);
+                let mut result = Ok(None);
+                for change in changes.into_iter().rev() {
+                    result = Box::pin(self.apply_history_change(change, history_target)).await;
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                self.history.finish_group();
+
+                result
+            }
         }
     }
 }
@@ -1,11 +1,14 @@
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use crossterm::event::{Event, EventStream, KeyEventKind};
 use ratatui::{Terminal, backend::Backend};
 
-use crate::app::{App, UIComponents};
-use crate::cli::PendingCliCommand;
+use crate::app::{App, UIComponents, filter::Filter};
+use crate::cli::{HeadlessCliCommand, PendingCliCommand, StartupAction, StartupContext};
 use crate::settings::{BackendType, Settings};
 use futures_util::StreamExt;
+use tokio::time::{Instant, MissedTickBehavior};
 
 use backend::DataProvider;
 #[cfg(feature = "json")]
@@ -13,10 +16,14 @@ use backend::JsonDataProvide;
 #[cfg(feature = "sqlite")]
 use backend::SqliteDataProvide;
 
+use super::headless;
 use super::keymap::Input;
 use super::ui::Styles;
 use super::ui::ui_functions::render_message_centered;
 
+/// Interval to persist the drafts of the unsaved changes and to check for the idle auto-save.
+const AUTO_SAVE_TICK: Duration = Duration::from_secs(1);
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum HandleInputReturnType {
     Handled,
@@ -30,101 +37,202 @@ pub async fn run<B: Backend>(
     settings: Settings,
     styles: Styles,
     pending_cmd: Option<PendingCliCommand>,
+    startup_context: StartupContext,
 ) -> Result<()> {
     match settings.backend_type.unwrap_or_default() {
         #[cfg(feature = "json")]
         BackendType::Json => {
-            let path = if let Some(path) = &settings.json_backend.file_path {
-                path.clone()
-            } else {
-                crate::settings::json_backend::get_default_json_path()?
-            };
-            let data_provider = JsonDataProvide::new(path);
-            run_intern(terminal, data_provider, settings, styles, pending_cmd).await
-        }
-        #[cfg(not(feature = "json"))]
-        BackendType::Json => {
-            anyhow::bail!(
-                "Feature 'json' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'json' feature"
+            let data_provider = create_json_provider(&settings)?;
+            run_intern(
+                terminal,
+                data_provider,
+                settings,
+                styles,
+                pending_cmd,
+                startup_context,
             )
+            .await
         }
+        #[cfg(not(feature = "json"))]
+        BackendType::Json => bail_json_missing(),
         #[cfg(feature = "sqlite")]
         BackendType::Sqlite => {
-            let path = if let Some(path) = &settings.sqlite_backend.file_path {
-                path.clone()
-            } else {
-                crate::settings::sqlite_backend::get_default_sqlite_path()?
-            };
-            let data_provider = SqliteDataProvide::from_file(path).await?;
-            run_intern(terminal, data_provider, settings, styles, pending_cmd).await
+            let data_provider = create_sqlite_provider(&settings).await?;
+            run_intern(
+                terminal,
+                data_provider,
+                settings,
+                styles,
+                pending_cmd,
+                startup_context,
+            )
+            .await
         }
         #[cfg(not(feature = "sqlite"))]
+        BackendType::Sqlite => bail_sqlite_missing(),
+    }
+}
+
+/// Executes the given command with the configured back-end without starting the TUI.
+pub async fn run_headless(settings: Settings, cmd: HeadlessCliCommand) -> Result<()> {
+    match settings.backend_type.unwrap_or_default() {
+        #[cfg(feature = "json")]
+        BackendType::Json => {
+            let data_provider = create_json_provider(&settings)?;
+            headless::exec(App::new(data_provider, settings), cmd).await
+        }
+        #[cfg(not(feature = "json"))]
+        BackendType::Json => bail_json_missing(),
+        #[cfg(feature = "sqlite")]
         BackendType::Sqlite => {
-            anyhow::bail!(
-                "Feature 'sqlite' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'sqlite' feature"
-            )
+            let data_provider = create_sqlite_provider(&settings).await?;
+            headless::exec(App::new(data_provider, settings), cmd).await
         }
+        #[cfg(not(feature = "sqlite"))]
+        BackendType::Sqlite => bail_sqlite_missing(),
     }
 }
 
+#[cfg(feature = "json")]
+fn create_json_provider(settings: &Settings) -> Result<JsonDataProvide> {
+    let path = if let Some(path) = &settings.json_backend.file_path {
+        path.clone()
+    } else {
+        crate::settings::json_backend::get_default_json_path()?
+    };
+
+    Ok(JsonDataProvide::new(path))
+}
+
+#[cfg(not(feature = "json"))]
+fn bail_json_missing() -> Result<()> {
+    anyhow::bail!(
+        "Feature 'json' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'json' feature"
+    )
+}
+
+#[cfg(feature = "sqlite")]
+async fn create_sqlite_provider(settings: &Settings) -> Result<SqliteDataProvide> {
+    let path = if let Some(path) = &settings.sqlite_backend.file_path {
+        path.clone()
+    } else {
+        crate::settings::sqlite_backend::get_default_sqlite_path()?
+    };
+
+    SqliteDataProvide::from_file(path).await
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn bail_sqlite_missing() -> Result<()> {
+    anyhow::bail!(
+        "Feature 'sqlite' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'sqlite' feature"
+    )
+}
+
 async fn run_intern<B, D>(
     terminal: &mut Terminal<B>,
     data_provider: D,
     settings: Settings,
     styles: Styles,
     pending_cmd: Option<PendingCliCommand>,
+    startup_context: StartupContext,
 ) -> anyhow::Result<()>
 where
     B: Backend,
     D: DataProvider,
 {
-    let mut ui_components = UIComponents::new(styles);
+    let mut ui_components = UIComponents::new(styles, settings.editor_profile);
     let mut app = App::new(data_provider, settings);
-    if let Some(cmd) = pending_cmd
-        && let Err(err) = exec_pending_cmd(terminal, &app, cmd).await
-    {
-        ui_components.show_err_msg(err.to_string());
+    if let Some(cmd) = pending_cmd {
+        match exec_pending_cmd(terminal, &app, cmd).await {
+            Ok(Some(msg)) => ui_components.show_info_msg(msg),
+            Ok(None) => {}
+            Err(err) => ui_components.show_err_msg(err.to_string()),
+        }
     }
 
     app.load_state(&mut ui_components);
+    app.load_spell_checker(&mut ui_components);
 
     if let Err(err) = app.load_entries().await {
         ui_components.show_err_msg(err.to_string());
     }
 
-    ui_components.set_current_entry(app.entries.first().map(|entry| entry.id), &mut app);
+    if let Some(query) = startup_context.filter {
+        app.apply_filter(Some(Filter {
+            query: Some(query),
+            ..Default::default()
+        }));
+    }
+
+    let first_entry_id = app.get_active_entries().next().map(|entry| entry.id);
+    ui_components.set_current_entry(first_entry_id, &mut app);
+
+    if let Some(action) = startup_context.action {
+        apply_startup_action(action, &mut app, &mut ui_components);
+    }
+
+    ui_components.show_leftover_drafts(&mut app);
 
     draw_ui(terminal, &mut app, &mut ui_components)?;
 
     let mut input_stream = EventStream::new();
-    while let Some(event) = input_stream.next().await {
-        let event = event.context("Error getting input stream")?;
-        match handle_input(event, &mut app, &mut ui_components).await {
-            Ok(result) => {
-                match result {
-                    HandleInputReturnType::Handled => {
-                        ui_components.update_current_entry(&mut app);
-                        draw_ui(terminal, &mut app, &mut ui_components)?;
+    let mut auto_save_interval = tokio::time::interval(AUTO_SAVE_TICK);
+    auto_save_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut last_input = Instant::now();
+
+    loop {
+        tokio::select! {
+            event = input_stream.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+                last_input = Instant::now();
+
+                let event = event.context("Error getting input stream")?;
+                match handle_input(event, &mut app, &mut ui_components).await {
+                    Ok(result) => {
+                        match result {
+                            HandleInputReturnType::Handled => {
+                                ui_components.update_current_entry(&mut app);
+                                draw_ui(terminal, &mut app, &mut ui_components)?;
+                            }
+                            HandleInputReturnType::NotFound => {
+                                // UI should be drawn even if the input isn't handled in the app
+                                // logic to catch events like resize, Font resize, Mouse activation...
+                                draw_ui(terminal, &mut app, &mut ui_components)?;
+                            }
+                            HandleInputReturnType::ExitApp => {
+                                // Logging persisting errors by closing the app is enough
+                                if let Err(err) = app.persist_state() {
+                                    log::error!("Persisting app state failed: Error info {err}");
+                                }
+
+                                // Changes are either saved or discarded at this point.
+                                if let Err(err) = app.sync_draft(None) {
+                                    log::error!("Removing draft failed: Error info {err}");
+                                }
+
+                                return Ok(());
+                            }
+                            HandleInputReturnType::Ignore => {}
+                        };
                     }
-                    HandleInputReturnType::NotFound => {
-                        // UI should be drawn even if the input isn't handled in the app logic to
-                        // catch events like resize, Font resize, Mouse activation...
+                    Err(err) => {
+                        ui_components.show_err_msg(err.to_string());
                         draw_ui(terminal, &mut app, &mut ui_components)?;
                     }
-                    HandleInputReturnType::ExitApp => {
-                        // Logging persisting errors by closing the app is enough
-                        if let Err(err) = app.persist_state() {
-                            log::error!("Persisting app state failed: Error info {err}");
-                        }
-
-                        return Ok(());
-                    }
-                    HandleInputReturnType::Ignore => {}
-                };
+                }
             }
-            Err(err) => {
-                ui_components.show_err_msg(err.to_string());
-                draw_ui(terminal, &mut app, &mut ui_components)?;
+            _ = auto_save_interval.tick() => {
+                match handle_auto_save(&mut app, &mut ui_components, last_input).await {
+                    Ok(true) => draw_ui(terminal, &mut app, &mut ui_components)?,
+                    Ok(false) => {}
+                    Err(err) => {
+                        ui_components.show_err_msg(err.to_string());
+                        draw_ui(terminal, &mut app, &mut ui_components)?;
+                    }
+                }
             }
         }
     }
@@ -132,24 +240,71 @@ where
     Ok(())
 }
 
+/// Persists the draft of the unsaved changes and saves them to the back-end if the auto-save is
+/// enabled and there was no input in its idle time. Returns whether the UI needs to be redrawn.
+async fn handle_auto_save<D: DataProvider>(
+    app: &mut App<D>,
+    ui_components: &mut UIComponents<'_>,
+    last_input: Instant,
+) -> anyhow::Result<bool> {
+    ui_components.sync_draft(app)?;
+
+    let Some(idle_seconds) = app.settings.auto_save.idle_seconds else {
+        return Ok(false);
+    };
+
+    if last_input.elapsed() < Duration::from_secs(idle_seconds) {
+        return Ok(false);
+    }
+
+    ui_components.auto_save(app).await
+}
+
+fn apply_startup_action<D: DataProvider>(
+    action: StartupAction,
+    app: &mut App<D>,
+    ui_components: &mut UIComponents,
+) {
+    match action {
+        StartupAction::OpenEntry(id) => {
+            if app.get_active_entries().any(|entry| entry.id == id) {
+                ui_components.set_current_entry(Some(id), app);
+            } else if app.get_entry(id).is_some() {
+                ui_components.show_err_msg(format!("Journal with ID {id} is hidden by the filter"));
+            } else {
+                ui_components.show_err_msg(format!("Journal with ID {id} doesn't exist"));
+            }
+        }
+        StartupAction::NewEntry { title } => ui_components.show_new_entry_popup(app, title),
+    }
+}
+
+/// Executes the pending command from the CLI, returning the message to show to the user if the
+/// command has one.
 async fn exec_pending_cmd<B: Backend, D: DataProvider>(
     terminal: &mut Terminal<B>,
     app: &App<D>,
     pending_cmd: PendingCliCommand,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<String>> {
     match pending_cmd {
         PendingCliCommand::ImportJournals(file_path) => {
             terminal.draw(|f| render_message_centered(f, "Importing journals..."))?;
 
             app.import_entries(file_path).await?;
         }
+        PendingCliCommand::ImportFrom(source) => {
+            terminal.draw(|f| render_message_centered(f, "Importing journals..."))?;
+
+            let result = app.import_from(&source).await?;
+            return Ok(Some(result.report(false)));
+        }
         PendingCliCommand::AssignPriority(priority) => {
             terminal.draw(|f| render_message_centered(f, "Assigning Priority to Journals..."))?;
             app.assign_priority_to_entries(priority).await?;
         }
     }
 
-    Ok(())
+    Ok(None)
 }
 
 fn draw_ui<B: Backend, D: DataProvider>(
@@ -0,0 +1,565 @@
+//! Minimal reader for Hunspell dictionaries, supporting the parts of the format needed to check
+//! words in the common dictionaries: flags, prefixes and suffixes with their conditions and
+//! cross products, and the `TRY` and `REP` options used for suggestions.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, anyhow, bail};
+
+/// Maximum count of suggestions returned for a misspelled word.
+const MAX_SUGGESTIONS: usize = 10;
+
+type Flag = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagType {
+    /// Each flag is one character (the default).
+    Short,
+    /// Each flag is two characters.
+    Long,
+    /// Flags are numbers separated by commas.
+    Num,
+    /// Each flag is one unicode character.
+    Utf8,
+}
+
+impl FlagType {
+    fn parse_flags(self, text: &str) -> Vec<Flag> {
+        match self {
+            FlagType::Short | FlagType::Utf8 => text.chars().map(|ch| ch as Flag).collect(),
+            FlagType::Long => text
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|pair| pair.iter().fold(0, |flag, ch| (flag << 16) | *ch as Flag))
+                .collect(),
+            FlagType::Num => text
+                .split(',')
+                .filter_map(|num| num.trim().parse().ok())
+                .collect(),
+        }
+    }
+
+    fn parse_flag(self, text: &str) -> Option<Flag> {
+        self.parse_flags(text).into_iter().next()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConditionPart {
+    Any,
+    Char(char),
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+impl ConditionPart {
+    fn matches(&self, ch: char) -> bool {
+        match self {
+            ConditionPart::Any => true,
+            ConditionPart::Char(expected) => *expected == ch,
+            ConditionPart::OneOf(chars) => chars.contains(&ch),
+            ConditionPart::NoneOf(chars) => !chars.contains(&ch),
+        }
+    }
+}
+
+fn parse_condition(text: &str) -> anyhow::Result<Vec<ConditionPart>> {
+    if text == "." {
+        return Ok(Vec::new());
+    }
+
+    let mut parts = Vec::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        let part = match ch {
+            '.' => ConditionPart::Any,
+            '[' => {
+                let mut group: Vec<char> = Vec::new();
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == ']' {
+                        closed = true;
+                        break;
+                    }
+                    group.push(ch);
+                }
+                if !closed {
+                    bail!("Unclosed group in affix condition '{text}'");
+                }
+                match group.split_first() {
+                    Some(('^', rest)) => ConditionPart::NoneOf(rest.to_vec()),
+                    _ => ConditionPart::OneOf(group),
+                }
+            }
+            ch => ConditionPart::Char(ch),
+        };
+        parts.push(part);
+    }
+
+    Ok(parts)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AffixKind {
+    Prefix,
+    Suffix,
+}
+
+#[derive(Debug, Clone)]
+struct Affix {
+    flag: Flag,
+    cross_product: bool,
+    strip: String,
+    add: String,
+    condition: Vec<ConditionPart>,
+}
+
+impl Affix {
+    /// Checks if the condition of the affix is met on the given stem.
+    fn condition_matches(&self, kind: AffixKind, stem: &str) -> bool {
+        let len = self.condition.len();
+        match kind {
+            AffixKind::Prefix => {
+                stem.chars().count() >= len
+                    && stem
+                        .chars()
+                        .zip(&self.condition)
+                        .all(|(ch, part)| part.matches(ch))
+            }
+            AffixKind::Suffix => {
+                stem.chars().count() >= len
+                    && stem
+                        .chars()
+                        .rev()
+                        .zip(self.condition.iter().rev())
+                        .all(|(ch, part)| part.matches(ch))
+            }
+        }
+    }
+
+    /// Returns the stem of the word by removing the affix from it if it's applicable.
+    fn strip_from(&self, kind: AffixKind, word: &str) -> Option<String> {
+        let stem = match kind {
+            AffixKind::Prefix => {
+                let rest = word.strip_prefix(self.add.as_str())?;
+                format!("{}{rest}", self.strip)
+            }
+            AffixKind::Suffix => {
+                let rest = word.strip_suffix(self.add.as_str())?;
+                format!("{rest}{}", self.strip)
+            }
+        };
+
+        // The remaining part of the word can't be empty.
+        if stem.len() == self.strip.len() || !self.condition_matches(kind, &stem) {
+            return None;
+        }
+
+        Some(stem)
+    }
+}
+
+/// Hunspell dictionary parsed from the affix and the dictionary files.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashMap<String, Vec<Flag>>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+    try_chars: Vec<char>,
+    replacements: Vec<(String, String)>,
+    forbidden_flag: Option<Flag>,
+    need_affix_flag: Option<Flag>,
+    no_suggest_flag: Option<Flag>,
+}
+
+impl Dictionary {
+    /// Parses the dictionary from the raw content of the `.aff` and `.dic` files, decoding them
+    /// with the encoding defined in the affix file.
+    pub fn parse(aff: &[u8], dic: &[u8]) -> anyhow::Result<Self> {
+        let encoding = String::from_utf8_lossy(aff)
+            .lines()
+            .find_map(|line| line.strip_prefix("SET "))
+            .map(|enc| enc.trim().to_uppercase());
+        let decode = |bytes: &[u8]| match encoding.as_deref() {
+            // The 8-bit encodings are mapped to unicode directly, which is right for ISO8859-1
+            // and close enough for the others.
+            Some(enc) if enc.starts_with("ISO8859") || enc.starts_with("ISO-8859") => {
+                bytes.iter().map(|byte| *byte as char).collect::<String>()
+            }
+            _ => String::from_utf8_lossy(bytes).into_owned(),
+        };
+
+        let mut dictionary = Dictionary::default();
+        let flag_type = dictionary.parse_affixes(&decode(aff))?;
+        dictionary.parse_words(&decode(dic), flag_type);
+
+        Ok(dictionary)
+    }
+
+    fn parse_affixes(&mut self, aff: &str) -> anyhow::Result<FlagType> {
+        let mut flag_type = FlagType::Short;
+        let mut lines = aff
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        while let Some(line) = lines.next() {
+            let mut fields = line.split_whitespace();
+            let Some(option) = fields.next() else {
+                continue;
+            };
+            let args: Vec<&str> = fields.collect();
+
+            match (option, args.as_slice()) {
+                ("FLAG", [kind, ..]) => {
+                    flag_type = match *kind {
+                        "long" => FlagType::Long,
+                        "num" => FlagType::Num,
+                        "UTF-8" => FlagType::Utf8,
+                        _ => FlagType::Short,
+                    };
+                }
+                ("TRY", [chars, ..]) => self.try_chars = chars.chars().collect(),
+                ("FORBIDDENWORD", [flag, ..]) => self.forbidden_flag = flag_type.parse_flag(flag),
+                ("NEEDAFFIX", [flag, ..]) => self.need_affix_flag = flag_type.parse_flag(flag),
+                ("NOSUGGEST", [flag, ..]) => self.no_suggest_flag = flag_type.parse_flag(flag),
+                ("REP", [count]) => {
+                    let count: usize = count.parse().unwrap_or_default();
+                    for line in lines.by_ref().take(count) {
+                        if let [_, from, to, ..] = line.split_whitespace().collect::<Vec<_>>()[..] {
+                            // Underscores stand for spaces in the replacements.
+                            self.replacements
+                                .push((from.replace('_', " "), to.replace('_', " ")));
+                        }
+                    }
+                }
+                ("PFX" | "SFX", [flag, cross_product, count, ..]) => {
+                    let kind = if option == "PFX" {
+                        AffixKind::Prefix
+                    } else {
+                        AffixKind::Suffix
+                    };
+                    let flag = flag_type
+                        .parse_flag(flag)
+                        .with_context(|| format!("Invalid affix flag in line '{line}'"))?;
+                    let cross_product = *cross_product == "Y";
+                    let count: usize = count
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid affix rules count in line '{line}'"))?;
+
+                    for rule in lines.by_ref().take(count) {
+                        let affix = parse_affix_rule(rule, flag, cross_product)?;
+                        match kind {
+                            AffixKind::Prefix => self.prefixes.push(affix),
+                            AffixKind::Suffix => self.suffixes.push(affix),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(flag_type)
+    }
+
+    fn parse_words(&mut self, dic: &str, flag_type: FlagType) {
+        // The first line has the approximate count of the words.
+        for line in dic.lines().skip(1) {
+            // Morphological fields are separated by whitespace after the word and its flags.
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) => (word, flag_type.parse_flags(flags)),
+                None => (entry, Vec::new()),
+            };
+            if word.is_empty() {
+                continue;
+            }
+
+            self.words.entry(word.to_owned()).or_default().extend(flags);
+        }
+    }
+
+    /// Adds a word without any flags to the dictionary.
+    pub fn add_word(&mut self, word: &str) {
+        self.words.entry(word.to_owned()).or_default();
+    }
+
+    /// Checks if the word is spelled correctly considering its capitalization.
+    pub fn check(&self, word: &str) -> bool {
+        if self.check_exact(word) {
+            return true;
+        }
+
+        let mut chars = word.chars();
+        let Some(first) = chars.next() else {
+            return true;
+        };
+        let rest = chars.as_str();
+
+        // Capitalized words and words in all caps can have lower case forms in the dictionary.
+        if word.chars().all(|ch| !ch.is_lowercase()) {
+            let lower = word.to_lowercase();
+            let capitalized: String = first
+                .to_uppercase()
+                .chain(rest.to_lowercase().chars())
+                .collect();
+            return self.check_exact(&capitalized) || self.check_exact(&lower);
+        }
+
+        if first.is_uppercase() && rest.chars().all(|ch| !ch.is_uppercase()) {
+            return self.check_exact(&word.to_lowercase());
+        }
+
+        false
+    }
+
+    /// Checks the word as it's written, directly or by removing its affixes.
+    fn check_exact(&self, word: &str) -> bool {
+        if let Some(flags) = self.words.get(word) {
+            if self.has_flag(flags, self.forbidden_flag) {
+                return false;
+            }
+            if !self.has_flag(flags, self.need_affix_flag) {
+                return true;
+            }
+        }
+
+        self.check_affixed(word)
+    }
+
+    fn check_affixed(&self, word: &str) -> bool {
+        let stem_has_flag = |stem: &str, flag: Flag| {
+            self.words.get(stem).is_some_and(|flags| {
+                flags.contains(&flag) && !self.has_flag(flags, self.forbidden_flag)
+            })
+        };
+
+        for prefix in &self.prefixes {
+            if let Some(stem) = prefix.strip_from(AffixKind::Prefix, word)
+                && stem_has_flag(&stem, prefix.flag)
+            {
+                return true;
+            }
+        }
+
+        for suffix in &self.suffixes {
+            let Some(stem) = suffix.strip_from(AffixKind::Suffix, word) else {
+                continue;
+            };
+            if stem_has_flag(&stem, suffix.flag) {
+                return true;
+            }
+
+            // Words can have both a prefix and a suffix if both of them allow cross products.
+            if !suffix.cross_product {
+                continue;
+            }
+            for prefix in self.prefixes.iter().filter(|prefix| prefix.cross_product) {
+                if let Some(root) = prefix.strip_from(AffixKind::Prefix, &stem)
+                    && self.words.get(&root).is_some_and(|flags| {
+                        flags.contains(&prefix.flag) && flags.contains(&suffix.flag)
+                    })
+                {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn has_flag(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    /// Suggests correct words for the misspelled one from the replacement table and the words
+    /// within one edit from it, using the characters from the `TRY` option.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let mut candidates = Vec::new();
+
+        for (from, to) in &self.replacements {
+            for (index, _) in word.match_indices(from.as_str()) {
+                candidates.push(format!(
+                    "{}{to}{}",
+                    &word[..index],
+                    &word[index + from.len()..]
+                ));
+            }
+        }
+
+        let chars: Vec<char> = word.chars().collect();
+        let join = |chars: &[char]| chars.iter().collect::<String>();
+
+        // Wrong case, swapped neighbors, wrong character, missing character, extra character.
+        candidates.push(word.to_lowercase());
+        for i in 0..chars.len().saturating_sub(1) {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            candidates.push(join(&swapped));
+        }
+        for i in 0..chars.len() {
+            for ch in &self.try_chars {
+                let mut replaced = chars.clone();
+                replaced[i] = *ch;
+                candidates.push(join(&replaced));
+            }
+        }
+        for i in 0..=chars.len() {
+            for ch in &self.try_chars {
+                let mut inserted = chars.clone();
+                inserted.insert(i, *ch);
+                candidates.push(join(&inserted));
+            }
+        }
+        for i in 0..chars.len() {
+            let mut removed = chars.clone();
+            removed.remove(i);
+            candidates.push(join(&removed));
+        }
+
+        // Two words written together.
+        for i in 1..chars.len() {
+            let (first, second) = chars.split_at(i);
+            let (first, second) = (join(first), join(second));
+            if self.check(&first) && self.check(&second) {
+                candidates.push(format!("{first} {second}"));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|candidate| candidate != word && !candidate.is_empty())
+            .filter(|candidate| seen.insert(candidate.clone()))
+            .filter(|candidate| {
+                candidate
+                    .split(' ')
+                    .all(|part| self.check(part) && !self.is_no_suggest(part))
+            })
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    }
+
+    fn is_no_suggest(&self, word: &str) -> bool {
+        self.words
+            .get(word)
+            .is_some_and(|flags| self.has_flag(flags, self.no_suggest_flag))
+    }
+}
+
+/// Parses an affix rule line like `SFX D y ied [^aeiou]y`.
+fn parse_affix_rule(line: &str, flag: Flag, cross_product: bool) -> anyhow::Result<Affix> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [_, _, strip, add, rest @ ..] = fields.as_slice() else {
+        bail!("Invalid affix rule '{line}'");
+    };
+
+    let strip = match *strip {
+        "0" => String::new(),
+        strip => strip.to_owned(),
+    };
+    // Continuation flags of the affix aren't supported and get ignored.
+    let add = match add.split('/').next() {
+        Some("0") | None => String::new(),
+        Some(add) => add.to_owned(),
+    };
+    let condition = parse_condition(rest.first().copied().unwrap_or("."))?;
+
+    Ok(Affix {
+        flag,
+        cross_product,
+        strip,
+        add,
+        condition,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+FORBIDDENWORD !
+REP 1
+REP f ph
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX S Y 3
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     s          [^y]
+
+SFX D Y 2
+SFX D   0     ed         [^ey]
+SFX D   0     d          e
+";
+
+    const DIC: &str = "\
+6
+happy/U
+city/S
+day/S
+walk/DSU
+bake/D
+phone/S
+";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(AFF.as_bytes(), DIC.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn check_words_with_affixes() {
+        let dictionary = dictionary();
+
+        for word in [
+            "happy", "unhappy", "cities", "days", "walked", "unwalked", "baked", "unwalks",
+        ] {
+            assert!(dictionary.check(word), "'{word}' should be correct");
+        }
+
+        for word in ["citys", "dayies", "unday", "bakeed", "happys", "wal"] {
+            assert!(!dictionary.check(word), "'{word}' should be misspelled");
+        }
+    }
+
+    #[test]
+    fn check_capitalization() {
+        let dictionary = dictionary();
+
+        assert!(dictionary.check("Happy"));
+        assert!(dictionary.check("CITIES"));
+        assert!(!dictionary.check("hAppy"));
+    }
+
+    #[test]
+    fn suggest_corrections() {
+        let dictionary = dictionary();
+
+        assert_eq!(dictionary.suggest("hapy").first().unwrap(), "happy");
+        assert!(
+            dictionary
+                .suggest("citeis")
+                .contains(&String::from("cities"))
+        );
+        assert!(dictionary.suggest("fone").contains(&String::from("phone")));
+    }
+
+    #[test]
+    fn long_flags_and_latin1_encoding() {
+        let aff = b"SET ISO8859-1\nFLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n";
+        let dic = b"1\ncaf\xe9/Aa\n";
+
+        let dictionary = Dictionary::parse(aff, dic).unwrap();
+
+        assert!(dictionary.check("café"));
+        assert!(dictionary.check("cafés"));
+    }
+}
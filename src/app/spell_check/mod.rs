@@ -0,0 +1,226 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    ops::Range,
+    path::PathBuf,
+};
+
+use anyhow::{Context, anyhow};
+
+use crate::settings::spell_check::SpellCheckSettings;
+
+use hunspell::Dictionary;
+
+mod hunspell;
+
+/// Checks the spelling of words against a local Hunspell dictionary and the words of the
+/// personal dictionary.
+pub struct SpellChecker {
+    dictionary: Dictionary,
+    personal_dictionary_path: PathBuf,
+    /// Results of the checked words since the same words are checked on each render.
+    cache: RefCell<HashMap<String, bool>>,
+}
+
+impl SpellChecker {
+    /// Loads the dictionary of the configured language from the first directory containing it
+    /// with the words of the personal dictionary if it exists.
+    pub fn load(settings: &SpellCheckSettings) -> anyhow::Result<Self> {
+        let dirs = settings.get_dictionary_dirs();
+        let (aff_path, dic_path) = dirs
+            .iter()
+            .map(|dir| {
+                (
+                    dir.join(format!("{}.aff", settings.language)),
+                    dir.join(format!("{}.dic", settings.language)),
+                )
+            })
+            .find(|(aff, dic)| aff.exists() && dic.exists())
+            .with_context(|| {
+                let dirs: Vec<_> = dirs.iter().map(|dir| dir.display().to_string()).collect();
+                format!(
+                    "Dictionary for language '{}' couldn't be found in the directories: {}",
+                    settings.language,
+                    dirs.join(", ")
+                )
+            })?;
+
+        let aff = fs::read(&aff_path)
+            .map_err(|err| anyhow!("Failed to read affix file. Error info: {err}"))?;
+        let dic = fs::read(&dic_path)
+            .map_err(|err| anyhow!("Failed to read dictionary file. Error info: {err}"))?;
+        let dictionary = Dictionary::parse(&aff, &dic)
+            .map_err(|err| anyhow!("Failed to parse dictionary. Error info: {err}"))?;
+
+        let personal_dictionary_path = settings.get_personal_dictionary_path()?;
+
+        Self::new(dictionary, personal_dictionary_path)
+    }
+
+    fn new(mut dictionary: Dictionary, personal_dictionary_path: PathBuf) -> anyhow::Result<Self> {
+        if personal_dictionary_path.exists() {
+            let personal_words = fs::read_to_string(&personal_dictionary_path)
+                .map_err(|err| anyhow!("Failed to read personal dictionary. Error info: {err}"))?;
+            personal_words
+                .lines()
+                .map(str::trim)
+                .filter(|word| !word.is_empty())
+                .for_each(|word| dictionary.add_word(word));
+        }
+
+        Ok(Self {
+            dictionary,
+            personal_dictionary_path,
+            cache: RefCell::default(),
+        })
+    }
+
+    pub fn check(&self, word: &str) -> bool {
+        if let Some(correct) = self.cache.borrow().get(word) {
+            return *correct;
+        }
+
+        let correct = self.dictionary.check(word);
+        self.cache.borrow_mut().insert(word.to_owned(), correct);
+
+        correct
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        self.dictionary.suggest(word)
+    }
+
+    /// Adds the word to the personal dictionary file, creating it if it doesn't exist.
+    pub fn add_word(&mut self, word: &str) -> anyhow::Result<()> {
+        if let Some(parent) = self.personal_dictionary_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.personal_dictionary_path)
+            .map_err(|err| anyhow!("Failed to open personal dictionary. Error info: {err}"))?;
+        writeln!(file, "{word}")?;
+
+        self.dictionary.add_word(word);
+        self.cache.get_mut().remove(word);
+
+        Ok(())
+    }
+
+    /// Returns the byte ranges of the misspelled words in the line.
+    pub fn misspelled_words(&self, line: &str) -> Vec<Range<usize>> {
+        words(line)
+            .filter(|range| !self.check(&line[range.clone()]))
+            .collect()
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '\'' || ch == '’'
+}
+
+/// Returns the byte ranges of the words in the line to be checked. Tokens attached to digits or
+/// to characters used in paths, identifiers and addresses are skipped.
+pub fn words(line: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let is_attached = |ch: char| ch.is_numeric() || matches!(ch, '_' | '/' | '\\' | '@');
+
+    let mut chars = line.char_indices().peekable();
+    std::iter::from_fn(move || {
+        loop {
+            let (start, first) = chars.next()?;
+            if !is_word_char(first) {
+                continue;
+            }
+
+            let mut end = start + first.len_utf8();
+            let mut attached = line[..start].chars().next_back().is_some_and(is_attached);
+            while let Some((index, ch)) = chars.peek().copied() {
+                if is_word_char(ch) {
+                    end = index + ch.len_utf8();
+                    chars.next();
+                } else {
+                    attached |= is_attached(ch);
+                    break;
+                }
+            }
+
+            if attached {
+                continue;
+            }
+
+            // Quotes around the word aren't part of it.
+            let word = &line[start..end];
+            let trimmed_start = word.len() - word.trim_start_matches(['\'', '’']).len();
+            let trimmed_end = word.trim_end_matches(['\'', '’']).len();
+            if trimmed_start < trimmed_end {
+                return Some(start + trimmed_start..start + trimmed_end);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "TRY abcdefghijklmnopqrstuvwxyz\nSFX S Y 1\nSFX S 0 s .\n";
+    const DIC: &str = "3\nword/S\nthe\nis\n";
+
+    #[test]
+    fn split_words() {
+        let line = "It's 'quoted' snake_case path/to 3rd word, café!";
+
+        let found: Vec<&str> = words(line).map(|range| &line[range]).collect();
+
+        assert_eq!(found, ["It's", "quoted", "word", "café"]);
+    }
+
+    #[test]
+    fn personal_dictionary_words() {
+        let dir = tempfile::Builder::new()
+            .prefix("spell-check")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("nested").join("dictionary.txt");
+        let dictionary = Dictionary::parse(AFF.as_bytes(), DIC.as_bytes()).unwrap();
+        let mut checker = SpellChecker::new(dictionary, path.clone()).unwrap();
+
+        let line = "the words is tuijournal";
+        let misspelled: Vec<&str> = checker
+            .misspelled_words(line)
+            .into_iter()
+            .map(|range| &line[range])
+            .collect();
+        assert_eq!(misspelled, ["tuijournal"]);
+
+        checker.add_word("tuijournal").unwrap();
+        assert!(checker.misspelled_words(line).is_empty());
+
+        // Added words are loaded again from the personal dictionary.
+        let dictionary = Dictionary::parse(AFF.as_bytes(), DIC.as_bytes()).unwrap();
+        let checker = SpellChecker::new(dictionary, path).unwrap();
+        assert!(checker.check("tuijournal"));
+    }
+
+    #[test]
+    fn missing_dictionary_errors() {
+        let dir = tempfile::Builder::new()
+            .prefix("spell-check-missing")
+            .tempdir()
+            .unwrap();
+        let settings = SpellCheckSettings {
+            enabled: true,
+            language: String::from("xx_XX"),
+            dictionary_dirs: vec![dir.path().to_path_buf()],
+            personal_dictionary: None,
+        };
+
+        let err = SpellChecker::load(&settings).err().unwrap();
+
+        assert!(err.to_string().contains("xx_XX"));
+    }
+}
@@ -3,6 +3,10 @@ use std::{fs, io::BufWriter};
 use directories::BaseDirs;
 use serde::{Deserialize, Serialize};
 
+use crate::settings::layout::PanesOrientation;
+
+use super::filter::FilterPreset;
+
 use super::*;
 
 const STATE_FILE_NAME: &str = "state.json";
@@ -11,6 +15,41 @@ const STATE_FILE_NAME: &str = "state.json";
 pub struct AppState {
     pub sorter: Sorter,
     pub full_screen: bool,
+    #[serde(default)]
+    /// Orientation of the panes changed at runtime, overriding the one from the settings.
+    pub panes_orientation: Option<PanesOrientation>,
+    #[serde(default)]
+    /// Size of the entries list changed at runtime, overriding the one from the settings.
+    pub entries_list_percentage: Option<u16>,
+    #[serde(default)]
+    pub content_pane: ContentPaneMode,
+    #[serde(default)]
+    /// Renders the Markdown of the content formatted in the preview pane.
+    pub render_markdown: bool,
+    #[serde(default)]
+    pub filter_presets: Vec<FilterPreset>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+/// Represents what is rendered next to the entries list.
+pub enum ContentPaneMode {
+    #[default]
+    /// Editor for the content of the current entry.
+    Editor,
+    /// Read-only preview of the current entry while the entries list keeps the focus.
+    Preview,
+    /// Entries list only.
+    Hidden,
+}
+
+impl ContentPaneMode {
+    pub fn next(self) -> Self {
+        match self {
+            ContentPaneMode::Editor => ContentPaneMode::Preview,
+            ContentPaneMode::Preview => ContentPaneMode::Hidden,
+            ContentPaneMode::Hidden => ContentPaneMode::Editor,
+        }
+    }
 }
 
 impl AppState {
@@ -37,10 +76,15 @@ impl AppState {
     }
 
     fn get_persist_path(settings: &Settings) -> anyhow::Result<PathBuf> {
-        if let Some(path) = settings.app_state_dir.as_ref() {
-            Ok(path.join(STATE_FILE_NAME))
-        } else {
-            Self::default_persist_dir().map(|dir| dir.join(STATE_FILE_NAME))
+        Self::get_persist_dir(settings).map(|dir| dir.join(STATE_FILE_NAME))
+    }
+
+    /// Return the directory used to persist the application state, preferring the one from the
+    /// settings over the default one.
+    pub fn get_persist_dir(settings: &Settings) -> anyhow::Result<PathBuf> {
+        match settings.app_state_dir.as_ref() {
+            Some(path) => Ok(path.clone()),
+            None => Self::default_persist_dir(),
         }
     }
 
@@ -149,6 +193,10 @@ impl AppState {
 mod tests {
     use std::path::PathBuf;
 
+    use crate::app::filter::{
+        CriteriaRelation, Filter, FilterCriterion, criterion::TagFilterOption,
+    };
+
     use super::*;
 
     #[test]
@@ -189,16 +237,65 @@ mod tests {
         let state = AppState {
             sorter,
             full_screen: true,
+            panes_orientation: Some(PanesOrientation::Vertical),
+            entries_list_percentage: Some(45),
+            content_pane: ContentPaneMode::Preview,
+            render_markdown: true,
+            filter_presets: vec![FilterPreset::new(
+                String::from("Work"),
+                Filter {
+                    relation: CriteriaRelation::Or,
+                    criteria: vec![
+                        FilterCriterion::Tag(TagFilterOption::Tag(String::from("work"))),
+                        FilterCriterion::DateRange {
+                            from: NaiveDate::from_ymd_opt(2024, 3, 1),
+                            to: None,
+                        },
+                    ],
+                    query: Some("tag:oncall AND NOT priority<2".parse().unwrap()),
+                },
+            )],
         };
 
         state.save(&settings).unwrap();
         let loaded = AppState::load(&settings).unwrap();
 
         assert!(loaded.full_screen);
+        assert_eq!(loaded.panes_orientation, Some(PanesOrientation::Vertical));
+        assert_eq!(loaded.entries_list_percentage, Some(45));
+        assert_eq!(loaded.content_pane, ContentPaneMode::Preview);
+        assert!(loaded.render_markdown);
+        assert_eq!(loaded.filter_presets, state.filter_presets);
         assert_eq!(loaded.sorter.get_criteria(), &[SortCriteria::Title]);
         assert!(matches!(loaded.sorter.order, SortOrder::Ascending));
     }
 
+    #[test]
+    fn legacy_state_loads_layout_defaults() {
+        let dir = tempfile::Builder::new()
+            .prefix("state-legacy")
+            .tempdir()
+            .unwrap();
+        let settings = Settings {
+            app_state_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let sorter = serde_json::to_string(&Sorter::default()).unwrap();
+        fs::write(
+            dir.path().join(STATE_FILE_NAME),
+            format!(r#"{{"sorter": {sorter}, "full_screen": false}}"#),
+        )
+        .unwrap();
+
+        let loaded = AppState::load(&settings).unwrap();
+
+        assert_eq!(loaded.panes_orientation, None);
+        assert_eq!(loaded.entries_list_percentage, None);
+        assert_eq!(loaded.content_pane, ContentPaneMode::Editor);
+        assert!(!loaded.render_markdown);
+        assert!(loaded.filter_presets.is_empty());
+    }
+
     #[test]
     fn custom_dir_sets_path() {
         let settings = Settings {
@@ -105,6 +105,7 @@ async fn test_filter_relations() {
     let mut filter = Filter {
         criteria,
         relation: CriteriaRelation::Or,
+        query: None,
     };
 
     app.apply_filter(Some(filter.clone()));
@@ -265,3 +266,92 @@ async fn cycle_tag_existing_filter() {
         1
     );
 }
+
+#[tokio::test]
+async fn test_date_range_with_relations() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let date_range = FilterCriterion::DateRange {
+        from: NaiveDate::from_ymd_opt(2023, 10, 1),
+        to: NaiveDate::from_ymd_opt(2023, 10, 31),
+    };
+    let title = FilterCriterion::Title(String::from("Title 2"));
+
+    let filter = Filter {
+        relation: CriteriaRelation::And,
+        criteria: vec![date_range.clone(), title.clone()],
+        query: None,
+    };
+    app.apply_filter(Some(filter));
+    assert_eq!(app.get_active_entries().count(), 0);
+
+    let filter = Filter {
+        relation: CriteriaRelation::Or,
+        criteria: vec![date_range.clone(), title],
+        query: None,
+    };
+    app.apply_filter(Some(filter));
+    assert_eq!(app.get_active_entries().count(), 2);
+
+    let filter = Filter {
+        relation: CriteriaRelation::And,
+        criteria: vec![date_range],
+        query: None,
+    };
+    app.apply_filter(Some(filter));
+    assert_eq!(app.get_active_entries().count(), 1);
+    assert_eq!(app.get_active_entries().next().unwrap().id, 0);
+}
+
+#[tokio::test]
+async fn test_filter_query_with_criteria() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let filter = Filter {
+        query: Some("NOT tag:\"Tag 1\" OR priority>=2".parse().unwrap()),
+        ..Default::default()
+    };
+    app.apply_filter(Some(filter));
+    assert_eq!(app.get_active_entries().count(), 1);
+    assert_eq!(app.get_active_entries().next().unwrap().id, 1);
+
+    // Query is combined with the criteria using AND
+    let filter = Filter {
+        relation: CriteriaRelation::Or,
+        criteria: vec![FilterCriterion::Title(String::from("Title 1"))],
+        query: Some("NOT tag:\"Tag 1\"".parse().unwrap()),
+    };
+    app.apply_filter(Some(filter));
+    assert_eq!(app.get_active_entries().count(), 0);
+}
+
+#[tokio::test]
+async fn test_filter_presets() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let title_filter = |title: &str| Filter {
+        criteria: vec![FilterCriterion::Title(String::from(title))],
+        ..Default::default()
+    };
+
+    app.save_filter_preset(String::from("Second"), title_filter("Title 2"));
+    app.save_filter_preset(String::from("First"), title_filter("Title 1"));
+    // Saving with an existing name replaces the preset.
+    app.save_filter_preset(String::from("Second"), title_filter("title 2"));
+    assert_eq!(app.get_filter_presets().len(), 2);
+    assert_eq!(app.get_filter_presets()[0].filter, title_filter("title 2"));
+
+    assert!(app.apply_filter_preset(1));
+    assert_eq!(app.get_active_entries().count(), 1);
+    assert_eq!(app.get_active_entries().next().unwrap().id, 0);
+
+    app.rename_filter_preset(1, String::from("Renamed"));
+    assert_eq!(app.get_filter_presets()[1].name, "Renamed");
+
+    app.delete_filter_preset(0);
+    assert_eq!(app.get_filter_presets().len(), 1);
+    assert!(!app.apply_filter_preset(1));
+}
@@ -69,7 +69,11 @@ async fn test_data_provider_errors() {
     );
     assert!(app.delete_entry(0).await.is_err());
     assert!(app.get_current_entry().is_none());
-    assert!(app.export_entries(PathBuf::default()).await.is_err());
+    assert!(
+        app.export_entries(PathBuf::default(), ExportFormat::Json)
+            .await
+            .is_err()
+    );
     assert!(app.import_entries(PathBuf::default()).await.is_err());
 }
 
@@ -229,3 +233,332 @@ async fn test_sorter_with_filter() {
     let ids: Vec<u32> = app.get_active_entries().map(|entry| entry.id).collect();
     assert_eq!(ids, vec![4, 2, 0], "Apply Filter Then Sorter Descending");
 }
+
+#[test]
+fn resize_entries_list_is_bounded() {
+    let mut app = create_default_app();
+    assert_eq!(app.get_entries_list_percentage(), 30);
+
+    app.resize_entries_list(5);
+    assert_eq!(app.get_entries_list_percentage(), 35);
+
+    app.resize_entries_list(100);
+    assert_eq!(
+        app.get_entries_list_percentage(),
+        MAX_ENTRIES_LIST_PERCENTAGE
+    );
+
+    app.resize_entries_list(-100);
+    assert_eq!(
+        app.get_entries_list_percentage(),
+        MIN_ENTRIES_LIST_PERCENTAGE
+    );
+}
+
+#[test]
+fn runtime_orientation_overrides_settings() {
+    let mut app = create_default_app();
+    app.settings.layout.orientation = PanesOrientation::Vertical;
+    assert_eq!(app.get_panes_orientation(), PanesOrientation::Vertical);
+
+    app.toggle_panes_orientation();
+    assert_eq!(app.get_panes_orientation(), PanesOrientation::Horizontal);
+    assert_eq!(
+        app.state.panes_orientation,
+        Some(PanesOrientation::Horizontal)
+    );
+}
+
+#[tokio::test]
+async fn closest_entry_to_date() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let near_first = NaiveDate::from_ymd_opt(2023, 10, 20).unwrap();
+    let near_second = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+    assert_eq!(app.get_closest_entry_id(near_first), Some(0));
+    assert_eq!(app.get_closest_entry_id(near_second), Some(1));
+}
+
+#[tokio::test]
+async fn export_missing_entry_content_fails() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let err = app
+        .export_entry_content(99, PathBuf::default(), ExportFormat::Json)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("99"));
+}
+
+#[tokio::test]
+async fn headless_export_with_template() {
+    let dir = tempfile::Builder::new()
+        .prefix("headless-export")
+        .tempdir()
+        .unwrap();
+    let templates_dir = dir.path().join("templates");
+    std::fs::create_dir(&templates_dir).unwrap();
+    std::fs::write(
+        templates_dir.join("titles.md.jinja"),
+        "{% for entry in entries %}- {{ entry.title }}\n{% endfor %}",
+    )
+    .unwrap();
+
+    let mut settings = Settings::default();
+    settings.export.templates_dir = Some(templates_dir);
+    let app = App::new(MockDataProvider::new_with_data(), settings);
+    let output = dir.path().join("out").join("titles.md");
+
+    let cmd = crate::cli::HeadlessCliCommand::Export {
+        template: Some(String::from("titles")),
+        format: None,
+        output: Some(output.clone()),
+        filter: Some(crate::app::filter::query::parse_filter_query("tag:\"Tag 1\"").unwrap()),
+    };
+    headless::exec(app, cmd).await.unwrap();
+
+    assert_eq!(std::fs::read_to_string(output).unwrap(), "- Title 1\n");
+}
+
+#[tokio::test]
+async fn import_csv_updates_journals() {
+    let dir = tempfile::Builder::new()
+        .prefix("csv-update")
+        .tempdir()
+        .unwrap();
+    let path = dir.path().join("journals.csv");
+    std::fs::write(
+        &path,
+        "id,date,title,tags,priority,content\n\
+        1,2023-12-02 01:02:03,Title 2 edited,\"Tag 3, Tag 4\",5,Content 2\n\
+        ,2024-01-01,Added,,,New content\n\
+        7,2024-01-02,Missing,,,\n",
+    )
+    .unwrap();
+
+    let mut app = create_default_app();
+    let source = ImportSource {
+        path,
+        format: crate::cli::CliImportFormat::Csv,
+        columns: Vec::new(),
+        update: true,
+    };
+    let result = app.import_from(&source).await.unwrap();
+    app.load_entries().await.unwrap();
+
+    assert_eq!(result.updates.len(), 1);
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(app.entries.len(), 3);
+
+    let updated = app.get_entry(1).unwrap();
+    assert_eq!(updated.title, "Title 2 edited");
+    assert_eq!(updated.tags, [String::from("Tag 3"), String::from("Tag 4")]);
+    assert_eq!(updated.priority, Some(5));
+    assert!(app.entries.iter().any(|entry| entry.title == "Added"));
+}
+
+#[tokio::test]
+async fn import_writes_nothing_on_invalid_journals() {
+    let dir = tempfile::Builder::new()
+        .prefix("import-invalid")
+        .tempdir()
+        .unwrap();
+    let path = dir.path().join("journals.json");
+    let drafts = ["Valid", " "]
+        .map(|title| EntryDraft::new(Utc::now(), String::from(title), Vec::new(), None));
+    std::fs::write(
+        &path,
+        serde_json::to_string(&EntriesDTO::new(drafts.to_vec())).unwrap(),
+    )
+    .unwrap();
+
+    let mut app = create_default_app();
+    let source = ImportSource {
+        path,
+        format: crate::cli::CliImportFormat::Json,
+        columns: Vec::new(),
+        update: false,
+    };
+    assert!(app.import_from(&source).await.is_err());
+
+    app.load_entries().await.unwrap();
+    assert_eq!(app.entries.len(), 2);
+}
+
+#[tokio::test]
+async fn headless_add_entry() {
+    let dir = tempfile::Builder::new()
+        .prefix("headless-add")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("entries.json");
+    let settings = Settings {
+        default_journal_priority: Some(3),
+        ..Default::default()
+    };
+    let app = App::new(backend::JsonDataProvide::new(file_path.clone()), settings);
+
+    let cmd = crate::cli::HeadlessCliCommand::AddEntry {
+        title: String::from(" Deploy "),
+        tags: vec![String::from("work"), String::from(" work "), String::new()],
+        priority: None,
+        date: NaiveDate::from_ymd_opt(2024, 3, 5),
+        content: Some(String::from("Released v2")),
+    };
+    headless::exec(app, cmd).await.unwrap();
+
+    let entries = backend::JsonDataProvide::new(file_path)
+        .load_all_entries()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].title, "Deploy");
+    assert_eq!(entries[0].content, "Released v2");
+    assert_eq!(entries[0].tags, [String::from("work")]);
+    assert_eq!(entries[0].priority, Some(3));
+    assert_eq!(
+        entries[0].date,
+        Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn headless_list_entries() {
+    use crate::cli::{CliSortCriteria, ListArgs};
+
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+    add_extra_entries_drafts(&mut app).await;
+
+    let args = ListArgs {
+        since: NaiveDate::from_ymd_opt(2023, 11, 1),
+        sort: vec![CliSortCriteria::Title],
+        ascending: true,
+        ..Default::default()
+    };
+    let titles: Vec<&str> = headless::list_entries(&mut app, None, &args)
+        .iter()
+        .map(|entry| entry.title.as_str())
+        .collect();
+    assert_eq!(titles, ["Title 2", "Title 3"]);
+
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+    let query = crate::app::filter::query::parse_filter_query("content").unwrap();
+    let args = ListArgs {
+        tags: vec![String::from("Tag 1")],
+        ..Default::default()
+    };
+    let ids: Vec<u32> = headless::list_entries(&mut app, Some(query), &args)
+        .iter()
+        .map(|entry| entry.id)
+        .collect();
+    assert_eq!(ids, [0]);
+}
+
+#[tokio::test]
+async fn headless_set_and_remove_entries() {
+    use crate::cli::HeadlessCliCommand;
+
+    let dir = tempfile::Builder::new()
+        .prefix("headless-modify")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("entries.json");
+    let provider = backend::JsonDataProvide::new(file_path.clone());
+    for title in ["First", "Second"] {
+        let draft = EntryDraft::new(
+            Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap(),
+            String::from(title),
+            vec![String::from("todo"), String::from("work")],
+            Some(1),
+        );
+        provider.add_entry(draft).await.unwrap();
+    }
+    let exec = |cmd| {
+        let app = App::new(
+            backend::JsonDataProvide::new(file_path.clone()),
+            Settings::default(),
+        );
+        headless::exec(app, cmd)
+    };
+
+    exec(HeadlessCliCommand::SetAttributes {
+        id: 0,
+        title: Some(String::from("Renamed")),
+        tags_add: vec![String::from("done")],
+        tags_remove: vec![String::from("todo")],
+        priority: Some(None),
+    })
+    .await
+    .unwrap();
+
+    // Titles are validated before reaching the back-end since not all back-ends check them.
+    let err = exec(HeadlessCliCommand::SetAttributes {
+        id: 1,
+        title: Some(String::from("  ")),
+        tags_add: Vec::new(),
+        tags_remove: Vec::new(),
+        priority: None,
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Entry title can't be empty");
+
+    let err = exec(HeadlessCliCommand::RemoveEntries {
+        ids: vec![1, 7],
+        confirmed: true,
+    })
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("ID 7 doesn't exist"));
+
+    // Repeated IDs are deleted once.
+    exec(HeadlessCliCommand::RemoveEntries {
+        ids: vec![1, 1],
+        confirmed: true,
+    })
+    .await
+    .unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].title, "Renamed");
+    assert_eq!(
+        entries[0].tags,
+        [String::from("work"), String::from("done")]
+    );
+    assert_eq!(entries[0].priority, None);
+}
+
+#[tokio::test]
+async fn standard_profile_list_keys_with_list_focused() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::app::keymap::Input;
+    use crate::app::ui::Styles;
+    use crate::settings::EditorProfile;
+
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let mut ui_components = UIComponents::new(Styles::default(), EditorProfile::Standard);
+    let first_id = app.get_active_entries().next().map(|entry| entry.id);
+    ui_components.set_current_entry(first_id, &mut app);
+
+    ui_components
+        .handle_input(
+            &Input::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            &mut app,
+        )
+        .await
+        .unwrap();
+
+    // The key moves the selection in the list instead of being typed in the editor.
+    let second_id = app.get_active_entries().nth(1).map(|entry| entry.id);
+    assert_eq!(app.current_entry_id, second_id);
+    assert!(!ui_components.has_unsaved());
+}
@@ -185,3 +185,44 @@ async fn many() {
         assert_eq!(app.entries.len(), current_count);
     }
 }
+
+#[tokio::test]
+/// Bulk edits on the selected entries are undone and redone at once
+async fn bulk_edit_group() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let tags_of = |app: &App<MockDataProvider>, id| app.get_entry(id).unwrap().tags.clone();
+
+    app.selected_entries.extend([0, 1]);
+    let changed = app
+        .apply_bulk_edit(&BulkEdit::AddTags(vec![String::from("Tag 2")]))
+        .await
+        .unwrap();
+    // Entry 0 has the tag already.
+    assert_eq!(changed, 1);
+
+    let changed = app
+        .apply_bulk_edit(&BulkEdit::RenameTag {
+            from: String::from("Tag 2"),
+            to: String::from("Renamed"),
+        })
+        .await
+        .unwrap();
+    assert_eq!(changed, 2);
+    assert_eq!(tags_of(&app, 0), ["Tag 1", "Renamed"]);
+    assert_eq!(tags_of(&app, 1), ["Renamed"]);
+
+    app.undo().await.unwrap();
+    assert_eq!(tags_of(&app, 0), ["Tag 1", "Tag 2"]);
+    assert_eq!(tags_of(&app, 1), ["Tag 2"]);
+
+    app.undo().await.unwrap();
+    assert!(tags_of(&app, 1).is_empty());
+
+    app.redo().await.unwrap();
+    app.redo().await.unwrap();
+    assert_eq!(tags_of(&app, 0), ["Tag 1", "Renamed"]);
+    assert_eq!(tags_of(&app, 1), ["Renamed"]);
+    assert!(app.redo().await.unwrap().is_none());
+}
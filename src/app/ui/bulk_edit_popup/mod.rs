@@ -0,0 +1,287 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use tui_textarea::TextArea;
+
+use crate::app::{BulkEdit, keymap::Input};
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect_exact_height};
+
+type BulkEditPopupReturn = PopupReturn<BulkEdit>;
+
+const FOOTER_TEXT: &str =
+    "Enter or <Ctrl-m>: Apply to the selected journals | Esc or <Ctrl-c>: Cancel";
+const FOOTER_MARGIN: usize = 8;
+
+/// Separator between the current and the new name when renaming a tag.
+const RENAME_SEPARATOR: &str = "->";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkEditKind {
+    AddTags,
+    RemoveTags,
+    RenameTag,
+    SetPriority,
+    ShiftDates,
+}
+
+impl BulkEditKind {
+    fn title(self, count: usize) -> String {
+        match self {
+            BulkEditKind::AddTags => format!("Add tags to {count} journals"),
+            BulkEditKind::RemoveTags => format!("Remove tags from {count} journals"),
+            BulkEditKind::RenameTag => format!("Rename tag in {count} journals"),
+            BulkEditKind::SetPriority => format!("Set priority of {count} journals"),
+            BulkEditKind::ShiftDates => format!("Shift dates of {count} journals"),
+        }
+    }
+
+    fn input_hint(self) -> &'static str {
+        match self {
+            BulkEditKind::AddTags | BulkEditKind::RemoveTags => "Tags separated with commas",
+            BulkEditKind::RenameTag => "Current tag -> New tag",
+            BulkEditKind::SetPriority => "Priority, or empty to clear it",
+            BulkEditKind::ShiftDates => "Days to move the dates by, like 3 or -7",
+        }
+    }
+}
+
+/// Asks for the input of an edit to apply on all selected journals in multi-select mode.
+pub struct BulkEditPopup<'a> {
+    kind: BulkEditKind,
+    selected_count: usize,
+    input_txt: TextArea<'a>,
+    err_msg: String,
+}
+
+impl BulkEditPopup<'_> {
+    pub fn new(kind: BulkEditKind, selected_count: usize) -> Self {
+        Self {
+            kind,
+            selected_count,
+            input_txt: TextArea::default(),
+            err_msg: String::new(),
+        }
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let mut area = centered_rect_exact_height(60, 9, area);
+
+        if area.width < 40 {
+            area.width = 40.min(frame.area().width);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(self.kind.title(self.selected_count));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(4)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Length(3),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        self.render_input(frame, chunks[0], styles);
+
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, chunks[1]);
+    }
+
+    fn render_input(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let gstyles = &styles.general;
+        let (block, cursor_style) = if self.err_msg.is_empty() {
+            (
+                Block::default()
+                    .title(self.kind.input_hint())
+                    .borders(Borders::ALL)
+                    .style(gstyles.input_block_active),
+                Style::from(gstyles.input_cursor_active),
+            )
+        } else {
+            (
+                Block::default()
+                    .title(self.err_msg.to_owned())
+                    .borders(Borders::ALL)
+                    .style(gstyles.input_block_invalid),
+                Style::from(gstyles.input_cursor_invalid),
+            )
+        };
+
+        self.input_txt.set_cursor_style(cursor_style);
+        self.input_txt.set_cursor_line_style(Style::default());
+        self.input_txt.set_block(block);
+
+        frame.render_widget(&self.input_txt, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> BulkEditPopupReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc => PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => PopupReturn::Cancel,
+            KeyCode::Enter => self.confirm(),
+            KeyCode::Char('m') if has_control => self.confirm(),
+            _ => {
+                if self.input_txt.input(KeyEvent::from(input)) {
+                    // Errors are cleared while typing and shown again on confirming.
+                    self.err_msg.clear();
+                }
+                PopupReturn::KeepPopup
+            }
+        }
+    }
+
+    fn confirm(&mut self) -> BulkEditPopupReturn {
+        match parse_edit(self.kind, &self.input_txt.lines()[0]) {
+            Ok(edit) => PopupReturn::Apply(edit),
+            Err(err) => {
+                self.err_msg = err;
+                PopupReturn::KeepPopup
+            }
+        }
+    }
+}
+
+fn parse_edit(kind: BulkEditKind, text: &str) -> Result<BulkEdit, String> {
+    match kind {
+        BulkEditKind::AddTags => parse_tags(text).map(BulkEdit::AddTags),
+        BulkEditKind::RemoveTags => parse_tags(text).map(BulkEdit::RemoveTags),
+        BulkEditKind::RenameTag => {
+            let (from, to) = text
+                .split_once(RENAME_SEPARATOR)
+                .map(|(from, to)| (from.trim(), to.trim()))
+                .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+                .ok_or_else(|| {
+                    format!("Enter the tags as: Current tag {RENAME_SEPARATOR} New tag")
+                })?;
+
+            if to.contains(',') {
+                return Err(String::from("New tag can't contain commas"));
+            }
+
+            Ok(BulkEdit::RenameTag {
+                from: from.to_owned(),
+                to: to.to_owned(),
+            })
+        }
+        BulkEditKind::SetPriority => match text.trim() {
+            "" => Ok(BulkEdit::SetPriority(None)),
+            priority => priority
+                .parse()
+                .map(|priority| BulkEdit::SetPriority(Some(priority)))
+                .map_err(|_| String::from("Priority must be a positive number")),
+        },
+        BulkEditKind::ShiftDates => match text.trim().parse::<i64>() {
+            Ok(0) => Err(String::from("Days can't be zero")),
+            Ok(days) => Ok(BulkEdit::ShiftDates(days)),
+            Err(_) => Err(String::from("Days must be a whole number like 3 or -7")),
+        },
+    }
+}
+
+fn parse_tags(text: &str) -> Result<Vec<String>, String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_owned());
+        }
+    }
+
+    if tags.is_empty() {
+        Err(String::from("Enter at least one tag"))
+    } else {
+        Ok(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_edits() {
+        assert_eq!(
+            parse_edit(BulkEditKind::AddTags, " work, q2 ,, work"),
+            Ok(BulkEdit::AddTags(vec![
+                String::from("work"),
+                String::from("q2")
+            ]))
+        );
+        assert!(parse_edit(BulkEditKind::RemoveTags, " , ").is_err());
+
+        assert_eq!(
+            parse_edit(BulkEditKind::RenameTag, "q2 -> Q2"),
+            Ok(BulkEdit::RenameTag {
+                from: String::from("q2"),
+                to: String::from("Q2"),
+            })
+        );
+        assert!(parse_edit(BulkEditKind::RenameTag, "q2").is_err());
+        assert!(parse_edit(BulkEditKind::RenameTag, "q2 -> a, b").is_err());
+
+        assert_eq!(
+            parse_edit(BulkEditKind::SetPriority, ""),
+            Ok(BulkEdit::SetPriority(None))
+        );
+        assert_eq!(
+            parse_edit(BulkEditKind::SetPriority, "2"),
+            Ok(BulkEdit::SetPriority(Some(2)))
+        );
+        assert!(parse_edit(BulkEditKind::SetPriority, "-1").is_err());
+
+        assert_eq!(
+            parse_edit(BulkEditKind::ShiftDates, "+3"),
+            Ok(BulkEdit::ShiftDates(3))
+        );
+        assert_eq!(
+            parse_edit(BulkEditKind::ShiftDates, "-7"),
+            Ok(BulkEdit::ShiftDates(-7))
+        );
+        assert!(parse_edit(BulkEditKind::ShiftDates, "0").is_err());
+        assert!(parse_edit(BulkEditKind::ShiftDates, "week").is_err());
+    }
+
+    #[test]
+    fn confirm_shows_errors() {
+        let mut popup = BulkEditPopup::new(BulkEditKind::SetPriority, 2);
+        popup.handle_input(&Input::new(KeyCode::Char('x'), KeyModifiers::NONE));
+
+        let result = popup.handle_input(&Input::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(result, PopupReturn::KeepPopup));
+        assert!(!popup.err_msg.is_empty());
+
+        popup.handle_input(&Input::new(KeyCode::Backspace, KeyModifiers::NONE));
+        popup.handle_input(&Input::new(KeyCode::Char('3'), KeyModifiers::NONE));
+        assert!(popup.err_msg.is_empty());
+
+        let result = popup.handle_input(&Input::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(
+            result,
+            PopupReturn::Apply(BulkEdit::SetPriority(Some(3)))
+        ));
+    }
+}
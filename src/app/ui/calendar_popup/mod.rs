@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Utc};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Clear, Paragraph, Wrap,
+        calendar::{CalendarEventStore, Monthly},
+    },
+};
+
+use crate::app::keymap::Input;
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect};
+
+type CalendarReturn = PopupReturn<CalendarAction>;
+
+const FOOTER_TEXT: &str = "Enter or <Ctrl-m>: Filter journals by day | <g>: Go to closest journal | Esc, <q> or <Ctrl-c>: Cancel | <h/l> or <Left/Right>: Previous/Next day | <k/j> or <Up/Down>: Previous/Next week | <H/L> or <PageUp/PageDown>: Previous/Next month | <t>: Today";
+const FOOTER_MARGIN: usize = 8;
+/// Width of the monthly calendar including its borders.
+const CALENDAR_WIDTH: u16 = 24;
+
+pub struct CalendarPopup {
+    selected_date: NaiveDate,
+    /// Count of journals for each day that has journals.
+    entries_per_day: BTreeMap<NaiveDate, usize>,
+}
+
+/// The action to be done on the selected day in calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarAction {
+    FilterDate(NaiveDate),
+    JumpToDate(NaiveDate),
+}
+
+impl CalendarPopup {
+    pub fn new(dates: impl Iterator<Item = NaiveDate>, selected_date: NaiveDate) -> Self {
+        let mut entries_per_day = BTreeMap::new();
+        for date in dates {
+            *entries_per_day.entry(date).or_insert(0) += 1;
+        }
+
+        Self {
+            selected_date,
+            entries_per_day,
+        }
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(60, 60, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Calendar");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(4)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Min(5),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        let body_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(CALENDAR_WIDTH), Constraint::Min(10)].as_ref())
+            .split(chunks[0]);
+
+        self.render_calendar(frame, body_chunks[0], styles);
+        self.render_month_days(frame, body_chunks[1], styles);
+        self.render_footer(frame, chunks[1]);
+    }
+
+    fn render_calendar(&self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let gstyles = &styles.general;
+        let mut events =
+            CalendarEventStore::today(Style::default().add_modifier(Modifier::UNDERLINED));
+
+        for date in self.entries_per_day.keys() {
+            if let Some(date) = to_time_date(*date) {
+                events.add(date, Style::from(gstyles.list_item_selected));
+            }
+        }
+
+        if let Some(selected) = to_time_date(self.selected_date) {
+            events.add(selected, Style::from(gstyles.list_highlight_active));
+        }
+
+        let display_date =
+            to_time_date(self.selected_date).expect("Dates from chrono are valid for time crate");
+
+        let calendar = Monthly::new(display_date, events)
+            .show_month_header(Style::default().add_modifier(Modifier::BOLD))
+            .show_weekdays_header(Style::default().add_modifier(Modifier::ITALIC))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            );
+
+        frame.render_widget(calendar, area);
+    }
+
+    fn render_month_days(&self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let month_start = self.selected_date.with_day(1).unwrap();
+        let next_month_start = month_start + Months::new(1);
+
+        let lines: Vec<Line> = self
+            .entries_per_day
+            .range(month_start..next_month_start)
+            .map(|(date, count)| {
+                let text = format!(
+                    "{} {:>2}: {count} journal{}",
+                    date.weekday(),
+                    date.day(),
+                    if *count == 1 { "" } else { "s" }
+                );
+                if *date == self.selected_date {
+                    Line::styled(text, Style::from(styles.general.list_highlight_active))
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+
+        let month_count: usize = self
+            .entries_per_day
+            .range(month_start..next_month_start)
+            .map(|(_, count)| count)
+            .sum();
+
+        let paragraph = if lines.is_empty() {
+            Paragraph::new("\nNo journals in this month").alignment(Alignment::Center)
+        } else {
+            Paragraph::new(lines)
+        };
+
+        let paragraph = paragraph.wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(format!("Journals in month: {month_count}")),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> CalendarReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc | KeyCode::Char('q') => return PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => return PopupReturn::Cancel,
+            KeyCode::Enter => return self.apply_filter(),
+            KeyCode::Char('m') if has_control => return self.apply_filter(),
+            KeyCode::Char('g') => {
+                return PopupReturn::Apply(CalendarAction::JumpToDate(self.selected_date));
+            }
+            KeyCode::Char('h') | KeyCode::Left => self.move_days(-1),
+            KeyCode::Char('l') | KeyCode::Right => self.move_days(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_days(-7),
+            KeyCode::Char('j') | KeyCode::Down => self.move_days(7),
+            KeyCode::Char('H') | KeyCode::PageUp => self.move_months(-1),
+            KeyCode::Char('L') | KeyCode::PageDown => self.move_months(1),
+            KeyCode::Char('t') => self.selected_date = Utc::now().date_naive(),
+            _ => {}
+        }
+
+        PopupReturn::KeepPopup
+    }
+
+    fn apply_filter(&self) -> CalendarReturn {
+        PopupReturn::Apply(CalendarAction::FilterDate(self.selected_date))
+    }
+
+    fn move_days(&mut self, days: i64) {
+        let moved = if days.is_negative() {
+            self.selected_date
+                .checked_sub_days(Days::new(days.unsigned_abs()))
+        } else {
+            self.selected_date.checked_add_days(Days::new(days as u64))
+        };
+
+        if let Some(date) = moved {
+            self.selected_date = date;
+        }
+    }
+
+    fn move_months(&mut self, months: i32) {
+        let moved = if months.is_negative() {
+            self.selected_date
+                .checked_sub_months(Months::new(months.unsigned_abs()))
+        } else {
+            self.selected_date
+                .checked_add_months(Months::new(months as u32))
+        };
+
+        if let Some(date) = moved {
+            self.selected_date = date;
+        }
+    }
+}
+
+fn to_time_date(date: NaiveDate) -> Option<time::Date> {
+    let month = time::Month::try_from(date.month() as u8).ok()?;
+    time::Date::from_calendar_date(date.year(), month, date.day() as u8).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn counts_entries_per_day() {
+        let dates = [date(2024, 3, 1), date(2024, 3, 1), date(2024, 3, 5)];
+
+        let popup = CalendarPopup::new(dates.into_iter(), date(2024, 3, 1));
+
+        assert_eq!(popup.entries_per_day.get(&date(2024, 3, 1)), Some(&2));
+        assert_eq!(popup.entries_per_day.get(&date(2024, 3, 5)), Some(&1));
+        assert_eq!(popup.entries_per_day.get(&date(2024, 3, 2)), None);
+    }
+
+    #[test]
+    fn navigation_moves_selected_date() {
+        let mut popup = CalendarPopup::new(std::iter::empty(), date(2024, 1, 31));
+
+        popup.move_days(1);
+        assert_eq!(popup.selected_date, date(2024, 2, 1));
+
+        popup.move_days(-7);
+        assert_eq!(popup.selected_date, date(2024, 1, 25));
+
+        // Months are clamped to the last day of the target month.
+        popup.selected_date = date(2024, 1, 31);
+        popup.move_months(1);
+        assert_eq!(popup.selected_date, date(2024, 2, 29));
+    }
+
+    #[test]
+    fn converts_to_time_date() {
+        let converted = to_time_date(date(2024, 2, 29)).unwrap();
+
+        assert_eq!(converted.year(), 2024);
+        assert_eq!(converted.month(), time::Month::February);
+        assert_eq!(converted.day(), 29);
+    }
+}
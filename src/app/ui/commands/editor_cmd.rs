@@ -7,10 +7,13 @@ use super::{ClipboardOperation, CmdResult};
 pub fn exec_back_editor_to_normal_mode(ui_components: &mut UIComponents) -> CmdResult {
     if ui_components.active_control == ControlType::EntryContentTxt {
         match ui_components.editor.get_editor_mode() {
-            EditorMode::Insert | EditorMode::Visual => {
+            EditorMode::Insert
+            | EditorMode::Visual
+            | EditorMode::Command
+            | EditorMode::OperatorPending(_) => {
                 ui_components.editor.set_editor_mode(EditorMode::Normal);
             }
-            EditorMode::Normal => {
+            EditorMode::Normal | EditorMode::Standard => {
                 ui_components.change_active_control(ControlType::EntriesList);
             }
         }
@@ -73,7 +76,12 @@ pub fn exec_toggle_editor_visual_mode(ui_components: &mut UIComponents) -> CmdRe
     match ui_components.editor.get_editor_mode() {
         EditorMode::Normal => ui_components.editor.set_editor_mode(EditorMode::Visual),
         EditorMode::Visual => ui_components.editor.set_editor_mode(EditorMode::Normal),
-        EditorMode::Insert => return Ok(HandleInputReturnType::NotFound),
+        EditorMode::Insert
+        | EditorMode::Command
+        | EditorMode::OperatorPending(_)
+        | EditorMode::Standard => {
+            return Ok(HandleInputReturnType::NotFound);
+        }
     }
 
     Ok(HandleInputReturnType::Handled)
@@ -96,3 +104,81 @@ pub fn exec_paste_os_clipboard(ui_components: &mut UIComponents) -> CmdResult {
         .editor
         .exec_os_clipboard(ClipboardOperation::Paste)
 }
+
+const SPELL_CHECK_DISABLED_MSG: &str = "Spell checking is disabled";
+
+pub fn exec_next_spelling_error<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    match app.spell_checker.as_ref() {
+        Some(checker) => ui_components.editor.jump_to_next_spelling_error(checker),
+        None => ui_components
+            .editor
+            .set_status_msg(SPELL_CHECK_DISABLED_MSG.into()),
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn exec_show_spelling_suggestions<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    let Some(checker) = app.spell_checker.as_ref() else {
+        ui_components
+            .editor
+            .set_status_msg(SPELL_CHECK_DISABLED_MSG.into());
+        return Ok(HandleInputReturnType::Handled);
+    };
+
+    let Some((row, range)) = ui_components.editor.word_at_cursor() else {
+        ui_components
+            .editor
+            .set_status_msg("No word under the cursor".into());
+        return Ok(HandleInputReturnType::Handled);
+    };
+
+    let word = ui_components.editor.get_text(row, range.clone()).to_owned();
+    if checker.check(&word) {
+        ui_components
+            .editor
+            .set_status_msg(format!("'{word}' is spelled correctly"));
+        return Ok(HandleInputReturnType::Handled);
+    }
+
+    let suggestions = checker.suggest(&word);
+    let popup = SpellingPopup::new(word, row, range, suggestions);
+    ui_components
+        .popup_stack
+        .push(Popup::Spelling(Box::new(popup)));
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn exec_add_word_to_dictionary<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    let Some(checker) = app.spell_checker.as_mut() else {
+        ui_components
+            .editor
+            .set_status_msg(SPELL_CHECK_DISABLED_MSG.into());
+        return Ok(HandleInputReturnType::Handled);
+    };
+
+    let Some((row, range)) = ui_components.editor.word_at_cursor() else {
+        ui_components
+            .editor
+            .set_status_msg("No word under the cursor".into());
+        return Ok(HandleInputReturnType::Handled);
+    };
+
+    let word = ui_components.editor.get_text(row, range).to_owned();
+    checker.add_word(&word)?;
+    ui_components
+        .editor
+        .set_status_msg(format!("'{word}' added to the personal dictionary"));
+
+    Ok(HandleInputReturnType::Handled)
+}
@@ -1,8 +1,7 @@
-use std::{collections::HashMap, env};
-
-use crate::app::{App, UIComponents, external_editor, ui::*};
+use crate::app::{App, UIComponents, external_editor, state::ContentPaneMode, ui::*};
 
 use backend::DataProvider;
+use chrono::Utc;
 
 use super::{
     CmdResult,
@@ -289,17 +288,7 @@ pub async fn edit_in_external_editor<D: DataProvider>(
     use tokio::fs;
 
     if let Some(entry) = app.get_current_entry() {
-        const TEMP_FILENAME: &str = "tui_journal";
-        let temp_extension = &app.settings.external_editor.temp_file_extension;
-
-        let mut builder = tempfile::Builder::new();
-        builder.prefix(TEMP_FILENAME);
-
-        if !temp_extension.is_empty() {
-            builder.suffix(temp_extension);
-        };
-
-        let temp_file = builder.tempfile_in(env::temp_dir())?;
+        let temp_file = external_editor::create_temp_file(&app.settings)?;
         let file_path = temp_file.path();
 
         fs::write(file_path, entry.content.as_str()).await?;
@@ -435,10 +424,7 @@ pub fn exec_show_fuzzy_find<D: DataProvider>(
 }
 
 fn show_fuzzy_find<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
-    let entries: HashMap<u32, String> = app
-        .get_active_entries()
-        .map(|entry| (entry.id, entry.title.to_owned()))
-        .collect();
+    let entries = app.get_active_entries().map(FuzzFindEntry::from).collect();
     ui_components
         .popup_stack
         .push(Popup::FuzzFind(Box::new(FuzzFindPopup::new(entries))));
@@ -464,11 +450,200 @@ pub async fn continue_fuzzy_find<D: DataProvider>(
     Ok(HandleInputReturnType::Handled)
 }
 
+pub fn exec_show_calendar<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    if ui_components.has_unsaved() {
+        ui_components.show_unsaved_msg_box(Some(UICommand::ShowCalendar));
+    } else {
+        show_calendar(ui_components, app);
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn exec_show_statistics<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    let popup = StatisticsPopup::new(app.get_active_entries(), Utc::now().date_naive());
+    ui_components
+        .popup_stack
+        .push(Popup::Statistics(Box::new(popup)));
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+fn show_calendar<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
+    let selected_date = app
+        .get_current_entry()
+        .map(|entry| entry.date.date_naive())
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let dates = app.entries.iter().map(|entry| entry.date.date_naive());
+
+    ui_components
+        .popup_stack
+        .push(Popup::Calendar(Box::new(CalendarPopup::new(
+            dates,
+            selected_date,
+        ))));
+}
+
+pub async fn continue_show_calendar<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+    msg_box_result: MsgBoxResult,
+) -> CmdResult {
+    match msg_box_result {
+        MsgBoxResult::Ok | MsgBoxResult::Cancel => {}
+        MsgBoxResult::Yes => {
+            exec_save_entry_content(ui_components, app).await?;
+            show_calendar(ui_components, app);
+        }
+        MsgBoxResult::No => {
+            discard_current_content(ui_components, app);
+            show_calendar(ui_components, app);
+        }
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn exec_show_filter_presets<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    if ui_components.has_unsaved() {
+        ui_components.show_unsaved_msg_box(Some(UICommand::ShowFilterPresets));
+    } else {
+        show_filter_presets(ui_components, app);
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+fn show_filter_presets<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
+    let presets_popup =
+        FilterPresetsPopup::new(app.get_filter_presets().to_vec(), app.filter.clone());
+    ui_components
+        .popup_stack
+        .push(Popup::FilterPresets(Box::new(presets_popup)));
+}
+
+pub async fn continue_show_filter_presets<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+    msg_box_result: MsgBoxResult,
+) -> CmdResult {
+    match msg_box_result {
+        MsgBoxResult::Ok | MsgBoxResult::Cancel => {}
+        MsgBoxResult::Yes => {
+            exec_save_entry_content(ui_components, app).await?;
+            show_filter_presets(ui_components, app);
+        }
+        MsgBoxResult::No => {
+            discard_current_content(ui_components, app);
+            show_filter_presets(ui_components, app);
+        }
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn apply_filter_preset<D: DataProvider>(
+    number: u8,
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) {
+    let Some(index) = (number as usize).checked_sub(1) else {
+        return;
+    };
+
+    if app.apply_filter_preset(index) {
+        ui_components.select_first_if_current_filtered(app);
+    } else {
+        ui_components.show_err_msg(format!("There is no saved filter preset number {number}"));
+    }
+}
+
 pub fn exec_toggle_full_screen_mode<D: DataProvider>(app: &mut App<D>) -> CmdResult {
     app.state.full_screen = !app.state.full_screen;
     Ok(HandleInputReturnType::Handled)
 }
 
+/// Percentage used to resize the entries list on each resize command.
+pub const RESIZE_STEP: i16 = 5;
+
+pub fn exec_resize_entries_list<D: DataProvider>(app: &mut App<D>, delta: i16) -> CmdResult {
+    app.resize_entries_list(delta);
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn exec_toggle_panes_orientation<D: DataProvider>(app: &mut App<D>) -> CmdResult {
+    app.toggle_panes_orientation();
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn exec_cycle_content_pane_mode<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    if ui_components.has_unsaved() {
+        ui_components.show_unsaved_msg_box(Some(UICommand::CycleContentPaneMode));
+    } else {
+        cycle_content_pane_mode(ui_components, app);
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+fn cycle_content_pane_mode<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
+    let mode = app.get_content_pane_mode().next();
+    app.set_content_pane_mode(mode);
+
+    // The entries list keeps the focus while the editor isn't visible.
+    if mode != ContentPaneMode::Editor {
+        ui_components.change_active_control(ControlType::EntriesList);
+    }
+}
+
+/// Toggles rendering the Markdown in the preview, showing the preview pane if the rendering
+/// gets activated.
+pub fn toggle_markdown_preview<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) {
+    let rendered = !app.is_markdown_rendered();
+    app.set_markdown_rendered(rendered);
+
+    if rendered && app.get_content_pane_mode() != ContentPaneMode::Preview {
+        app.set_content_pane_mode(ContentPaneMode::Preview);
+        ui_components.change_active_control(ControlType::EntriesList);
+    }
+}
+
+pub async fn continue_cycle_content_pane_mode<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+    msg_box_result: MsgBoxResult,
+) -> CmdResult {
+    match msg_box_result {
+        MsgBoxResult::Ok | MsgBoxResult::Cancel => {}
+        MsgBoxResult::Yes => {
+            exec_save_entry_content(ui_components, app).await?;
+            cycle_content_pane_mode(ui_components, app);
+        }
+        MsgBoxResult::No => {
+            // Discard the changes explicitly so the preview shows the saved content.
+            discard_current_content(ui_components, app);
+            cycle_content_pane_mode(ui_components, app);
+        }
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
 pub fn exec_show_sort_options<D: DataProvider>(
     ui_components: &mut UIComponents,
     app: &mut App<D>,
@@ -538,3 +713,13 @@ pub fn page_down_entries<D: DataProvider>(ui_components: &mut UIComponents, app:
 
     select_next_entry(step, ui_components, app);
 }
+
+pub fn show_search_popup<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
+    let entries = app
+        .get_active_entries()
+        .map(|entry| SearchEntry::new(entry.id, entry.title.to_owned(), &entry.content))
+        .collect();
+    ui_components
+        .popup_stack
+        .push(Popup::Search(Box::new(SearchPopup::new(entries))));
+}
@@ -43,12 +43,22 @@ pub fn exec_show_help(ui_components: &mut UIComponents) -> CmdResult {
 
     ui_components
         .popup_stack
-        .push(Popup::Help(Box::new(HelpPopup::new(start_tab))));
+        .push(Popup::Help(Box::new(HelpPopup::new(
+            start_tab,
+            ui_components.editor.get_profile(),
+        ))));
 
     Ok(HandleInputReturnType::Handled)
 }
 
-pub fn exec_cycle_forward(ui_components: &mut UIComponents) -> CmdResult {
+pub fn exec_cycle_forward<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    if !ui_components.can_focus_editor(app) {
+        return Ok(HandleInputReturnType::Handled);
+    }
+
     let next_control = match ui_components.active_control {
         ControlType::EntriesList => ControlType::EntryContentTxt,
         ControlType::EntryContentTxt => ControlType::EntriesList,
@@ -58,7 +68,14 @@ pub fn exec_cycle_forward(ui_components: &mut UIComponents) -> CmdResult {
     Ok(HandleInputReturnType::Handled)
 }
 
-pub fn exec_cycle_backward(ui_components: &mut UIComponents) -> CmdResult {
+pub fn exec_cycle_backward<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    if !ui_components.can_focus_editor(app) {
+        return Ok(HandleInputReturnType::Handled);
+    }
+
     let prev_control = match ui_components.active_control {
         ControlType::EntriesList => ControlType::EntryContentTxt,
         ControlType::EntryContentTxt => ControlType::EntriesList,
@@ -69,8 +86,11 @@ pub fn exec_cycle_backward(ui_components: &mut UIComponents) -> CmdResult {
     Ok(HandleInputReturnType::Handled)
 }
 
-pub fn exec_start_edit_content(ui_components: &mut UIComponents) -> CmdResult {
-    ui_components.start_edit_current_entry()?;
+pub fn exec_start_edit_content<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    ui_components.start_edit_current_entry(app)?;
 
     Ok(HandleInputReturnType::Handled)
 }
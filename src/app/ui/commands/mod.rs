@@ -3,7 +3,9 @@ use std::fmt::Debug;
 
 use multi_select_cmd::*;
 
-use super::{App, HandleInputReturnType, MsgBoxResult, UIComponents};
+use super::{
+    App, HandleInputReturnType, MsgBoxResult, UIComponents, bulk_edit_popup::BulkEditKind,
+};
 
 use editor_cmd::*;
 use entries_list_cmd::*;
@@ -51,6 +53,11 @@ pub enum UICommand {
     MulSelInverSelection,
     MulSelDeleteEntries,
     MulSelExportEntries,
+    MulSelAddTags,
+    MulSelRemoveTags,
+    MulSelRenameTag,
+    MulSelSetPriority,
+    MulSelShiftDates,
     ShowFilter,
     ResetFilter,
     CycleTagFilter,
@@ -67,6 +74,20 @@ pub enum UICommand {
     PageDownEntries,
     Undo,
     Redo,
+    IncreaseEntriesListSize,
+    DecreaseEntriesListSize,
+    TogglePanesOrientation,
+    CycleContentPaneMode,
+    ShowCalendar,
+    ShowStatistics,
+    ShowFilterPresets,
+    /// Applies the filter preset with the given number starting from one.
+    ApplyFilterPreset(u8),
+    SearchAllEntries,
+    ToggleMarkdownPreview,
+    NextSpellingError,
+    ShowSpellingSuggestions,
+    AddWordToDictionary,
 }
 
 #[derive(Debug, Clone)]
@@ -129,9 +150,10 @@ impl UICommand {
                 CommandInfo::new("Discard changes", "Discard changes on journal content")
             }
             UICommand::ReloadAll => CommandInfo::new("Reload all", "Reload all entries"),
-            UICommand::ExportEntryContent => {
-                CommandInfo::new("Export journal content", "Export current journal content")
-            }
+            UICommand::ExportEntryContent => CommandInfo::new(
+                "Export journal content",
+                "Export current journal content or to Markdown, HTML, text or JSON",
+            ),
             UICommand::EditInExternalEditor => CommandInfo::new(
                 "Edit in external editor",
                 "Edit current journal content in external editor (The editor can be set in configurations file or via the environment variables VISUAL, EDITOR)",
@@ -166,7 +188,27 @@ impl UICommand {
             ),
             UICommand::MulSelExportEntries => CommandInfo::new(
                 "Export selection",
-                "Export selected journals to a transfer JSON file, which can be imported to other back-end files",
+                "Export selected journals, or the filtered ones if none are selected, to a transfer JSON file, which can be imported to other back-end files, or to Markdown, HTML or text",
+            ),
+            UICommand::MulSelAddTags => CommandInfo::new(
+                "Add tags to selection",
+                "Add tags to the selected journals in multi selection mode",
+            ),
+            UICommand::MulSelRemoveTags => CommandInfo::new(
+                "Remove tags from selection",
+                "Remove tags from the selected journals in multi selection mode",
+            ),
+            UICommand::MulSelRenameTag => CommandInfo::new(
+                "Rename tag in selection",
+                "Rename a tag in the selected journals in multi selection mode",
+            ),
+            UICommand::MulSelSetPriority => CommandInfo::new(
+                "Set priority of selection",
+                "Set or clear the priority of the selected journals in multi selection mode",
+            ),
+            UICommand::MulSelShiftDates => CommandInfo::new(
+                "Shift dates of selection",
+                "Move the dates of the selected journals forward or backward by days in multi selection mode",
             ),
             UICommand::ShowFilter => {
                 CommandInfo::new("Open filter", "Open filter popup for journals")
@@ -221,6 +263,60 @@ impl UICommand {
             ),
             UICommand::Undo => CommandInfo::new("Undo", "Undo the latest change on journals"),
             UICommand::Redo => CommandInfo::new("Redo", "Redo the latest change on journals"),
+            UICommand::IncreaseEntriesListSize => CommandInfo::new(
+                "Increase journals list size",
+                "Increase the size of the journals' list pane",
+            ),
+            UICommand::DecreaseEntriesListSize => CommandInfo::new(
+                "Decrease journals list size",
+                "Decrease the size of the journals' list pane",
+            ),
+            UICommand::TogglePanesOrientation => CommandInfo::new(
+                "Toggle panes orientation",
+                "Switch between placing the journals' list and the editor side by side or stacked",
+            ),
+            UICommand::CycleContentPaneMode => CommandInfo::new(
+                "Cycle content pane mode",
+                "Cycle the pane next to the journals' list between editor, read-only preview and hidden",
+            ),
+            UICommand::ShowCalendar => CommandInfo::new(
+                "Open calendar",
+                "Open calendar popup to browse journals by date, filtering them by day or going to the closest journal",
+            ),
+            UICommand::ShowStatistics => CommandInfo::new(
+                "Open statistics",
+                "Open statistics popup with journals per week, words per day, writing streaks and tags and priorities distributions of the shown journals",
+            ),
+            UICommand::ShowFilterPresets => CommandInfo::new(
+                "Open filter presets",
+                "Open popup to apply, save, rename and delete the saved filter presets",
+            ),
+            UICommand::ApplyFilterPreset(number) => CommandInfo {
+                name: format!("Apply filter preset {number}"),
+                description: format!(
+                    "Apply the filter preset number {number} from the saved presets"
+                ),
+            },
+            UICommand::SearchAllEntries => CommandInfo::new(
+                "Search in journals",
+                "Search the content of the journals with a smart-case regex and jump to the selected match in the editor",
+            ),
+            UICommand::ToggleMarkdownPreview => CommandInfo::new(
+                "Toggle Markdown preview",
+                "Toggle rendering the Markdown of the journal formatted in the read-only preview pane",
+            ),
+            UICommand::NextSpellingError => CommandInfo::new(
+                "Next spelling error",
+                "Move the cursor to the next misspelled word in the editor",
+            ),
+            UICommand::ShowSpellingSuggestions => CommandInfo::new(
+                "Spelling suggestions",
+                "Show suggestions to replace the misspelled word under the cursor with",
+            ),
+            UICommand::AddWordToDictionary => CommandInfo::new(
+                "Add word to dictionary",
+                "Add the word under the cursor to the personal dictionary",
+            ),
         }
     }
 
@@ -232,14 +328,14 @@ impl UICommand {
         match self {
             UICommand::Quit => exec_quit(ui_components),
             UICommand::ShowHelp => exec_show_help(ui_components),
-            UICommand::CycleFocusedControlForward => exec_cycle_forward(ui_components),
-            UICommand::CycleFocusedControlBack => exec_cycle_backward(ui_components),
+            UICommand::CycleFocusedControlForward => exec_cycle_forward(ui_components, app),
+            UICommand::CycleFocusedControlBack => exec_cycle_backward(ui_components, app),
             UICommand::SelectedNextEntry => exec_select_next_entry(ui_components, app),
             UICommand::SelectedPrevEntry => exec_select_prev_entry(ui_components, app),
             UICommand::CreateEntry => exec_create_entry(ui_components, app),
             UICommand::EditCurrentEntry => exec_edit_current_entry(ui_components, app),
             UICommand::DeleteCurrentEntry => exec_delete_current_entry(ui_components, app),
-            UICommand::StartEditEntryContent => exec_start_edit_content(ui_components),
+            UICommand::StartEditEntryContent => exec_start_edit_content(ui_components, app),
             UICommand::BackEditorNormalMode => exec_back_editor_to_normal_mode(ui_components),
             UICommand::SaveEntryContent => exec_save_entry_content(ui_components, app).await,
             UICommand::DiscardChangesEntryContent => exec_discard_content(ui_components),
@@ -256,6 +352,21 @@ impl UICommand {
             UICommand::MulSelInverSelection => exec_invert_selection(app),
             UICommand::MulSelDeleteEntries => exec_delete_selected_entries(ui_components, app),
             UICommand::MulSelExportEntries => exec_export_selected_entries(ui_components, app),
+            UICommand::MulSelAddTags => {
+                exec_bulk_edit_selected_entries(BulkEditKind::AddTags, ui_components, app)
+            }
+            UICommand::MulSelRemoveTags => {
+                exec_bulk_edit_selected_entries(BulkEditKind::RemoveTags, ui_components, app)
+            }
+            UICommand::MulSelRenameTag => {
+                exec_bulk_edit_selected_entries(BulkEditKind::RenameTag, ui_components, app)
+            }
+            UICommand::MulSelSetPriority => {
+                exec_bulk_edit_selected_entries(BulkEditKind::SetPriority, ui_components, app)
+            }
+            UICommand::MulSelShiftDates => {
+                exec_bulk_edit_selected_entries(BulkEditKind::ShiftDates, ui_components, app)
+            }
             UICommand::ShowFilter => exec_show_filter(ui_components, app),
             UICommand::ResetFilter => exec_reset_filter(app),
             UICommand::CycleTagFilter => exec_cycle_tag_filter(ui_components, app),
@@ -280,6 +391,36 @@ impl UICommand {
             }
             UICommand::Undo => exec_undo(ui_components, app).await,
             UICommand::Redo => exec_redo(ui_components, app).await,
+            UICommand::IncreaseEntriesListSize => exec_resize_entries_list(app, RESIZE_STEP),
+            UICommand::DecreaseEntriesListSize => exec_resize_entries_list(app, -RESIZE_STEP),
+            UICommand::TogglePanesOrientation => exec_toggle_panes_orientation(app),
+            UICommand::CycleContentPaneMode => exec_cycle_content_pane_mode(ui_components, app),
+            UICommand::ShowCalendar => exec_show_calendar(ui_components, app),
+            UICommand::ShowStatistics => exec_show_statistics(ui_components, app),
+            UICommand::ShowFilterPresets => exec_show_filter_presets(ui_components, app),
+            cmd @ UICommand::ApplyFilterPreset(number) => check_unsaved_then_exec_cmd(
+                *cmd,
+                |ui, app| apply_filter_preset(*number, ui, app),
+                ui_components,
+                app,
+            ),
+            UICommand::SearchAllEntries => check_unsaved_then_exec_cmd(
+                UICommand::SearchAllEntries,
+                show_search_popup,
+                ui_components,
+                app,
+            ),
+            UICommand::ToggleMarkdownPreview => check_unsaved_then_exec_cmd(
+                UICommand::ToggleMarkdownPreview,
+                toggle_markdown_preview,
+                ui_components,
+                app,
+            ),
+            UICommand::NextSpellingError => exec_next_spelling_error(ui_components, app),
+            UICommand::ShowSpellingSuggestions => {
+                exec_show_spelling_suggestions(ui_components, app)
+            }
+            UICommand::AddWordToDictionary => exec_add_word_to_dictionary(ui_components, app),
         }
     }
 
@@ -335,6 +476,11 @@ impl UICommand {
                 continue_delete_selected_entries(app, msg_box_result).await
             }
             UICommand::MulSelExportEntries => not_implemented(),
+            UICommand::MulSelAddTags => not_implemented(),
+            UICommand::MulSelRemoveTags => not_implemented(),
+            UICommand::MulSelRenameTag => not_implemented(),
+            UICommand::MulSelSetPriority => not_implemented(),
+            UICommand::MulSelShiftDates => not_implemented(),
             UICommand::ShowFilter => continue_show_filter(ui_components, app, msg_box_result).await,
             UICommand::ResetFilter => not_implemented(),
             UICommand::CycleTagFilter => {
@@ -389,6 +535,49 @@ impl UICommand {
             }
             UICommand::Undo => continue_undo(ui_components, app, msg_box_result).await,
             UICommand::Redo => continue_redo(ui_components, app, msg_box_result).await,
+            UICommand::IncreaseEntriesListSize => not_implemented(),
+            UICommand::DecreaseEntriesListSize => not_implemented(),
+            UICommand::TogglePanesOrientation => not_implemented(),
+            UICommand::CycleContentPaneMode => {
+                continue_cycle_content_pane_mode(ui_components, app, msg_box_result).await
+            }
+            UICommand::ShowCalendar => {
+                continue_show_calendar(ui_components, app, msg_box_result).await
+            }
+            UICommand::ShowStatistics => not_implemented(),
+            UICommand::ShowFilterPresets => {
+                continue_show_filter_presets(ui_components, app, msg_box_result).await
+            }
+            UICommand::ApplyFilterPreset(number) => {
+                continue_cmd_after_check_unsaved(
+                    |ui, app| apply_filter_preset(*number, ui, app),
+                    ui_components,
+                    app,
+                    msg_box_result,
+                )
+                .await
+            }
+            UICommand::SearchAllEntries => {
+                continue_cmd_after_check_unsaved(
+                    show_search_popup,
+                    ui_components,
+                    app,
+                    msg_box_result,
+                )
+                .await
+            }
+            UICommand::ToggleMarkdownPreview => {
+                continue_cmd_after_check_unsaved(
+                    toggle_markdown_preview,
+                    ui_components,
+                    app,
+                    msg_box_result,
+                )
+                .await
+            }
+            UICommand::NextSpellingError => not_implemented(),
+            UICommand::ShowSpellingSuggestions => not_implemented(),
+            UICommand::AddWordToDictionary => not_implemented(),
         }
     }
 }
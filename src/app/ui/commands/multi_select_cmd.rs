@@ -4,6 +4,7 @@ use crate::app::{
     App, HandleInputReturnType, UIComponents,
     ui::{
         MsgBoxResult, Popup,
+        bulk_edit_popup::{BulkEditKind, BulkEditPopup},
         export_popup::ExportPopup,
         msg_box::{MsgBoxActions, MsgBoxType},
     },
@@ -157,7 +158,8 @@ pub fn exec_export_selected_entries<D: DataProvider>(
     debug_assert!(ui_components.entries_list.multi_select_mode);
     debug_assert!(!ui_components.has_unsaved());
 
-    if app.selected_entries.is_empty() {
+    // The filtered journals are exported if none are selected.
+    if app.selected_entries.is_empty() && app.get_active_entries().next().is_none() {
         let msg = MsgBoxType::Info("No items have been selected".into());
         let msg_action = MsgBoxActions::Ok;
         ui_components.show_msg_box(msg, msg_action, None);
@@ -176,3 +178,27 @@ pub fn exec_export_selected_entries<D: DataProvider>(
 
     Ok(HandleInputReturnType::Handled)
 }
+
+pub fn exec_bulk_edit_selected_entries<D: DataProvider>(
+    kind: BulkEditKind,
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    debug_assert!(ui_components.entries_list.multi_select_mode);
+    debug_assert!(!ui_components.has_unsaved());
+
+    if app.selected_entries.is_empty() {
+        let msg = MsgBoxType::Info("No items have been selected".into());
+        let msg_action = MsgBoxActions::Ok;
+        ui_components.show_msg_box(msg, msg_action, None);
+
+        return Ok(HandleInputReturnType::Handled);
+    }
+
+    let popup = BulkEditPopup::new(kind, app.selected_entries.len());
+    ui_components
+        .popup_stack
+        .push(Popup::BulkEdit(Box::new(popup)));
+
+    Ok(HandleInputReturnType::Handled)
+}
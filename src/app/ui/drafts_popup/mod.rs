@@ -0,0 +1,426 @@
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::{drafts::Draft, keymap::Input};
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect};
+
+type DraftsPopupReturn = PopupReturn<DraftsAction>;
+
+const FOOTER_TEXT: &str = "Enter or <r>: Recover draft | <x> or <Delete>: Discard draft | <j/k> or <Up/Down>: Select next/previous | Esc, <q> or <Ctrl-c>: Keep drafts and close";
+const FOOTER_MARGIN: usize = 8;
+const MAX_LIST_HEIGHT: u16 = 8;
+
+const DIFF_REMOVED_COLOR: Color = Color::LightRed;
+const DIFF_ADDED_COLOR: Color = Color::LightGreen;
+
+/// Limits the cells of the table matching the changed lines. Bigger changes are shown as removed
+/// and added lines without matching them to keep the memory bounded for long journals.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// Draft left from a previous session with the info of its journal.
+pub struct RecoverableDraft {
+    pub draft: Draft,
+    pub title: String,
+    /// Content of the journal saved in the back-end to compare the draft with.
+    pub saved_content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DraftsAction {
+    Recover(Draft),
+    Discard(u32),
+}
+
+/// Offers to recover or discard the drafts left from previous sessions, showing their
+/// differences to the saved journals.
+pub struct DraftsPopup {
+    drafts: Vec<RecoverableDraft>,
+    list_state: ListState,
+    /// Diff lines of the selected draft with its journal ID, computed once per selection.
+    diff_cache: Option<(u32, Vec<Line<'static>>)>,
+}
+
+impl DraftsPopup {
+    pub fn new(drafts: Vec<RecoverableDraft>) -> Self {
+        let mut list_state = ListState::default();
+        if !drafts.is_empty() {
+            list_state.select(Some(0));
+        }
+
+        Self {
+            drafts,
+            list_state,
+            diff_cache: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.drafts.is_empty()
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(70, 80, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Unsaved Drafts Found");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let list_height = (self.drafts.len() as u16 + 2).min(MAX_LIST_HEIGHT);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(4)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Length(list_height),
+                    Constraint::Min(3),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        self.render_list(frame, chunks[0], styles);
+        self.render_diff(frame, chunks[1]);
+        self.render_footer(frame, chunks[2]);
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let items: Vec<ListItem> = self
+            .drafts
+            .iter()
+            .map(|item| {
+                let saved_at = item.draft.saved_at.with_timezone(&Local);
+                ListItem::new(format!(
+                    "{} (saved at {})",
+                    item.title,
+                    saved_at.format("%Y-%m-%d %H:%M")
+                ))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(styles.general.list_highlight_active)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_diff(&mut self, frame: &mut Frame, area: Rect) {
+        let selected = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.drafts.get(idx));
+
+        let lines = match selected {
+            Some(item) => {
+                let entry_id = item.draft.entry_id;
+                if self
+                    .diff_cache
+                    .as_ref()
+                    .is_none_or(|(cached_id, _)| *cached_id != entry_id)
+                {
+                    self.diff_cache = Some((entry_id, styled_diff(item)));
+                }
+
+                self.diff_cache
+                    .as_ref()
+                    .map(|(_, lines)| lines.clone())
+                    .unwrap_or_default()
+            }
+            None => Vec::new(),
+        };
+
+        let diff = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Changes to saved journal"),
+        );
+
+        frame.render_widget(diff, area);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> DraftsPopupReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc | KeyCode::Char('q') => PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => PopupReturn::Cancel,
+            KeyCode::Enter | KeyCode::Char('r') => self.recover_selected(),
+            KeyCode::Char('m') if has_control => self.recover_selected(),
+            KeyCode::Char('x') | KeyCode::Delete => self.discard_selected(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.cycle_next();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.cycle_prev();
+                PopupReturn::KeepPopup
+            }
+            _ => PopupReturn::KeepPopup,
+        }
+    }
+
+    fn recover_selected(&mut self) -> DraftsPopupReturn {
+        match self.take_selected() {
+            Some(item) => PopupReturn::Apply(DraftsAction::Recover(item.draft)),
+            None => PopupReturn::KeepPopup,
+        }
+    }
+
+    fn discard_selected(&mut self) -> DraftsPopupReturn {
+        match self.take_selected() {
+            Some(item) => PopupReturn::Apply(DraftsAction::Discard(item.draft.entry_id)),
+            None => PopupReturn::KeepPopup,
+        }
+    }
+
+    /// Removes the selected draft from the list, keeping the selection in the list bounds.
+    fn take_selected(&mut self) -> Option<RecoverableDraft> {
+        let idx = self.list_state.selected()?;
+        if idx >= self.drafts.len() {
+            return None;
+        }
+
+        let item = self.drafts.remove(idx);
+        let new_selection = match self.drafts.len() {
+            0 => None,
+            len => Some(idx.min(len - 1)),
+        };
+        self.list_state.select(new_selection);
+
+        Some(item)
+    }
+
+    fn cycle_next(&mut self) {
+        if self.drafts.is_empty() {
+            return;
+        }
+
+        let last_index = self.drafts.len() - 1;
+        let new_index = self
+            .list_state
+            .selected()
+            .map(|idx| if idx >= last_index { 0 } else { idx + 1 })
+            .unwrap_or(0);
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn cycle_prev(&mut self) {
+        if self.drafts.is_empty() {
+            return;
+        }
+
+        let last_index = self.drafts.len() - 1;
+        let new_index = self
+            .list_state
+            .selected()
+            .map(|idx| idx.checked_sub(1).unwrap_or(last_index))
+            .unwrap_or(last_index);
+
+        self.list_state.select(Some(new_index));
+    }
+}
+
+fn styled_diff(item: &RecoverableDraft) -> Vec<Line<'static>> {
+    diff_lines(&item.saved_content, &item.draft.content)
+        .into_iter()
+        .map(|diff| match diff {
+            DiffLine::Unchanged(line) => Line::from(format!("  {line}")),
+            DiffLine::Removed(line) => {
+                Line::styled(format!("- {line}"), Style::default().fg(DIFF_REMOVED_COLOR))
+            }
+            DiffLine::Added(line) => {
+                Line::styled(format!("+ {line}"), Style::default().fg(DIFF_ADDED_COLOR))
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffLine<'a> {
+    Unchanged(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Compares the lines of the texts using their longest common subsequence after skipping the
+/// common lines at their start and end.
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix_len = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let suffix_len = old[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new[prefix_len..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+
+    let old_changed = &old[prefix_len..old.len() - suffix_len];
+    let new_changed = &new[prefix_len..new.len() - suffix_len];
+
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    diff.extend(
+        old[..prefix_len]
+            .iter()
+            .map(|line| DiffLine::Unchanged(line)),
+    );
+
+    if (old_changed.len() + 1).saturating_mul(new_changed.len() + 1) > MAX_DIFF_CELLS {
+        diff.extend(old_changed.iter().map(|line| DiffLine::Removed(line)));
+        diff.extend(new_changed.iter().map(|line| DiffLine::Added(line)));
+    } else {
+        diff_changed_lines(old_changed, new_changed, &mut diff);
+    }
+
+    diff.extend(
+        old[old.len() - suffix_len..]
+            .iter()
+            .map(|line| DiffLine::Unchanged(line)),
+    );
+
+    diff
+}
+
+fn diff_changed_lines<'a>(old: &[&'a str], new: &[&'a str], diff: &mut Vec<DiffLine<'a>>) {
+    // Lengths of the longest common subsequences of the lines suffixes.
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for (i, old_line) in old.iter().enumerate().rev() {
+        for (j, new_line) in new.iter().enumerate().rev() {
+            lcs[i][j] = if old_line == new_line {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Unchanged(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    diff.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn recoverable(entry_id: u32) -> RecoverableDraft {
+        RecoverableDraft {
+            draft: Draft {
+                entry_id,
+                content: format!("draft {entry_id}"),
+                saved_at: Utc::now(),
+            },
+            title: format!("Title {entry_id}"),
+            saved_content: format!("saved {entry_id}"),
+        }
+    }
+
+    #[test]
+    fn diff_changed_lines() {
+        let diff = diff_lines("first\nsecond\nthird", "first\nchanged\nthird\nfourth");
+
+        assert_eq!(
+            diff,
+            [
+                DiffLine::Unchanged("first"),
+                DiffLine::Removed("second"),
+                DiffLine::Added("changed"),
+                DiffLine::Unchanged("third"),
+                DiffLine::Added("fourth"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_large_changes_without_matching() {
+        let old: String = (0..1500).map(|idx| format!("old {idx}\n")).collect();
+        let new: String = (0..1500).map(|idx| format!("new {idx}\n")).collect();
+        let old = format!("start\n{old}end");
+        let new = format!("start\n{new}end");
+
+        let diff = diff_lines(&old, &new);
+
+        assert_eq!(diff.len(), 3002);
+        assert_eq!(diff[0], DiffLine::Unchanged("start"));
+        assert_eq!(diff[1], DiffLine::Removed("old 0"));
+        assert_eq!(diff[1501], DiffLine::Added("new 0"));
+        assert_eq!(diff[3001], DiffLine::Unchanged("end"));
+    }
+
+    #[test]
+    fn recover_and_discard_drafts() {
+        let mut popup = DraftsPopup::new(vec![recoverable(1), recoverable(2), recoverable(3)]);
+
+        popup.handle_input(&Input::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        let result = popup.handle_input(&Input::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert!(matches!(
+            result,
+            PopupReturn::Apply(DraftsAction::Discard(2))
+        ));
+
+        let result = popup.handle_input(&Input::new(KeyCode::Enter, KeyModifiers::NONE));
+        let PopupReturn::Apply(DraftsAction::Recover(draft)) = result else {
+            panic!("Draft should be recovered");
+        };
+        assert_eq!(draft.entry_id, 3);
+        assert_eq!(draft.content, "draft 3");
+
+        popup.handle_input(&Input::new(KeyCode::Delete, KeyModifiers::NONE));
+        assert!(popup.is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+//! Syntax highlighting for fenced code blocks using the syntaxes and themes bundled in syntect.
+
+use std::sync::LazyLock;
+
+use ratatui::style::{Color, Modifier, Style};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{FontStyle, Theme, ThemeSet},
+    parsing::SyntaxSet,
+};
+
+use super::markdown::Highlight;
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// Highlights the lines of a code block in the given language, returning the styled byte
+/// ranges of each line.
+/// It returns `None` if the language or the color scheme is unknown.
+pub fn highlight_code_block<S: AsRef<str>>(
+    language: &str,
+    lines: &[S],
+    theme_name: &str,
+) -> Option<Vec<Vec<Highlight>>> {
+    let theme: &Theme = THEME_SET.themes.get(theme_name)?;
+    let syntax = SYNTAX_SET.find_syntax_by_token(language)?;
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let highlights = lines
+        .iter()
+        .map(|line| {
+            let line = line.as_ref();
+            // The syntaxes are loaded for lines with their line endings.
+            let line_with_ending = format!("{line}\n");
+            let Ok(tokens) = highlighter.highlight_line(&line_with_ending, &SYNTAX_SET) else {
+                return Vec::new();
+            };
+
+            let mut offset = 0;
+            tokens
+                .into_iter()
+                .filter_map(|(style, text)| {
+                    let start = offset;
+                    offset += text.len();
+                    let end = offset.min(line.len());
+                    (start < end).then(|| Highlight {
+                        range: start..end,
+                        style: convert_style(style),
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    Some(highlights)
+}
+
+/// Converts the foreground and the font style only to keep the background of the terminal.
+fn convert_style(style: syntect::highlighting::Style) -> Style {
+    let fg = style.foreground;
+    let mut modifiers = Modifier::empty();
+    if style.font_style.contains(FontStyle::BOLD) {
+        modifiers |= Modifier::BOLD;
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        modifiers |= Modifier::ITALIC;
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        modifiers |= Modifier::UNDERLINED;
+    }
+
+    Style::default()
+        .fg(Color::Rgb(fg.r, fg.g, fg.b))
+        .add_modifier(modifiers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THEME: &str = "base16-ocean.dark";
+
+    #[test]
+    fn highlight_known_language() {
+        let lines = ["fn main() {", "    let x = 1;", "}"];
+        let highlights = highlight_code_block("rs", &lines, THEME).unwrap();
+
+        assert_eq!(highlights.len(), lines.len());
+        for (line, line_highlights) in lines.iter().zip(highlights.iter()) {
+            assert!(line_highlights.iter().all(|h| h.range.end <= line.len()));
+        }
+
+        // Keyword and function name have different colors
+        let fn_style = highlights[0][0].style;
+        let name_style = highlights[0]
+            .iter()
+            .find(|h| &lines[0][h.range.clone()] == "main")
+            .unwrap()
+            .style;
+        assert_ne!(fn_style.fg, name_style.fg);
+    }
+
+    #[test]
+    fn unknown_language_or_theme() {
+        assert!(highlight_code_block("not-a-language", &["text"], THEME).is_none());
+        assert!(highlight_code_block("rust", &["fn main() {}"], "not-a-theme").is_none());
+    }
+}
@@ -0,0 +1,678 @@
+//! Line based Markdown parsing to highlight the content in the editor and to render it
+//! formatted in the read-only preview.
+
+use std::{collections::HashMap, ops::Range, sync::LazyLock};
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use regex::Regex;
+use unicode_width::UnicodeWidthChar;
+
+use crate::app::ui::themes::{self, EditorStyles};
+
+use super::code_highlight::highlight_code_block;
+
+static HEADING_RGX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}(#{1,6})(?:\s+|$)").unwrap());
+static LIST_ITEM_RGX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*([-*+]|\d{1,9}[.)])(?:\s+|$)(\[[ xX]\](?:\s+|$))?").unwrap());
+static CODE_RGX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`]+)`").unwrap());
+static LINK_RGX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").unwrap());
+static STRONG_RGX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__").unwrap());
+static EMPHASIS_RGX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*([^*\s](?:[^*]*[^*\s])?)\*|\b_([^_\s](?:[^_]*[^_\s])?)_\b").unwrap()
+});
+
+/// Block kind of a Markdown line. Ranges are in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineKind {
+    Heading {
+        /// Text after the heading markers.
+        content_start: usize,
+    },
+    /// Opening or closing line of a fenced code block, with the language of the block for
+    /// opening lines if it's defined.
+    Fence {
+        language: Option<String>,
+    },
+    Code,
+    ListItem {
+        marker: Range<usize>,
+        task: Option<Task>,
+        content_start: usize,
+    },
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    pub range: Range<usize>,
+    pub checked: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InlineKind {
+    Code,
+    Link,
+    Strong,
+    Emphasis,
+}
+
+/// Inline element with its whole range including the markers, the range of its content and
+/// the range of the url for links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InlineToken {
+    kind: InlineKind,
+    range: Range<usize>,
+    content: Range<usize>,
+    url: Option<Range<usize>>,
+}
+
+/// Style applied on a byte range of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    pub range: Range<usize>,
+    pub style: Style,
+}
+
+/// Converts theme styles to be combined with other styles without removing their modifiers.
+pub fn additive(style: themes::Style) -> Style {
+    Style {
+        sub_modifier: Modifier::empty(),
+        ..style.into()
+    }
+}
+
+/// Classifies the lines keeping track of the fenced code blocks between them.
+pub fn classify_lines<S: AsRef<str>>(lines: &[S]) -> Vec<LineKind> {
+    let mut open_fence: Option<&str> = None;
+
+    lines
+        .iter()
+        .map(|line| {
+            let line = line.as_ref();
+            let trimmed = line.trim_start();
+            let fence = ["```", "~~~"]
+                .into_iter()
+                .find(|fence| trimmed.starts_with(fence));
+
+            match (open_fence, fence) {
+                (Some(open), Some(fence)) if open == fence => {
+                    open_fence = None;
+                    return LineKind::Fence { language: None };
+                }
+                (Some(_), _) => return LineKind::Code,
+                (None, Some(fence)) => {
+                    open_fence = Some(fence);
+                    let language = trimmed[fence.len()..]
+                        .split_whitespace()
+                        .next()
+                        .map(String::from);
+                    return LineKind::Fence { language };
+                }
+                (None, None) => {}
+            }
+
+            if let Some(caps) = HEADING_RGX.captures(line) {
+                return LineKind::Heading {
+                    content_start: caps.get(0).unwrap().end(),
+                };
+            }
+
+            if let Some(caps) = LIST_ITEM_RGX.captures(line) {
+                let task = caps.get(2).map(|task| Task {
+                    range: task.start()..task.start() + 3,
+                    checked: !task.as_str().starts_with("[ "),
+                });
+                return LineKind::ListItem {
+                    marker: caps.get(1).unwrap().range(),
+                    task,
+                    content_start: caps.get(0).unwrap().end(),
+                };
+            }
+
+            LineKind::Text
+        })
+        .collect()
+}
+
+/// Finds the inline elements in the text sorted by their position. Elements can't be nested
+/// and code spans have the precedence over the others.
+fn inline_tokens(text: &str) -> Vec<InlineToken> {
+    let mut tokens: Vec<InlineToken> = Vec::new();
+
+    let patterns = [
+        (InlineKind::Code, &CODE_RGX),
+        (InlineKind::Link, &LINK_RGX),
+        (InlineKind::Strong, &STRONG_RGX),
+        (InlineKind::Emphasis, &EMPHASIS_RGX),
+    ];
+
+    for (kind, regex) in patterns {
+        for caps in regex.captures_iter(text) {
+            let range = caps.get(0).unwrap().range();
+            if tokens
+                .iter()
+                .any(|token| token.range.start < range.end && range.start < token.range.end)
+            {
+                continue;
+            }
+
+            // Alternations have the content in the first matching group
+            let content = caps
+                .iter()
+                .skip(1)
+                .flatten()
+                .next()
+                .map(|group| group.range())
+                .unwrap_or(range.clone());
+            let url = match kind {
+                InlineKind::Link => caps.get(2).map(|url| url.range()),
+                _ => None,
+            };
+
+            tokens.push(InlineToken {
+                kind,
+                range,
+                content,
+                url,
+            });
+        }
+    }
+
+    tokens.sort_by_key(|token| token.range.start);
+    tokens
+}
+
+fn inline_style(kind: InlineKind, styles: &EditorStyles) -> Style {
+    match kind {
+        InlineKind::Code => additive(styles.markdown_code),
+        InlineKind::Link => additive(styles.markdown_link),
+        InlineKind::Strong => additive(styles.markdown_strong),
+        InlineKind::Emphasis => additive(styles.markdown_emphasis),
+    }
+}
+
+/// Highlights the Markdown syntax of the lines in the given rows keeping all the characters in
+/// place. All the lines are needed to know which ones are in code blocks.
+/// Highlights of a line are ordered from the block to the inline ones to be applied in order.
+pub fn highlight_lines<S: AsRef<str>>(
+    lines: &[S],
+    rows: Range<usize>,
+    styles: &EditorStyles,
+) -> Vec<Vec<Highlight>> {
+    let kinds = classify_lines(lines);
+    let mut code_highlights = code_blocks_highlights(lines, &kinds, rows.clone(), styles);
+
+    lines[rows.clone()]
+        .iter()
+        .zip(kinds.into_iter().skip(rows.start))
+        .zip(rows)
+        .map(|((line, kind), row)| {
+            let line = line.as_ref();
+            let mut highlights = Vec::new();
+            let inline_start = match kind {
+                LineKind::Fence { .. } | LineKind::Code => {
+                    highlights.push(Highlight {
+                        range: 0..line.len(),
+                        style: additive(styles.markdown_code),
+                    });
+                    highlights.extend(code_highlights.remove(&row).unwrap_or_default());
+                    return highlights;
+                }
+                LineKind::Heading { .. } => {
+                    highlights.push(Highlight {
+                        range: 0..line.len(),
+                        style: additive(styles.markdown_heading),
+                    });
+                    0
+                }
+                LineKind::ListItem {
+                    marker,
+                    task,
+                    content_start,
+                } => {
+                    highlights.push(Highlight {
+                        range: marker,
+                        style: additive(styles.markdown_list_marker),
+                    });
+                    if let Some(task) = task {
+                        highlights.push(Highlight {
+                            range: task.range,
+                            style: additive(styles.markdown_task),
+                        });
+                    }
+                    content_start
+                }
+                LineKind::Text => 0,
+            };
+
+            let text = &line[inline_start..];
+            highlights.extend(inline_tokens(text).into_iter().map(|token| Highlight {
+                range: token.range.start + inline_start..token.range.end + inline_start,
+                style: inline_style(token.kind, styles),
+            }));
+
+            highlights
+        })
+        .collect()
+}
+
+/// Returns the ranges of the lines in the given rows that aren't prose and shouldn't be spell
+/// checked, which are the code blocks, the inline code and the URLs of the links.
+pub fn unchecked_ranges<S: AsRef<str>>(lines: &[S], rows: Range<usize>) -> Vec<Vec<Range<usize>>> {
+    let kinds = classify_lines(lines);
+
+    lines[rows.clone()]
+        .iter()
+        .zip(kinds.into_iter().skip(rows.start))
+        .map(|(line, kind)| {
+            let line = line.as_ref();
+            match kind {
+                LineKind::Fence { .. } | LineKind::Code => std::iter::once(0..line.len()).collect(),
+                _ => inline_tokens(line)
+                    .into_iter()
+                    .filter_map(|token| match token.kind {
+                        InlineKind::Code => Some(token.range),
+                        InlineKind::Link => token.url,
+                        InlineKind::Strong | InlineKind::Emphasis => None,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Highlights the syntax of the code lines in the given rows, for the code blocks with a known
+/// language, mapped by their rows.
+fn code_blocks_highlights<S: AsRef<str>>(
+    lines: &[S],
+    kinds: &[LineKind],
+    rows: Range<usize>,
+    styles: &EditorStyles,
+) -> HashMap<usize, Vec<Highlight>> {
+    let mut highlights = HashMap::new();
+
+    for (fence_row, kind) in kinds.iter().enumerate().take(rows.end) {
+        let LineKind::Fence {
+            language: Some(language),
+        } = kind
+        else {
+            continue;
+        };
+
+        let start = fence_row + 1;
+        let end = kinds[start..]
+            .iter()
+            .position(|kind| *kind != LineKind::Code)
+            .map_or(kinds.len(), |pos| start + pos);
+
+        // Code lines must be highlighted from the start of their block to get the state of the
+        // syntax parser right.
+        if end <= rows.start {
+            continue;
+        }
+        let end = end.min(rows.end);
+
+        if let Some(block) = highlight_code_block(language, &lines[start..end], &styles.code_theme)
+        {
+            highlights.extend((start..end).zip(block));
+        }
+    }
+
+    highlights
+}
+
+/// Splits the line into spans with the highlights combined on their overlapping parts.
+pub fn highlighted_spans<'a>(line: &'a str, highlights: &[Highlight]) -> Vec<Span<'a>> {
+    let mut bounds: Vec<usize> = highlights
+        .iter()
+        .flat_map(|highlight| [highlight.range.start, highlight.range.end])
+        .chain([0, line.len()])
+        .filter(|bound| *bound <= line.len())
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    bounds
+        .windows(2)
+        .map(|bound| {
+            let (start, end) = (bound[0], bound[1]);
+            let style = highlights
+                .iter()
+                .filter(|highlight| highlight.range.start <= start && end <= highlight.range.end)
+                .fold(Style::default(), |style, highlight| {
+                    style.patch(highlight.style)
+                });
+            Span::styled(&line[start..end], style)
+        })
+        .collect()
+}
+
+/// Renders the lines formatted for display, removing the Markdown markers and wrapping the
+/// text to the given width.
+pub fn render_lines<S: AsRef<str>>(
+    lines: &[S],
+    width: usize,
+    styles: &EditorStyles,
+) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let kinds = classify_lines(lines);
+    let mut code_highlights = code_blocks_highlights(lines, &kinds, 0..lines.len(), styles);
+    let mut rendered = Vec::new();
+
+    for (row, (line, kind)) in lines.iter().zip(kinds).enumerate() {
+        let line = line.as_ref();
+        match kind {
+            LineKind::Fence { .. } => {}
+            LineKind::Code => {
+                let mut highlights = vec![Highlight {
+                    range: 0..line.len(),
+                    style: additive(styles.markdown_code),
+                }];
+                highlights.extend(code_highlights.remove(&row).unwrap_or_default());
+                rendered.extend(wrap_code(highlighted_spans(line, &highlights), width));
+            }
+            LineKind::Heading { content_start } => {
+                let heading_style = additive(styles.markdown_heading);
+                let segments = inline_segments(&line[content_start..], styles)
+                    .into_iter()
+                    .map(|(text, style)| (text, heading_style.patch(style)))
+                    .collect();
+                rendered.extend(wrap_segments(segments, Vec::new(), width));
+            }
+            LineKind::ListItem {
+                marker,
+                task,
+                content_start,
+            } => {
+                let mut prefix = vec![Span::raw(line[..marker.start].to_owned())];
+                let marker_text = &line[marker.clone()];
+                let marker_text = if marker_text.len() == 1 {
+                    "•"
+                } else {
+                    marker_text
+                };
+                prefix.push(Span::styled(
+                    format!("{marker_text} "),
+                    additive(styles.markdown_list_marker),
+                ));
+                if let Some(task) = task {
+                    let check_box = if task.checked { "☑ " } else { "☐ " };
+                    prefix.push(Span::styled(check_box, additive(styles.markdown_task)));
+                }
+
+                let segments = inline_segments(&line[content_start..], styles);
+                rendered.extend(wrap_segments(segments, prefix, width));
+            }
+            LineKind::Text => {
+                let segments = inline_segments(line, styles);
+                rendered.extend(wrap_segments(segments, Vec::new(), width));
+            }
+        }
+    }
+
+    rendered
+}
+
+/// Wraps the code line on the width breaking the words since whitespaces are significant.
+fn wrap_code(spans: Vec<Span>, width: usize) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::default()];
+    let mut line_width = 0;
+
+    for span in spans {
+        for ch in span.content.chars() {
+            let ch_width = ch.width().unwrap_or_default();
+            if line_width > 0 && line_width + ch_width > width {
+                lines.push(Line::default());
+                line_width = 0;
+            }
+            let line = lines.last_mut().expect("Lines can't be empty");
+            match line.spans.last_mut() {
+                Some(last) if last.style == span.style => last.content.to_mut().push(ch),
+                _ => line.spans.push(Span::styled(ch.to_string(), span.style)),
+            }
+            line_width += ch_width;
+        }
+    }
+
+    lines
+}
+
+/// Splits the text into styled segments without the markers of the inline elements.
+fn inline_segments(text: &str, styles: &EditorStyles) -> Vec<(String, Style)> {
+    let mut segments = Vec::new();
+    let mut last_end = 0;
+
+    for token in inline_tokens(text) {
+        if token.range.start > last_end {
+            segments.push((
+                text[last_end..token.range.start].to_owned(),
+                Style::default(),
+            ));
+        }
+
+        let style = inline_style(token.kind, styles);
+        let content = &text[token.content];
+        match token.url {
+            Some(url) if &text[url.clone()] != content => {
+                segments.push((format!("{content} ({})", &text[url]), style));
+            }
+            _ => segments.push((content.to_owned(), style)),
+        }
+
+        last_end = token.range.end;
+    }
+
+    if last_end < text.len() {
+        segments.push((text[last_end..].to_owned(), Style::default()));
+    }
+
+    segments
+}
+
+/// Wraps the styled segments to the width, indenting the wrapped lines to the width of the
+/// prefix of the first line.
+fn wrap_segments(
+    segments: Vec<(String, Style)>,
+    prefix: Vec<Span<'static>>,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let indent: usize = prefix.iter().map(|span| span.width()).sum();
+    let text: String = segments.iter().map(|(text, _)| text.as_str()).collect();
+
+    if text.is_empty() {
+        return vec![Line::from(prefix)];
+    }
+
+    // Byte ranges of the segments in the whole text
+    let mut segment_ranges = Vec::with_capacity(segments.len());
+    let mut offset = 0;
+    for (segment_text, style) in segments.iter() {
+        segment_ranges.push((offset..offset + segment_text.len(), *style));
+        offset += segment_text.len();
+    }
+
+    let wrap_width = width.saturating_sub(indent).max(1);
+    let mut search_start = 0;
+    let mut prefix = Some(prefix);
+
+    textwrap::wrap(&text, wrap_width)
+        .iter()
+        .map(|wrapped| {
+            // Wrapped lines are sub-slices of the text in order with the whitespaces at the
+            // breaks removed.
+            let start = text[search_start..]
+                .find(wrapped.as_ref())
+                .map_or(search_start, |pos| search_start + pos);
+            let end = start + wrapped.len();
+            search_start = end;
+
+            let mut spans = prefix
+                .take()
+                .unwrap_or_else(|| vec![Span::raw(" ".repeat(indent))]);
+            spans.extend(segment_ranges.iter().filter_map(|(range, style)| {
+                let span_start = range.start.max(start);
+                let span_end = range.end.min(end);
+                (span_start < span_end)
+                    .then(|| Span::styled(text[span_start..span_end].to_owned(), *style))
+            }));
+
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn classify_blocks() {
+        let lines = [
+            "# Title",
+            "- [x] done",
+            "12. item",
+            "```rust",
+            "# not a heading",
+            "```",
+            "#hashtag",
+        ];
+
+        let kinds = classify_lines(&lines);
+
+        assert_eq!(kinds[0], LineKind::Heading { content_start: 2 });
+        assert_eq!(
+            kinds[1],
+            LineKind::ListItem {
+                marker: 0..1,
+                task: Some(Task {
+                    range: 2..5,
+                    checked: true
+                }),
+                content_start: 6,
+            }
+        );
+        assert_eq!(
+            kinds[2],
+            LineKind::ListItem {
+                marker: 0..3,
+                task: None,
+                content_start: 4,
+            }
+        );
+        assert_eq!(
+            kinds[3],
+            LineKind::Fence {
+                language: Some(String::from("rust"))
+            }
+        );
+        assert_eq!(kinds[4], LineKind::Code);
+        assert_eq!(kinds[5], LineKind::Fence { language: None });
+        assert_eq!(kinds[6], LineKind::Text);
+    }
+
+    #[test]
+    fn inline_elements() {
+        let text = "Some **bold**, *italic* and `code **not bold**` with [link](https://a.b)";
+        let kinds: Vec<_> = inline_tokens(text)
+            .into_iter()
+            .map(|token| (token.kind, text[token.content].to_owned()))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (InlineKind::Strong, String::from("bold")),
+                (InlineKind::Emphasis, String::from("italic")),
+                (InlineKind::Code, String::from("code **not bold**")),
+                (InlineKind::Link, String::from("link")),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_combines_styles() {
+        let styles = EditorStyles::default();
+        let highlights = highlight_lines(&["# Title with `code`"], 0..1, &styles);
+        let spans = highlighted_spans("# Title with `code`", &highlights[0]);
+
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].content, "# Title with ");
+        assert_eq!(spans[1].content, "`code`");
+        assert_eq!(
+            spans[1].style,
+            additive(styles.markdown_heading).patch(additive(styles.markdown_code))
+        );
+    }
+
+    #[test]
+    fn render_removes_markers_and_wraps() {
+        let styles = EditorStyles::default();
+        let lines = [
+            "## Heading",
+            "- [ ] buy **milk** and bread",
+            "```",
+            "let x = 1;",
+            "```",
+        ];
+
+        let rendered: Vec<_> = render_lines(&lines, 16, &styles)
+            .iter()
+            .map(line_text)
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec!["Heading", "• ☐ buy milk and", "    bread", "let x = 1;"]
+        );
+    }
+
+    #[test]
+    fn wrap_wide_code_chars() {
+        let styles = EditorStyles::default();
+        let lines = ["```", "let 名前 = 1;", "```"];
+
+        let rendered: Vec<_> = render_lines(&lines, 7, &styles)
+            .iter()
+            .map(line_text)
+            .collect();
+
+        assert_eq!(rendered, vec!["let 名", "前 = 1;"]);
+    }
+
+    #[test]
+    fn highlight_code_blocks_by_language() {
+        let styles = EditorStyles::default();
+        let lines = [
+            "```rust",
+            "let x = 1;",
+            "```",
+            "```unknown",
+            "let x = 1;",
+            "```",
+        ];
+
+        let highlights = highlight_lines(&lines, 0..lines.len(), &styles);
+
+        // Code style and syntax highlights
+        assert!(highlights[1].len() > 2);
+        // Code style only
+        assert_eq!(highlights[4].len(), 1);
+
+        // Highlighting part of the block gives the same result
+        let partial = highlight_lines(&lines, 1..2, &styles);
+        assert_eq!(partial[0], highlights[1]);
+    }
+}
@@ -7,22 +7,76 @@ use ratatui::{
     prelude::Margin,
     style::{Color, Style},
     symbols,
-    widgets::{Block, Borders, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
 };
 
-use crate::app::{App, keymap::Input, runner::HandleInputReturnType};
+use crate::{
+    app::{
+        App,
+        filter::pattern::format_regex_error,
+        keymap::Input,
+        runner::HandleInputReturnType,
+        spell_check::{self, SpellChecker},
+    },
+    settings::EditorProfile,
+};
 
 use backend::DataProvider;
+use std::ops::Range;
 use tui_textarea::{CursorMove, Scrolling, TextArea};
 
-use super::Styles;
 use super::commands::ClipboardOperation;
+use super::{Styles, themes::EditorStyles};
+
+use search::{Substitute, smart_case_pattern};
+use unicode_width::UnicodeWidthChar;
+use vim::{Awaiting, Motion, ObjectScope, Operator, PendingKeys, TextRange, VimState, text_object};
+
+pub use search::build_search_regex;
+
+mod code_highlight;
+mod markdown;
+mod search;
+mod vim;
+
+/// Average reading speed used to estimate the reading time of the content.
+const READING_WORDS_PER_MINUTE: usize = 200;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EditorMode {
     Normal,
     Insert,
     Visual,
+    /// Typing a search pattern or an ex command in the command line at the bottom of the editor.
+    Command,
+    /// Waiting for the motion or the text object to apply the operator on, like after `d`.
+    OperatorPending(Operator),
+    /// The only mode of the standard profile, editing the text with conventional keybindings.
+    Standard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandKind {
+    SearchForward,
+    SearchBackward,
+    Ex,
+}
+
+impl CommandKind {
+    fn prompt(self) -> char {
+        match self {
+            CommandKind::SearchForward => '/',
+            CommandKind::SearchBackward => '?',
+            CommandKind::Ex => ':',
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CommandLine {
+    kind: CommandKind,
+    text: String,
 }
 
 pub struct Editor<'a> {
@@ -31,6 +85,14 @@ pub struct Editor<'a> {
     is_active: bool,
     is_dirty: bool,
     has_unsaved: bool,
+    command_line: Option<CommandLine>,
+    /// Last search text, kept to repeat the search with `n` and `N` even after switching entries.
+    last_search: Option<String>,
+    search_backward: bool,
+    /// Message shown at the bottom of the editor until the next input, like search errors.
+    status_msg: Option<String>,
+    profile: EditorProfile,
+    vim: VimState,
 }
 
 impl From<&Input> for KeyEvent {
@@ -54,9 +116,27 @@ impl<'a> Editor<'a> {
             is_active: false,
             is_dirty: false,
             has_unsaved: false,
+            command_line: None,
+            last_search: None,
+            search_backward: false,
+            status_msg: None,
+            profile: EditorProfile::default(),
+            vim: VimState::default(),
         }
     }
 
+    pub fn set_profile(&mut self, profile: EditorProfile) {
+        self.profile = profile;
+        self.mode = match profile {
+            EditorProfile::Vim => EditorMode::Normal,
+            EditorProfile::Standard => EditorMode::Standard,
+        };
+    }
+
+    pub fn get_profile(&self) -> EditorProfile {
+        self.profile
+    }
+
     #[inline]
     pub fn is_insert_mode(&self) -> bool {
         self.mode == EditorMode::Insert
@@ -67,9 +147,49 @@ impl<'a> Editor<'a> {
         self.mode == EditorMode::Visual
     }
 
+    #[inline]
+    pub fn is_command_mode(&self) -> bool {
+        self.mode == EditorMode::Command
+    }
+
+    #[inline]
+    pub fn is_standard_mode(&self) -> bool {
+        self.mode == EditorMode::Standard
+    }
+
+    /// Checks if the editor handles the input before the keymaps. This applies only while the
+    /// editor is focused since the standard profile keeps its mode for the whole session.
     #[inline]
     pub fn is_prioritized(&self) -> bool {
-        matches!(self.mode, EditorMode::Insert | EditorMode::Visual)
+        self.is_active
+            && matches!(
+                self.mode,
+                EditorMode::Insert
+                    | EditorMode::Visual
+                    | EditorMode::Command
+                    | EditorMode::Standard
+            )
+    }
+
+    /// Checks if the input must be handled by the editor before the global keymaps, like the
+    /// keys of an incomplete vim command or the ones starting a search or a macro, since `?` is
+    /// used to show the help and `q` to quit there.
+    pub fn handles_before_keymaps(&self, input: &Input) -> bool {
+        if self.is_insert_mode() {
+            return false;
+        }
+
+        if self.is_operator_pending() || !self.vim.pending.is_empty() {
+            return true;
+        }
+
+        // Searches, commands and macros start in the normal mode only.
+        self.mode == EditorMode::Normal
+            && input.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+            && matches!(
+                input.key_code,
+                KeyCode::Char('/' | '?' | 'n' | 'N' | ':' | 'q' | '@' | '.' | '"')
+            )
     }
 
     pub fn set_current_entry<D: DataProvider>(&mut self, entry_id: Option<u32>, app: &App<D>) {
@@ -90,6 +210,7 @@ impl<'a> Editor<'a> {
         };
 
         self.text_area = text_area;
+        self.restore_search_pattern();
 
         self.refresh_has_unsaved(app);
     }
@@ -99,7 +220,20 @@ impl<'a> Editor<'a> {
         input: &Input,
         app: &App<D>,
     ) -> anyhow::Result<HandleInputReturnType> {
-        if self.is_insert_mode() {
+        self.status_msg = None;
+
+        if self.is_command_mode() {
+            self.handle_command_line_input(input, app);
+            return Ok(HandleInputReturnType::Handled);
+        }
+
+        if self.is_standard_mode() && self.handle_standard_shortcuts(input) {
+            self.is_dirty = true;
+            self.refresh_has_unsaved(app);
+            return Ok(HandleInputReturnType::Handled);
+        }
+
+        if self.is_insert_mode() || self.is_standard_mode() {
             // We must handle clipboard operation separately if sync with system clipboard is
             // activated
             if app.settings.sync_os_clipboard {
@@ -126,9 +260,7 @@ impl<'a> Editor<'a> {
                 }
             }
 
-            // give the input to the editor
-            let key_event = KeyEvent::from(input);
-            if self.text_area.input(key_event) {
+            if self.insert_input(input) {
                 self.is_dirty = true;
                 self.refresh_has_unsaved(app);
             }
@@ -146,13 +278,16 @@ impl<'a> Editor<'a> {
     ) -> anyhow::Result<HandleInputReturnType> {
         debug_assert!(!self.is_insert_mode());
 
+        self.status_msg = None;
+
         if app.get_current_entry().is_none() {
             return Ok(HandleInputReturnType::Handled);
         }
 
         let sync_os_clipboard = app.settings.sync_os_clipboard;
 
-        if is_default_navigation(input) {
+        let is_typing_command = self.is_operator_pending() || !self.vim.pending.is_empty();
+        if is_default_navigation(input) && !is_typing_command {
             let key_event = KeyEvent::from(input);
             self.text_area.input(key_event);
         } else if !self.is_visual_mode()
@@ -213,129 +348,955 @@ impl<'a> Editor<'a> {
         }
     }
 
+    /// Handles the shortcuts of the standard profile which differ from the emacs keybindings of
+    /// the text area, returning if the input has been handled.
+    fn handle_standard_shortcuts(&mut self, input: &Input) -> bool {
+        if !input.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+
+        match input.key_code {
+            KeyCode::Char('z') => self.text_area.undo(),
+            KeyCode::Char('y' | 'Z') => self.text_area.redo(),
+            _ => return false,
+        };
+
+        true
+    }
+
+    /// Gives the input to the text area in the insert mode, returning if the text has changed.
+    fn insert_input(&mut self, input: &Input) -> bool {
+        if self.vim.recording_insert {
+            self.vim.command_inputs.push(*input);
+        }
+
+        self.text_area.input(KeyEvent::from(input))
+    }
+
+    /// Handles the input in the normal and visual modes, keeping the keys of the command being
+    /// typed to repeat it with `.` when it changes the text.
     fn handle_vim_motions(&mut self, input: &Input, sync_os_clipboard: bool) -> anyhow::Result<()> {
+        self.vim.command_inputs.push(*input);
+
+        let is_change = self.handle_vim_keys(input, sync_os_clipboard)?;
+
+        if self.vim.pending.is_empty() && !self.is_operator_pending() {
+            self.vim.finish_command(is_change, self.is_insert_mode());
+        }
+
+        Ok(())
+    }
+
+    /// Handles the vim keys returning whether the text has been changed.
+    fn handle_vim_keys(&mut self, input: &Input, sync_os_clipboard: bool) -> anyhow::Result<bool> {
+        if let Some(awaiting) = self.vim.pending.awaiting.take() {
+            return self.handle_awaited_key(awaiting, input, sync_os_clipboard);
+        }
+
         let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
 
         match (input.key_code, has_control) {
-            (KeyCode::Char('h'), false) => {
-                self.text_area.move_cursor(CursorMove::Back);
+            (KeyCode::Char(digit), false) if self.vim.pending.push_digit(digit) => {
+                return Ok(false);
             }
-            (KeyCode::Char('j'), false) => {
-                self.text_area.move_cursor(CursorMove::Down);
+            (KeyCode::Char('"'), false) if !self.is_operator_pending() => {
+                self.vim.pending.awaiting = Some(Awaiting::Register);
+                return Ok(false);
             }
-            (KeyCode::Char('k'), false) => {
-                self.text_area.move_cursor(CursorMove::Up);
+            (KeyCode::Char('g'), false) => {
+                self.vim.pending.awaiting = Some(Awaiting::GoTo);
+                return Ok(false);
             }
-            (KeyCode::Char('l'), false) => {
-                self.text_area.move_cursor(CursorMove::Forward);
+            (KeyCode::Char('i' | 'a'), false) if self.is_operator_pending() => {
+                let scope = if input.key_code == KeyCode::Char('i') {
+                    ObjectScope::Inner
+                } else {
+                    ObjectScope::Around
+                };
+                self.vim.pending.awaiting = Some(Awaiting::TextObject(scope));
+                return Ok(false);
             }
-            (KeyCode::Char('w'), false) | (KeyCode::Char('e'), false) => {
-                self.text_area.move_cursor(CursorMove::WordForward);
+            (KeyCode::Char('q'), false) if self.is_operator_pending() => {}
+            (KeyCode::Char('q'), false) if self.vim.recording_macro.is_some() => {
+                self.stop_macro_recording();
+                return Ok(false);
             }
-            (KeyCode::Char('b'), false) => {
-                self.text_area.move_cursor(CursorMove::WordBack);
+            (KeyCode::Char('q'), false) => {
+                self.vim.pending.awaiting = Some(Awaiting::MacroRecord);
+                return Ok(false);
             }
-            (KeyCode::Char('^'), false) => {
-                self.text_area.move_cursor(CursorMove::Head);
+            (KeyCode::Char('@'), false) if !self.is_operator_pending() => {
+                self.vim.pending.awaiting = Some(Awaiting::MacroPlay);
+                return Ok(false);
             }
-            (KeyCode::Char('$'), false) => {
-                self.text_area.move_cursor(CursorMove::End);
+            _ => {}
+        }
+
+        let pending = std::mem::take(&mut self.vim.pending);
+        let count = pending.total_count();
+
+        if let EditorMode::OperatorPending(operator) = self.mode {
+            self.set_editor_mode(EditorMode::Normal);
+            let range = match input.key_code {
+                // Doubled operator like `dd` applies on the lines
+                KeyCode::Char(key) if key == operator.key() && !has_control => {
+                    let (row, _) = self.text_area.cursor();
+                    let last_row = self.text_area.lines().len() - 1;
+                    Some(TextRange::Lines {
+                        start: row,
+                        end: (row + count - 1).min(last_row),
+                    })
+                }
+                KeyCode::Char('0') if !has_control => {
+                    self.motion_range(operator, Motion::LineHead, &pending)
+                }
+                _ => Motion::from_input(input)
+                    .and_then(|motion| self.motion_range(operator, motion, &pending)),
+            };
+
+            return match range {
+                Some(range) => {
+                    self.apply_operator(operator, range, pending.register, sync_os_clipboard)
+                }
+                None => Ok(false),
+            };
+        }
+
+        if let Some(motion) = Motion::from_input(input).filter(|_| !has_control) {
+            self.move_by_motion(motion, &pending);
+            return Ok(false);
+        }
+
+        let is_change = match (input.key_code, has_control) {
+            (KeyCode::Char('0'), false) => {
+                self.text_area.move_cursor(CursorMove::Head);
+                false
             }
-            (KeyCode::Char('D'), false) => {
-                self.text_area.delete_line_by_end();
-                self.exec_os_clipboard(ClipboardOperation::Copy)?;
+            (KeyCode::Char(key @ ('d' | 'c' | 'y')), false) => {
+                let operator = Operator::from_key(key).expect("Operator keys are checked");
+                // Keep the count and the register for the motion
+                self.vim.pending = PendingKeys {
+                    operator_count: pending.count,
+                    register: pending.register,
+                    ..Default::default()
+                };
+                self.set_editor_mode(EditorMode::OperatorPending(operator));
+                false
             }
-            (KeyCode::Char('C'), false) => {
-                self.text_area.delete_line_by_end();
-                self.exec_os_clipboard(ClipboardOperation::Copy)?;
-                self.mode = EditorMode::Insert;
+            (KeyCode::Char('x'), false) => {
+                let range = self.motion_range(Operator::Delete, Motion::Right, &pending);
+                match range {
+                    Some(range) => self.apply_operator(
+                        Operator::Delete,
+                        range,
+                        pending.register,
+                        sync_os_clipboard,
+                    )?,
+                    None => false,
+                }
             }
-            (KeyCode::Char('p'), false) => {
-                if sync_os_clipboard {
-                    self.exec_os_clipboard(ClipboardOperation::Paste)?;
+            (KeyCode::Char(key @ ('D' | 'C')), false) => {
+                let operator = if key == 'D' {
+                    Operator::Delete
                 } else {
-                    self.text_area.paste();
+                    Operator::Change
+                };
+                match self.motion_range(operator, Motion::LineEnd, &pending) {
+                    Some(range) => {
+                        self.apply_operator(operator, range, pending.register, sync_os_clipboard)?
+                    }
+                    None if operator == Operator::Change => {
+                        self.set_editor_mode(EditorMode::Insert);
+                        true
+                    }
+                    None => false,
                 }
             }
+            (KeyCode::Char(key @ ('p' | 'P')), false) => {
+                self.paste_register(pending.register, key == 'P', count, sync_os_clipboard)?
+            }
+            (KeyCode::Char('.'), false) => {
+                let inputs = self.vim.last_change.clone();
+                self.vim.queue_replay(&inputs, count);
+                false
+            }
             (KeyCode::Char('u'), false) => {
-                self.text_area.undo();
+                for _ in 0..count {
+                    self.text_area.undo();
+                }
+                false
             }
             (KeyCode::Char('r'), true) => {
-                self.text_area.redo();
+                for _ in 0..count {
+                    self.text_area.redo();
+                }
+                false
             }
-            (KeyCode::Char('x'), false) => {
-                self.text_area.delete_next_char();
-                self.exec_os_clipboard(ClipboardOperation::Copy)?;
+            (KeyCode::Char('i'), false) => {
+                self.mode = EditorMode::Insert;
+                true
             }
-            (KeyCode::Char('i'), false) => self.mode = EditorMode::Insert,
             (KeyCode::Char('a'), false) => {
                 self.text_area.move_cursor(CursorMove::Forward);
                 self.mode = EditorMode::Insert;
+                true
             }
             (KeyCode::Char('A'), false) => {
                 self.text_area.move_cursor(CursorMove::End);
                 self.mode = EditorMode::Insert;
+                true
             }
             (KeyCode::Char('o'), false) => {
                 self.text_area.move_cursor(CursorMove::End);
                 self.text_area.insert_newline();
                 self.mode = EditorMode::Insert;
+                true
             }
             (KeyCode::Char('O'), false) => {
                 self.text_area.move_cursor(CursorMove::Head);
                 self.text_area.insert_newline();
                 self.text_area.move_cursor(CursorMove::Up);
                 self.mode = EditorMode::Insert;
+                true
             }
             (KeyCode::Char('I'), false) => {
                 self.text_area.move_cursor(CursorMove::Head);
                 self.mode = EditorMode::Insert;
+                true
             }
             (KeyCode::Char('d'), true) => {
                 self.text_area.scroll(Scrolling::HalfPageDown);
+                false
             }
             (KeyCode::Char('u'), true) => {
                 self.text_area.scroll(Scrolling::HalfPageUp);
+                false
             }
             (KeyCode::Char('f'), true) => {
                 self.text_area.scroll(Scrolling::PageDown);
+                false
             }
             (KeyCode::Char('b'), true) => {
                 self.text_area.scroll(Scrolling::PageUp);
+                false
+            }
+            (KeyCode::Char('/'), false) => {
+                self.start_command_line(CommandKind::SearchForward);
+                false
+            }
+            (KeyCode::Char('?'), false) => {
+                self.start_command_line(CommandKind::SearchBackward);
+                false
+            }
+            (KeyCode::Char(':'), false) => {
+                self.start_command_line(CommandKind::Ex);
+                false
+            }
+            (KeyCode::Char('n'), false) => {
+                for _ in 0..count {
+                    self.search_next(false);
+                }
+                false
             }
+            (KeyCode::Char('N'), false) => {
+                for _ in 0..count {
+                    self.search_next(true);
+                }
+                false
+            }
+            _ => false,
+        };
+
+        Ok(is_change)
+    }
+
+    /// Handles the key after a prefix key like `"`, `q`, `@`, `g` or the start of a text object.
+    fn handle_awaited_key(
+        &mut self,
+        awaiting: Awaiting,
+        input: &Input,
+        sync_os_clipboard: bool,
+    ) -> anyhow::Result<bool> {
+        let key = match input.key_code {
+            KeyCode::Char(key) if !input.modifiers.contains(KeyModifiers::CONTROL) => key,
+            // Any other key cancels the whole command like in vim
+            _ => {
+                self.cancel_pending_keys();
+                return Ok(false);
+            }
+        };
+
+        match awaiting {
+            Awaiting::Register if is_register_name(key) => {
+                self.vim.pending.register = (key != '"').then_some(key);
+                Ok(false)
+            }
+            Awaiting::MacroRecord if key.is_ascii_alphanumeric() => {
+                self.vim.pending = PendingKeys::default();
+                self.vim.recording_macro = Some((key.to_ascii_lowercase(), Vec::new()));
+                Ok(false)
+            }
+            Awaiting::MacroPlay if key == '@' || key.is_ascii_alphanumeric() => {
+                let count = self.vim.pending.total_count();
+                self.vim.pending = PendingKeys::default();
+                let name = match key {
+                    '@' => self.vim.last_macro,
+                    key => Some(key.to_ascii_lowercase()),
+                };
+                match name.and_then(|name| self.vim.macros.get(&name).map(|keys| (name, keys))) {
+                    Some((name, inputs)) => {
+                        let inputs = inputs.clone();
+                        self.vim.last_macro = Some(name);
+                        self.vim.queue_replay(&inputs, count);
+                    }
+                    None => self.status_msg = Some(String::from("No macro recorded")),
+                }
+                Ok(false)
+            }
+            Awaiting::GoTo if key == 'g' => {
+                let pending = std::mem::take(&mut self.vim.pending);
+                match self.mode {
+                    EditorMode::OperatorPending(operator) => {
+                        self.set_editor_mode(EditorMode::Normal);
+                        let range = self.motion_range(operator, Motion::Top, &pending);
+                        match range {
+                            Some(range) => self.apply_operator(
+                                operator,
+                                range,
+                                pending.register,
+                                sync_os_clipboard,
+                            ),
+                            None => Ok(false),
+                        }
+                    }
+                    _ => {
+                        self.move_by_motion(Motion::Top, &pending);
+                        Ok(false)
+                    }
+                }
+            }
+            Awaiting::TextObject(scope) => {
+                let pending = std::mem::take(&mut self.vim.pending);
+                let EditorMode::OperatorPending(operator) = self.mode else {
+                    return Ok(false);
+                };
+                self.set_editor_mode(EditorMode::Normal);
+                let range = text_object(
+                    self.text_area.lines(),
+                    self.text_area.cursor(),
+                    scope,
+                    key,
+                    pending.total_count(),
+                );
+                match range {
+                    Some(range) => {
+                        self.apply_operator(operator, range, pending.register, sync_os_clipboard)
+                    }
+                    None => Ok(false),
+                }
+            }
+            _ => {
+                self.cancel_pending_keys();
+                Ok(false)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn is_operator_pending(&self) -> bool {
+        matches!(self.mode, EditorMode::OperatorPending(_))
+    }
+
+    /// Drops the keys of the command being typed and leaves the operator-pending mode.
+    fn cancel_pending_keys(&mut self) {
+        self.vim.pending = PendingKeys::default();
+        self.vim.command_inputs.clear();
+        if self.is_operator_pending() {
+            self.set_editor_mode(EditorMode::Normal);
+        }
+    }
+
+    /// Moves the cursor with the motion the times of the count.
+    fn move_by_motion(&mut self, motion: Motion, pending: &PendingKeys) {
+        let count = pending.total_count();
+        let has_count = pending.count.is_some() || pending.operator_count.is_some();
+
+        if let Some(cursor_move) = motion.cursor_move() {
+            for _ in 0..count {
+                self.text_area.move_cursor(cursor_move);
+            }
+            return;
+        }
+
+        let last_row = self.text_area.lines().len() - 1;
+        match motion {
+            Motion::LineHead => self.text_area.move_cursor(CursorMove::Head),
+            Motion::FirstNonBlank => {
+                let (row, _) = self.text_area.cursor();
+                let col = first_non_blank(&self.text_area.lines()[row]);
+                self.text_area
+                    .move_cursor(CursorMove::Jump(row as u16, col as u16));
+            }
+            Motion::LineEnd => {
+                for _ in 1..count {
+                    self.text_area.move_cursor(CursorMove::Down);
+                }
+                self.text_area.move_cursor(CursorMove::End);
+            }
+            Motion::Top | Motion::Bottom => {
+                let row = match (has_count, motion) {
+                    (true, _) => (count - 1).min(last_row),
+                    (false, Motion::Top) => 0,
+                    (false, _) => last_row,
+                };
+                let col = first_non_blank(&self.text_area.lines()[row]);
+                self.text_area
+                    .move_cursor(CursorMove::Jump(row as u16, col as u16));
+            }
+            _ => unreachable!("Motion {motion:?} is handled with its cursor move"),
+        }
+    }
+
+    /// Gets the range between the cursor and the target of the motion for the operator.
+    fn motion_range(
+        &mut self,
+        operator: Operator,
+        motion: Motion,
+        pending: &PendingKeys,
+    ) -> Option<TextRange> {
+        let start = self.text_area.cursor();
+        let lines = self.text_area.lines();
+
+        // `cw` changes the word up to its end like `ce` in vim
+        if operator == Operator::Change
+            && motion == Motion::WordForward
+            && lines[start.0]
+                .chars()
+                .nth(start.1)
+                .is_some_and(|ch| !ch.is_whitespace())
+        {
+            let count = pending.total_count();
+            let word = text_object(lines, start, ObjectScope::Inner, 'w', count * 2 - 1)?;
+            let TextRange::Chars { end, .. } = word else {
+                unreachable!("Words are char ranges");
+            };
+            return Some(TextRange::Chars { start, end });
+        }
+
+        self.move_by_motion(motion, pending);
+        let target = self.text_area.cursor();
+        self.text_area
+            .move_cursor(CursorMove::Jump(start.0 as u16, start.1 as u16));
+
+        let lines = self.text_area.lines();
+        if motion.is_linewise() {
+            return Some(TextRange::Lines {
+                start: start.0.min(target.0),
+                end: start.0.max(target.0),
+            });
+        }
+
+        let (from, mut to) = if target < start {
+            (target, start)
+        } else {
+            (start, target)
+        };
+
+        let line_len = |row: usize| lines[row].chars().count();
+        match motion {
+            // Chars motions stay in the current line with operators
+            Motion::Left | Motion::Right if to.0 != from.0 => to = (from.0, line_len(from.0)),
+            // Word motions don't take the indentation of the next line
+            Motion::WordForward
+                if to.0 > from.0 && lines[to.0].chars().take(to.1).all(char::is_whitespace) =>
+            {
+                to = (to.0 - 1, line_len(to.0 - 1));
+            }
+            motion if motion.is_inclusive() => to.1 = (to.1 + 1).min(line_len(to.0)),
             _ => {}
         }
 
+        (from != to).then_some(TextRange::Chars {
+            start: from,
+            end: to,
+        })
+    }
+
+    /// Applies the operator on the range returning whether the text has been changed.
+    fn apply_operator(
+        &mut self,
+        operator: Operator,
+        range: TextRange,
+        register: Option<char>,
+        sync_os_clipboard: bool,
+    ) -> anyhow::Result<bool> {
+        let text = range.text(self.text_area.lines());
+        let linewise = matches!(range, TextRange::Lines { .. });
+
+        match operator {
+            Operator::Yank => {
+                let (row, col) = match range {
+                    TextRange::Chars { start, .. } => start,
+                    TextRange::Lines { start, .. } => (start, self.text_area.cursor().1),
+                };
+                self.text_area
+                    .move_cursor(CursorMove::Jump(row as u16, col as u16));
+            }
+            Operator::Delete => self.delete_range(range, false),
+            Operator::Change => {
+                self.delete_range(range, true);
+                self.set_editor_mode(EditorMode::Insert);
+            }
+        }
+
+        self.store_yank(text, linewise, register, sync_os_clipboard)?;
+
+        Ok(operator != Operator::Yank)
+    }
+
+    /// Deletes the text of the range as one undo step, keeping an empty line in place of the
+    /// deleted lines if `keep_line` is set.
+    fn delete_range(&mut self, range: TextRange, keep_line: bool) {
+        let lines = self.text_area.lines();
+        let line_len = |row: usize| lines[row].chars().count();
+
+        let (start, end) = match range {
+            TextRange::Chars { start, end } => (start, end),
+            TextRange::Lines { start, end } if keep_line => ((start, 0), (end, line_len(end))),
+            TextRange::Lines { start, end } if end + 1 < lines.len() => ((start, 0), (end + 1, 0)),
+            TextRange::Lines { start, end } if start > 0 => {
+                ((start - 1, line_len(start - 1)), (end, line_len(end)))
+            }
+            TextRange::Lines { start, end } => ((start, 0), (end, line_len(end))),
+        };
+
+        self.text_area
+            .move_cursor(CursorMove::Jump(start.0 as u16, start.1 as u16));
+        self.text_area.start_selection();
+        self.text_area
+            .move_cursor(CursorMove::Jump(end.0 as u16, end.1 as u16));
+        self.text_area.cut();
+
+        if let TextRange::Lines { start, .. } = range {
+            let row = start.min(self.text_area.lines().len() - 1);
+            let col = if keep_line {
+                0
+            } else {
+                first_non_blank(&self.text_area.lines()[row])
+            };
+            self.text_area
+                .move_cursor(CursorMove::Jump(row as u16, col as u16));
+        }
+    }
+
+    /// Stores the yanked or deleted text in the unnamed register and in the given one, or in the
+    /// clipboard of the operating system if it's synced.
+    fn store_yank(
+        &mut self,
+        text: String,
+        linewise: bool,
+        register: Option<char>,
+        sync_os_clipboard: bool,
+    ) -> anyhow::Result<()> {
+        self.text_area.set_yank_text(text.clone());
+        self.vim.yank_linewise = linewise;
+
+        match register {
+            Some('+' | '*') => set_os_clipboard(text),
+            Some(name) => {
+                self.vim.set_register(name, text, linewise);
+                Ok(())
+            }
+            None if sync_os_clipboard => set_os_clipboard(text),
+            None => Ok(()),
+        }
+    }
+
+    /// Pastes the text of the register the times of the count after the cursor, or before it
+    /// if `before` is set. Lines are pasted below or above the current line.
+    fn paste_register(
+        &mut self,
+        register: Option<char>,
+        before: bool,
+        count: usize,
+        sync_os_clipboard: bool,
+    ) -> anyhow::Result<bool> {
+        let (text, linewise) = match register {
+            Some('+' | '*') => (get_os_clipboard()?, false),
+            Some(name) => match self.vim.get_register(name) {
+                Some(register) => (register.text.clone(), register.linewise),
+                None => {
+                    self.status_msg = Some(format!("Register {name} is empty"));
+                    return Ok(false);
+                }
+            },
+            None if sync_os_clipboard => {
+                let text = get_os_clipboard()?;
+                // Lines yanked in the editor are still lines if the clipboard didn't change
+                let linewise = self.vim.yank_linewise && text == self.text_area.yank_text();
+                (text, linewise)
+            }
+            None => (self.text_area.yank_text(), self.vim.yank_linewise),
+        };
+
+        if text.is_empty() && !linewise {
+            return Ok(false);
+        }
+
+        let separator = if linewise { "\n" } else { "" };
+        let text = vec![text; count].join(separator);
+        let (row, col) = self.text_area.cursor();
+
+        match (linewise, before) {
+            (true, true) => {
+                self.text_area.move_cursor(CursorMove::Head);
+                self.text_area.insert_str(format!("{text}\n"));
+                self.text_area.move_cursor(CursorMove::Jump(row as u16, 0));
+            }
+            (true, false) => {
+                self.text_area.move_cursor(CursorMove::End);
+                self.text_area.insert_str(format!("\n{text}"));
+                self.text_area
+                    .move_cursor(CursorMove::Jump(row as u16 + 1, 0));
+            }
+            (false, true) => {
+                self.text_area.insert_str(text);
+            }
+            (false, false) => {
+                if col < self.text_area.lines()[row].chars().count() {
+                    self.text_area.move_cursor(CursorMove::Forward);
+                }
+                self.text_area.insert_str(text);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn stop_macro_recording(&mut self) {
+        if let Some((name, mut inputs)) = self.vim.recording_macro.take() {
+            // Drop the `q` which stopped the recording
+            inputs.pop();
+            self.vim.macros.insert(name, inputs);
+        }
+    }
+
+    /// Records the input in the macro if a recording is running.
+    pub fn record_macro_input(&mut self, input: &Input) {
+        if let Some((_, inputs)) = self.vim.recording_macro.as_mut() {
+            inputs.push(*input);
+        }
+    }
+
+    /// Takes the inputs to play after the current one, from a macro or a repeated change.
+    pub fn take_replay_inputs(&mut self) -> Vec<Input> {
+        std::mem::take(&mut self.vim.replay)
+    }
+
+    pub fn set_status_msg(&mut self, msg: String) {
+        self.status_msg = Some(msg);
+    }
+
+    fn start_command_line(&mut self, kind: CommandKind) {
+        self.set_editor_mode(EditorMode::Command);
+        self.command_line = Some(CommandLine {
+            kind,
+            text: String::new(),
+        });
+    }
+
+    fn handle_command_line_input<D: DataProvider>(&mut self, input: &Input, app: &App<D>) {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+        let Some(command_line) = self.command_line.as_mut() else {
+            self.set_editor_mode(EditorMode::Normal);
+            return;
+        };
+
+        match input.key_code {
+            KeyCode::Esc => self.set_editor_mode(EditorMode::Normal),
+            KeyCode::Char('c' | '[') if has_control => self.set_editor_mode(EditorMode::Normal),
+            // Backspace removes the last char, leaving the command line when empty like in vim
+            KeyCode::Backspace if command_line.text.pop().is_none() => {
+                self.set_editor_mode(EditorMode::Normal);
+            }
+            KeyCode::Enter => {
+                let command_line = self.command_line.take().expect("Command line is checked");
+                self.set_editor_mode(EditorMode::Normal);
+                self.execute_command_line(command_line, app);
+            }
+            KeyCode::Char(c) if !has_control => command_line.text.push(c),
+            _ => {}
+        }
+    }
+
+    fn execute_command_line<D: DataProvider>(&mut self, command_line: CommandLine, app: &App<D>) {
+        match command_line.kind {
+            CommandKind::SearchForward | CommandKind::SearchBackward => {
+                self.search_backward = command_line.kind == CommandKind::SearchBackward;
+                // Empty search repeats the last one like in vim
+                if !command_line.text.is_empty()
+                    && let Err(err) = self.set_search(command_line.text)
+                {
+                    self.status_msg = Some(err);
+                    return;
+                }
+                self.search_next(false);
+            }
+            CommandKind::Ex => {
+                let command = command_line.text.trim();
+                match command {
+                    "" => {}
+                    "noh" | "nohlsearch" => {
+                        // Clears the highlights only. `n` and `N` will bring them back.
+                        let _ = self.text_area.set_search_pattern("");
+                    }
+                    command => match Substitute::parse(command) {
+                        Ok(substitute) => self.substitute(&substitute, app),
+                        Err(err) => self.status_msg = Some(err),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Sets the search text as the current pattern highlighting its matches.
+    fn set_search(&mut self, text: String) -> Result<(), String> {
+        self.text_area
+            .set_search_pattern(smart_case_pattern(&text))
+            .map_err(|err| format_regex_error(&err))?;
+        self.last_search = Some(text);
+
         Ok(())
     }
 
+    /// Applies the last search on the text area after replacing it on entry change.
+    fn restore_search_pattern(&mut self) {
+        if let Some(text) = self.last_search.as_ref() {
+            // The pattern is already validated when it has been set.
+            let _ = self.text_area.set_search_pattern(smart_case_pattern(text));
+        }
+    }
+
+    /// Moves the cursor to the next match of the last search in its direction, or the opposite
+    /// one when `reverse` is set.
+    fn search_next(&mut self, reverse: bool) {
+        let Some(text) = self.last_search.clone() else {
+            self.status_msg = Some(String::from("No previous search pattern"));
+            return;
+        };
+
+        // Highlights could have been cleared with `:noh`
+        if self.text_area.search_pattern().is_none() {
+            self.restore_search_pattern();
+        }
+
+        let found = if self.search_backward != reverse {
+            self.text_area.search_back(false)
+        } else {
+            self.text_area.search_forward(false)
+        };
+
+        if !found {
+            self.status_msg = Some(format!("Pattern not found: {text}"));
+        }
+    }
+
+    fn substitute<D: DataProvider>(&mut self, substitute: &Substitute, app: &App<D>) {
+        let lines = self.text_area.lines();
+        let (cursor_row, _) = self.text_area.cursor();
+        let (start, end) = if substitute.all_lines {
+            (0, lines.len() - 1)
+        } else {
+            (cursor_row, cursor_row)
+        };
+
+        let mut count = 0;
+        let new_lines: Vec<String> = lines[start..=end]
+            .iter()
+            .map(|line| {
+                let (new_line, line_count) = substitute.apply(line);
+                count += line_count;
+                new_line
+            })
+            .collect();
+
+        if count == 0 {
+            self.status_msg = Some(String::from("Pattern not found"));
+            return;
+        }
+
+        let end_col = lines[end].chars().count();
+
+        // Replacing the lines as one selection keeps the substitution as a single undo step.
+        self.text_area
+            .move_cursor(CursorMove::Jump(start as u16, 0));
+        self.text_area.start_selection();
+        self.text_area
+            .move_cursor(CursorMove::Jump(end as u16, end_col as u16));
+        self.text_area.insert_str(new_lines.join("\n"));
+        self.text_area.move_cursor(CursorMove::Head);
+
+        self.is_dirty = true;
+        self.refresh_has_unsaved(app);
+
+        self.status_msg = Some(match count {
+            1 => String::from("1 substitution"),
+            count => format!("{count} substitutions"),
+        });
+    }
+
+    /// Moves the cursor to the given position and highlights the matches of the search text
+    /// there, setting it as the last search.
+    pub fn jump_to_match(&mut self, row: usize, col: usize, search_text: String) {
+        self.set_editor_mode(EditorMode::Normal);
+        self.search_backward = false;
+        if let Err(err) = self.set_search(search_text) {
+            self.status_msg = Some(err);
+        }
+        self.text_area
+            .move_cursor(CursorMove::Jump(row as u16, col as u16));
+    }
+
+    /// Returns the row and the byte range of the word under the cursor or right before it.
+    pub fn word_at_cursor(&self) -> Option<(usize, Range<usize>)> {
+        let (row, col) = self.text_area.cursor();
+        let line = &self.text_area.lines()[row];
+        let byte_col = line
+            .char_indices()
+            .nth(col)
+            .map_or(line.len(), |(idx, _)| idx);
+
+        spell_check::words(line)
+            .find(|range| range.start <= byte_col && byte_col <= range.end)
+            .map(|range| (row, range))
+    }
+
+    pub fn get_text(&self, row: usize, range: Range<usize>) -> &str {
+        &self.text_area.lines()[row][range]
+    }
+
+    /// Moves the cursor to the next misspelled word, wrapping around to the start of the content.
+    pub fn jump_to_next_spelling_error(&mut self, checker: &SpellChecker) {
+        let cursor = self.text_area.cursor();
+        let lines = self.text_area.lines();
+        let errors: Vec<(usize, usize)> = spelling_errors(lines, 0..lines.len(), checker)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(row, ranges)| {
+                ranges
+                    .into_iter()
+                    .map(move |range| (row, lines[row][..range.start].chars().count()))
+            })
+            .collect();
+
+        let next = match errors.iter().find(|pos| **pos > cursor) {
+            Some(pos) => Some(*pos),
+            None => {
+                let first = errors.first().copied();
+                if first.is_some_and(|pos| pos != cursor) {
+                    self.status_msg = Some("Search wrapped to the top".into());
+                }
+                first
+            }
+        };
+
+        match next {
+            Some((row, col)) => {
+                if self.is_visual_mode() {
+                    self.set_editor_mode(EditorMode::Normal);
+                }
+                self.text_area
+                    .move_cursor(CursorMove::Jump(row as u16, col as u16));
+            }
+            None => self.status_msg = Some("No spelling errors found".into()),
+        }
+    }
+
+    /// Replaces the text in the byte range of the given row.
+    pub fn replace_text<D: DataProvider>(
+        &mut self,
+        row: usize,
+        range: Range<usize>,
+        text: &str,
+        app: &App<D>,
+    ) {
+        let Some(line) = self.text_area.lines().get(row) else {
+            return;
+        };
+        let Some(start) = line.get(..range.start).map(|text| text.chars().count()) else {
+            return;
+        };
+        let Some(end) = line.get(range).map(|text| start + text.chars().count()) else {
+            return;
+        };
+
+        if self.is_visual_mode() {
+            self.set_editor_mode(EditorMode::Normal);
+        }
+
+        self.text_area
+            .move_cursor(CursorMove::Jump(row as u16, start as u16));
+        self.text_area.start_selection();
+        self.text_area
+            .move_cursor(CursorMove::Jump(row as u16, end as u16));
+        self.text_area.insert_str(text);
+
+        self.is_dirty = true;
+        self.refresh_has_unsaved(app);
+    }
+
     pub fn get_editor_mode(&self) -> EditorMode {
         self.mode
     }
 
     pub fn set_editor_mode(&mut self, mode: EditorMode) {
+        // The standard profile edits the text in one mode.
+        if self.profile == EditorProfile::Standard {
+            return;
+        }
+
         match (self.mode, mode) {
             (EditorMode::Normal, EditorMode::Visual) => {
                 self.text_area.start_selection();
             }
-            (EditorMode::Visual, EditorMode::Normal | EditorMode::Insert) => {
+            (EditorMode::Visual, EditorMode::Normal | EditorMode::Insert | EditorMode::Command) => {
                 self.text_area.cancel_selection();
             }
+            (EditorMode::Command, _) => {
+                self.command_line = None;
+            }
+            (EditorMode::Insert, EditorMode::Normal) => {
+                self.vim.finish_insert();
+            }
+            (EditorMode::OperatorPending(_), _) => {
+                self.vim.pending = PendingKeys::default();
+            }
             _ => {}
         }
 
         self.mode = mode;
     }
 
-    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+    pub fn render_widget(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        styles: &Styles,
+        spell_checker: Option<&SpellChecker>,
+    ) {
         let mut title = "Content".to_owned();
         if self.is_active {
             let mode_caption = match self.mode {
-                EditorMode::Normal => " - NORMAL",
+                EditorMode::Normal | EditorMode::OperatorPending(_) => " - NORMAL",
                 EditorMode::Insert => " - EDIT",
                 EditorMode::Visual => " - Visual",
+                EditorMode::Command => " - COMMAND",
+                EditorMode::Standard => " - EDIT",
             };
             title.push_str(mode_caption);
+            if let Some((name, _)) = self.vim.recording_macro.as_ref() {
+                title.push_str(&format!(" - recording @{name}"));
+            }
         }
         if self.has_unsaved {
             title.push_str(" *");
@@ -344,23 +1305,47 @@ impl<'a> Editor<'a> {
         let estyles = &styles.editor;
 
         let text_block_style = match (self.mode, self.is_active) {
-            (EditorMode::Insert, _) => estyles.block_insert,
+            (EditorMode::Insert, _) | (EditorMode::Standard, true) => estyles.block_insert,
+            (EditorMode::Standard, false) => estyles.block_normal_inactive,
             (EditorMode::Visual, _) => estyles.block_visual,
-            (EditorMode::Normal, true) => estyles.block_normal_active,
-            (EditorMode::Normal, false) => estyles.block_normal_inactive,
+            (EditorMode::Normal | EditorMode::Command | EditorMode::OperatorPending(_), true) => {
+                estyles.block_normal_active
+            }
+            (EditorMode::Normal | EditorMode::Command | EditorMode::OperatorPending(_), false) => {
+                estyles.block_normal_inactive
+            }
         };
 
-        self.text_area.set_block(
-            Block::default()
-                .borders(Borders::ALL)
-                .style(text_block_style)
-                .title(title),
+        let (words, chars) = self.text_counts();
+        let counts = format!(
+            " {words} words | {chars} chars | {} min read ",
+            words.div_ceil(READING_WORDS_PER_MINUTE)
         );
 
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .style(text_block_style)
+            .title(title)
+            .title(Line::from(counts).right_aligned());
+
+        if let Some(command_line) = self.command_line.as_ref() {
+            block = block.title_bottom(format!(
+                "{}{}",
+                command_line.kind.prompt(),
+                command_line.text
+            ));
+        } else if let Some(msg) = self.status_msg.as_ref() {
+            block = block.title_bottom(Line::from(msg.to_owned()));
+        }
+
+        self.text_area.set_block(block);
+
         let cursor_style = if self.is_active {
             let s = match self.mode {
-                EditorMode::Normal => estyles.cursor_normal,
-                EditorMode::Insert => estyles.cursor_insert,
+                EditorMode::Normal | EditorMode::Command | EditorMode::OperatorPending(_) => {
+                    estyles.cursor_normal
+                }
+                EditorMode::Insert | EditorMode::Standard => estyles.cursor_insert,
                 EditorMode::Visual => estyles.cursor_visual,
             };
             Style::from(s)
@@ -376,12 +1361,158 @@ impl<'a> Editor<'a> {
         self.text_area
             .set_selection_style(Style::default().bg(Color::White).fg(Color::Black));
 
+        self.text_area.set_search_style(estyles.search_match.into());
+
         frame.render_widget(&self.text_area, area);
 
+        let block_bg = text_block_style.bg.unwrap_or(Color::Reset);
+        self.render_highlights(frame, area, block_bg, estyles, spell_checker);
+
         self.render_vertical_scrollbar(frame, area);
         self.render_horizontal_scrollbar(frame, area);
     }
 
+    /// Returns the count of the words and the characters of the content.
+    fn text_counts(&self) -> (usize, usize) {
+        let lines = self.text_area.lines();
+        let words = lines
+            .iter()
+            .map(|line| line.split_whitespace().count())
+            .sum();
+        let chars = lines.iter().map(|line| line.chars().count()).sum::<usize>()
+            + lines.len().saturating_sub(1);
+
+        (words, chars)
+    }
+
+    /// Applies the Markdown highlights and the underlines of the spelling errors on the rendered
+    /// text area since `TextArea` doesn't support custom highlighting. Cells styled by the text
+    /// area itself, like the cursor, the selection and the search matches, are kept as they are.
+    fn render_highlights(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        block_bg: Color,
+        styles: &EditorStyles,
+        spell_checker: Option<&SpellChecker>,
+    ) {
+        let inner = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        if inner.is_empty() {
+            return;
+        }
+
+        let (top_row, top_col) = self.viewport_top(inner.height);
+        let tab_len = self.text_area.tab_length().max(1) as usize;
+        let lines = self.text_area.lines();
+        let rows = top_row..(top_row + inner.height as usize).min(lines.len());
+        let mut highlights = markdown::highlight_lines(lines, rows.clone(), styles);
+        if let Some(checker) = spell_checker {
+            let errors = spelling_errors(lines, rows.clone(), checker);
+            for (line_highlights, errors) in highlights.iter_mut().zip(errors) {
+                line_highlights.extend(errors.into_iter().map(|range| markdown::Highlight {
+                    range,
+                    style: markdown::additive(styles.spelling_error),
+                }));
+            }
+        }
+
+        let buffer = frame.buffer_mut();
+        for (offset, (line, highlights)) in lines[rows].iter().zip(highlights).enumerate() {
+            let y = inner.y + offset as u16;
+            let mut col = 0;
+            for span in markdown::highlighted_spans(line, &highlights) {
+                for ch in span.content.chars() {
+                    let ch_width = match ch {
+                        '\t' => tab_len - col % tab_len,
+                        ch => ch.width().unwrap_or_default(),
+                    };
+                    if span.style != Style::default() {
+                        let visible_cols = (col..col + ch_width)
+                            .filter(|cell_col| *cell_col >= top_col)
+                            .map(|cell_col| cell_col - top_col)
+                            .filter(|x| *x < inner.width as usize);
+                        for x in visible_cols {
+                            let cell = &mut buffer[(inner.x + x as u16, y)];
+                            // Text cells get the reset style of the text area over the block one.
+                            if cell.bg == block_bg || cell.bg == Color::Reset {
+                                cell.set_style(span.style);
+                            }
+                        }
+                    }
+                    col += ch_width;
+                }
+            }
+        }
+    }
+
+    /// Gets the top row and column of the text area viewport from its last rendering, which
+    /// isn't exposed by `TextArea`, by moving the cursor into the viewport then restoring it.
+    fn viewport_top(&mut self, height: u16) -> (usize, usize) {
+        let (row, col) = self.text_area.cursor();
+
+        self.text_area.move_cursor(CursorMove::Jump(0, 0));
+        self.text_area.move_cursor(CursorMove::InViewport);
+        let (top_row, _) = self.text_area.cursor();
+
+        // The column is limited to the length of the line, so the longest visible line is used.
+        // If it's shorter than the column then all visible lines are scrolled out anyway.
+        let lines = self.text_area.lines();
+        let bottom_row = (top_row + height as usize).min(lines.len());
+        let longest_row = (top_row..bottom_row)
+            .max_by_key(|row| lines[*row].chars().count())
+            .unwrap_or(top_row);
+        self.text_area
+            .move_cursor(CursorMove::Jump(longest_row as u16, 0));
+        self.text_area.move_cursor(CursorMove::InViewport);
+        let (_, top_col) = self.text_area.cursor();
+
+        self.text_area
+            .move_cursor(CursorMove::Jump(row as u16, col as u16));
+
+        (top_row, top_col)
+    }
+
+    /// Renders the content of the current entry read-only while the focus stays on the entries list.
+    /// The content is rendered formatted if `render_markdown` is set, otherwise it's highlighted.
+    pub fn render_preview(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        styles: &Styles,
+        render_markdown: bool,
+    ) {
+        let title = if render_markdown {
+            "Preview - Markdown"
+        } else {
+            "Preview"
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .style(styles.editor.block_normal_inactive)
+            .title(title);
+
+        let lines = self.text_area.lines();
+
+        let preview = if render_markdown {
+            let width = area.width.saturating_sub(2) as usize;
+            Paragraph::new(markdown::render_lines(lines, width, &styles.editor)).block(block)
+        } else {
+            let highlights = markdown::highlight_lines(lines, 0..lines.len(), &styles.editor);
+            let text: Vec<Line> = lines
+                .iter()
+                .zip(highlights.iter())
+                .map(|(line, highlights)| Line::from(markdown::highlighted_spans(line, highlights)))
+                .collect();
+            Paragraph::new(text).block(block).wrap(Wrap { trim: false })
+        };
+
+        frame.render_widget(preview, area);
+    }
+
     pub fn render_vertical_scrollbar(&mut self, frame: &mut Frame, area: Rect) {
         let lines_count = self.text_area.lines().len();
 
@@ -481,6 +1612,7 @@ impl<'a> Editor<'a> {
         text_area.move_cursor(tui_textarea::CursorMove::End);
 
         self.text_area = text_area;
+        self.restore_search_pattern();
 
         self.refresh_has_unsaved(app);
     }
@@ -550,6 +1682,313 @@ fn is_default_navigation(input: &Input) -> bool {
     }
 }
 
+/// Returns the byte ranges of the misspelled words in the given rows skipping the code.
+fn spelling_errors(
+    lines: &[String],
+    rows: Range<usize>,
+    checker: &SpellChecker,
+) -> Vec<Vec<Range<usize>>> {
+    let unchecked = markdown::unchecked_ranges(lines, rows.clone());
+
+    lines[rows]
+        .iter()
+        .zip(unchecked)
+        .map(|(line, unchecked)| {
+            checker
+                .misspelled_words(line)
+                .into_iter()
+                .filter(|range| {
+                    !unchecked
+                        .iter()
+                        .any(|skip| skip.start < range.end && range.start < skip.end)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Gets the column of the first non-blank char in the line.
+fn first_non_blank(line: &str) -> usize {
+    line.chars()
+        .position(|ch| !ch.is_whitespace())
+        .unwrap_or_default()
+}
+
+/// Checks if the char names a register, where `"` is the unnamed one and `+` and `*` are the
+/// clipboard of the operating system.
+fn is_register_name(name: char) -> bool {
+    name.is_ascii_alphanumeric() || matches!(name, '"' | '+' | '*')
+}
+
+fn set_os_clipboard(text: String) -> anyhow::Result<()> {
+    let mut clipboard = Clipboard::new().map_err(map_clipboard_error)?;
+    clipboard.set_text(text).map_err(map_clipboard_error)
+}
+
+fn get_os_clipboard() -> anyhow::Result<String> {
+    let mut clipboard = Clipboard::new().map_err(map_clipboard_error)?;
+    clipboard.get_text().map_err(map_clipboard_error)
+}
+
 fn map_clipboard_error(err: arboard::Error) -> anyhow::Error {
     anyhow!("Error while communicating with the operation system clipboard.\nError Details: {err}",)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    #[test]
+    fn markdown_highlights_follow_scrolling() {
+        let mut editor = Editor::new();
+        editor.set_active(true);
+        let lines = (0..50)
+            .map(|idx| {
+                if idx % 2 == 0 {
+                    format!("# Heading {idx}")
+                } else {
+                    format!("Text {idx}")
+                }
+            })
+            .collect();
+        editor.text_area = TextArea::new(lines);
+        editor.text_area.move_cursor(CursorMove::Jump(31, 0));
+
+        let styles = Styles::default();
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        terminal
+            .draw(|frame| editor.render_widget(frame, frame.area(), &styles, None))
+            .unwrap();
+
+        // Cursor stays in place after getting the viewport
+        assert_eq!(editor.text_area.cursor(), (31, 0));
+
+        let heading_fg = styles.editor.markdown_heading.fg;
+        let buffer = terminal.backend().buffer();
+        let first_line: String = (1..29).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert!(first_line.starts_with("# Heading 24"));
+        for y in 1..9 {
+            let line: String = (1..29).map(|x| buffer[(x, y)].symbol()).collect();
+            let is_heading = line.starts_with("# Heading");
+            assert_eq!(
+                is_heading,
+                buffer[(3, y)].fg == heading_fg.unwrap(),
+                "{line}"
+            );
+        }
+    }
+
+    /// Types the keys in the editor like the UI does, where `\x1b` is the escape key.
+    fn type_keys(editor: &mut Editor, keys: &str) {
+        let mut inputs: VecDeque<(Input, bool)> = keys
+            .chars()
+            .map(|key| {
+                let input = match key {
+                    '\x1b' => Input::new(KeyCode::Esc, KeyModifiers::NONE),
+                    key => Input::new(KeyCode::Char(key), KeyModifiers::NONE),
+                };
+                (input, true)
+            })
+            .collect();
+
+        while let Some((input, is_typed)) = inputs.pop_front() {
+            if is_typed {
+                editor.record_macro_input(&input);
+            }
+            match (editor.get_editor_mode(), input.key_code) {
+                (EditorMode::Insert, KeyCode::Esc) => editor.set_editor_mode(EditorMode::Normal),
+                (EditorMode::Insert, _) => {
+                    editor.insert_input(&input);
+                }
+                _ => editor.handle_vim_motions(&input, false).unwrap(),
+            }
+            for (idx, input) in editor.take_replay_inputs().into_iter().enumerate() {
+                inputs.insert(idx, (input, false));
+            }
+        }
+    }
+
+    fn editor_with_lines(lines: &[&str]) -> Editor<'static> {
+        let mut editor = Editor::new();
+        editor.text_area = TextArea::new(lines.iter().map(|line| line.to_string()).collect());
+        editor
+    }
+
+    #[test]
+    fn operators_with_counts_and_text_objects() {
+        let mut editor = editor_with_lines(&["one two three four", "call(a, b)", "last"]);
+
+        type_keys(&mut editor, "d2w");
+        assert_eq!(editor.text_area.lines()[0], "three four");
+
+        type_keys(&mut editor, "wciwfive\x1b");
+        assert_eq!(editor.text_area.lines()[0], "three five");
+
+        type_keys(&mut editor, "j05ldi(");
+        assert_eq!(editor.text_area.lines()[1], "call()");
+
+        type_keys(&mut editor, "2dd");
+        assert_eq!(editor.text_area.lines(), ["three five"]);
+        assert_eq!(editor.get_editor_mode(), EditorMode::Normal);
+    }
+
+    #[test]
+    fn named_registers_and_linewise_paste() {
+        let mut editor = editor_with_lines(&["first", "second", "", "third"]);
+
+        type_keys(&mut editor, "\"ayapGx");
+        assert_eq!(editor.text_area.lines()[3], "hird");
+
+        type_keys(&mut editor, "\"ap");
+        assert_eq!(
+            editor.text_area.lines(),
+            ["first", "second", "", "hird", "first", "second", ""]
+        );
+
+        // Unnamed register holds the deleted char while `a` keeps the paragraph
+        type_keys(&mut editor, "ggP");
+        assert_eq!(editor.text_area.lines()[0], "tfirst");
+    }
+
+    #[test]
+    fn repeat_last_change() {
+        let mut editor = editor_with_lines(&["a b c d e"]);
+
+        type_keys(&mut editor, "dw.");
+        assert_eq!(editor.text_area.lines(), ["c d e"]);
+
+        type_keys(&mut editor, "cwx\x1bw.w.");
+        assert_eq!(editor.text_area.lines(), ["x x x"]);
+    }
+
+    #[test]
+    fn record_and_play_macros() {
+        let mut editor = editor_with_lines(&["one", "two", "three", "four"]);
+
+        type_keys(&mut editor, "ggqaA!\x1bjq");
+        assert_eq!(editor.text_area.lines()[0], "one!");
+
+        type_keys(&mut editor, "2@a");
+        assert_eq!(editor.text_area.lines(), ["one!", "two!", "three!", "four"]);
+
+        type_keys(&mut editor, "@@");
+        assert_eq!(editor.text_area.lines()[3], "four!");
+    }
+
+    #[test]
+    fn keys_before_keymaps_in_normal_mode_only() {
+        let mut editor = editor_with_lines(&["text"]);
+        let help_key = Input::new(KeyCode::Char('?'), KeyModifiers::NONE);
+        assert!(editor.handles_before_keymaps(&help_key));
+
+        editor.set_editor_mode(EditorMode::Visual);
+        assert!(!editor.handles_before_keymaps(&help_key));
+
+        // Keys of the commands being typed still go to the editor.
+        type_keys(&mut editor, "\"");
+        assert!(editor.handles_before_keymaps(&Input::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn standard_profile_keybindings() {
+        let mut editor = editor_with_lines(&["hello"]);
+        editor.set_profile(EditorProfile::Standard);
+        editor.set_editor_mode(EditorMode::Normal);
+        assert_eq!(editor.get_editor_mode(), EditorMode::Standard);
+
+        let mut press = |key_code, modifiers| {
+            let input = Input::new(key_code, modifiers);
+            if !editor.handle_standard_shortcuts(&input) {
+                editor.insert_input(&input);
+            }
+            editor.get_content()
+        };
+
+        press(KeyCode::Char('e'), KeyModifiers::CONTROL);
+        assert_eq!(press(KeyCode::Char('!'), KeyModifiers::NONE), "hello!");
+        assert_eq!(press(KeyCode::Char('z'), KeyModifiers::CONTROL), "hello");
+        assert_eq!(press(KeyCode::Char('y'), KeyModifiers::CONTROL), "hello!");
+
+        // Shift selects the text which gets replaced on typing
+        press(KeyCode::Left, KeyModifiers::CONTROL);
+        press(KeyCode::Home, KeyModifiers::SHIFT);
+        assert_eq!(press(KeyCode::Char('H'), KeyModifiers::SHIFT), "H!");
+    }
+
+    #[test]
+    fn spelling_errors_outside_code() {
+        let dir = tempfile::Builder::new()
+            .prefix("editor-spelling")
+            .tempdir()
+            .unwrap();
+        std::fs::write(
+            dir.path().join("en_US.aff"),
+            "TRY abcdefghijklmnopqrstuvwxyz\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("en_US.dic"), "4\nsome\ntext\nhere\ncode\n").unwrap();
+        let checker = SpellChecker::load(&crate::settings::spell_check::SpellCheckSettings {
+            enabled: true,
+            dictionary_dirs: vec![dir.path().to_path_buf()],
+            personal_dictionary: Some(dir.path().join("dictionary.txt")),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut editor = editor_with_lines(&[
+            "some txet here",
+            "`wrnog` code",
+            "```",
+            "fn wrnog() {}",
+            "```",
+            "here sme",
+        ]);
+        editor.set_active(true);
+
+        let styles = Styles::default();
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        terminal
+            .draw(|frame| editor.render_widget(frame, frame.area(), &styles, Some(&checker)))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let underlined = |x: u16, y: u16| {
+            buffer[(x, y)]
+                .modifier
+                .contains(ratatui::style::Modifier::UNDERLINED)
+        };
+        assert!(!underlined(1, 1));
+        assert!((6..10).all(|x| underlined(x, 1)));
+        assert!(!underlined(11, 1));
+        assert!(!underlined(9, 4));
+        assert!(underlined(6, 6));
+
+        editor.text_area.move_cursor(CursorMove::Jump(0, 0));
+        editor.jump_to_next_spelling_error(&checker);
+        assert_eq!(editor.text_area.cursor(), (0, 5));
+        editor.jump_to_next_spelling_error(&checker);
+        assert_eq!(editor.text_area.cursor(), (5, 5));
+        assert_eq!(
+            editor
+                .word_at_cursor()
+                .map(|(row, range)| editor.get_text(row, range)),
+            Some("sme")
+        );
+        editor.jump_to_next_spelling_error(&checker);
+        assert_eq!(editor.text_area.cursor(), (0, 5));
+        assert!(editor.status_msg.is_some());
+    }
+
+    #[test]
+    fn word_and_char_counts() {
+        let editor = editor_with_lines(&["Hello  world,", "", "  café au lait "]);
+        assert_eq!(editor.text_counts(), (5, 30));
+
+        let editor = editor_with_lines(&[]);
+        assert_eq!(editor.text_counts(), (0, 0));
+    }
+}
@@ -0,0 +1,183 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::app::filter::pattern::{format_regex_error, regex_has_uppercase};
+
+/// Converts the search text to a regex pattern using smart-case, which makes the search
+/// case-insensitive unless the text has uppercase characters outside of its escape sequences.
+pub fn smart_case_pattern(text: &str) -> String {
+    if regex_has_uppercase(text) {
+        text.to_owned()
+    } else {
+        format!("(?i){text}")
+    }
+}
+
+/// Builds a regex from the search text using smart-case.
+pub fn build_search_regex(text: &str) -> Result<Regex, String> {
+    Regex::new(&smart_case_pattern(text)).map_err(|err| format_regex_error(&err))
+}
+
+/// Substitute command in the form `s/pattern/replacement/flags` where `%s` applies it on all
+/// lines instead of the current one only.
+#[derive(Debug)]
+pub struct Substitute {
+    pub all_lines: bool,
+    regex: Regex,
+    replacement: String,
+    global: bool,
+}
+
+impl Substitute {
+    /// Parses the substitute command text without the leading `:`.
+    ///
+    /// Supported flags are `g` to replace all the matches in the line and `i` to ignore case.
+    /// Any character can be used as delimiter instead of `/` and it can be escaped with `\`.
+    /// The replacement can refer to capture groups with `$1` or `${name}`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (all_lines, rest) = match text.strip_prefix('%') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+
+        let rest = rest
+            .strip_prefix('s')
+            .ok_or_else(|| format!("Not an editor command: {text}"))?;
+
+        let mut chars = rest.chars();
+        let delimiter = chars
+            .next()
+            .ok_or_else(|| String::from("Missing pattern for substitute command"))?;
+        if delimiter.is_alphanumeric() || delimiter == '\\' || delimiter.is_whitespace() {
+            return Err(format!("Invalid delimiter '{delimiter}'"));
+        }
+
+        let parts = split_unescaped(chars.as_str(), delimiter);
+        let (pattern, replacement, flags) = match parts.as_slice() {
+            [pattern] => (pattern.as_str(), "", ""),
+            [pattern, replacement] => (pattern.as_str(), replacement.as_str(), ""),
+            [pattern, replacement, flags] => {
+                (pattern.as_str(), replacement.as_str(), flags.as_str())
+            }
+            _ => return Err(String::from("Too many delimiters in substitute command")),
+        };
+
+        if pattern.is_empty() {
+            return Err(String::from("Missing pattern for substitute command"));
+        }
+
+        let mut global = false;
+        let mut ignore_case = false;
+        for flag in flags.chars() {
+            match flag {
+                'g' => global = true,
+                'i' => ignore_case = true,
+                flag => return Err(format!("Unknown flag '{flag}'")),
+            }
+        }
+
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|err| format_regex_error(&err))?;
+
+        Ok(Self {
+            all_lines,
+            regex,
+            replacement: replacement.to_owned(),
+            global,
+        })
+    }
+
+    /// Applies the substitution on the line returning the new line and the count of replaced
+    /// matches.
+    pub fn apply(&self, line: &str) -> (String, usize) {
+        let count = if self.global {
+            self.regex.find_iter(line).count()
+        } else {
+            usize::from(self.regex.is_match(line))
+        };
+        if count == 0 {
+            return (line.to_owned(), 0);
+        }
+
+        // Zero as limit replaces all matches.
+        let limit = if self.global { 0 } else { 1 };
+        let replaced = self.regex.replacen(line, limit, self.replacement.as_str());
+
+        (replaced.into_owned(), count)
+    }
+}
+
+/// Splits the text on the delimiter skipping the escaped ones, removing the escape character
+/// before them.
+fn split_unescaped(text: &str, delimiter: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let current = parts.last_mut().expect("Parts are never empty");
+        match c {
+            '\\' if chars.peek() == Some(&delimiter) => {
+                current.push(delimiter);
+                chars.next();
+            }
+            c if c == delimiter => parts.push(String::new()),
+            c => current.push(c),
+        }
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smart_case_search() {
+        let lower = build_search_regex("todo").unwrap();
+        assert!(lower.is_match("TODO: write tests"));
+
+        let upper = build_search_regex("TODO").unwrap();
+        assert!(!upper.is_match("todo: write tests"));
+
+        let escaped = build_search_regex(r"todo\W\s\D").unwrap();
+        assert!(escaped.is_match("TODO: write tests"));
+
+        assert!(build_search_regex("(todo").is_err());
+    }
+
+    #[test]
+    fn substitute_current_line() {
+        let sub = Substitute::parse("s/foo/bar/").unwrap();
+        assert!(!sub.all_lines);
+        assert_eq!(sub.apply("foo foo"), (String::from("bar foo"), 1));
+        assert_eq!(sub.apply("nothing"), (String::from("nothing"), 0));
+    }
+
+    #[test]
+    fn substitute_global_with_flags() {
+        let sub = Substitute::parse("%s/Foo/bar/gi").unwrap();
+        assert!(sub.all_lines);
+        assert_eq!(sub.apply("foo FOO Foo"), (String::from("bar bar bar"), 3));
+    }
+
+    #[test]
+    fn substitute_escaped_delimiter_and_groups() {
+        let sub = Substitute::parse(r"s/(\w+)\/(\w+)/${2}-$1/g").unwrap();
+        assert_eq!(sub.apply("a/b c/d"), (String::from("b-a d-c"), 2));
+
+        let sub = Substitute::parse("s#/usr#/opt#").unwrap();
+        assert_eq!(sub.apply("/usr/bin"), (String::from("/opt/bin"), 1));
+    }
+
+    #[test]
+    fn substitute_invalid_commands() {
+        assert!(Substitute::parse("x/foo/bar/").is_err());
+        assert!(Substitute::parse("s").is_err());
+        assert!(Substitute::parse("s//bar/").is_err());
+        assert!(Substitute::parse("s/foo/bar/x").is_err());
+        assert!(Substitute::parse("s/foo/bar/g/extra").is_err());
+        assert!(Substitute::parse("s/(foo/bar/").is_err());
+    }
+}
@@ -0,0 +1,618 @@
+//! State and text helpers for the vim emulation of the editor, like counts, operators, text
+//! objects, registers and the recorded inputs to repeat changes and play macros.
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use tui_textarea::CursorMove;
+
+use crate::app::keymap::Input;
+
+/// Upper limit of the counts to avoid freezing the app with typos like `99999999x`.
+const MAX_COUNT: usize = 10_000;
+
+/// Position in the text as row and column in characters.
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+impl Operator {
+    pub fn from_key(key: char) -> Option<Self> {
+        match key {
+            'd' => Some(Operator::Delete),
+            'c' => Some(Operator::Change),
+            'y' => Some(Operator::Yank),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> char {
+        match self {
+            Operator::Delete => 'd',
+            Operator::Change => 'c',
+            Operator::Yank => 'y',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordEnd,
+    WordBack,
+    LineHead,
+    FirstNonBlank,
+    LineEnd,
+    /// Goes to the first line or to the line of the count with `gg`.
+    Top,
+    /// Goes to the last line or to the line of the count with `G`.
+    Bottom,
+    ParagraphForward,
+    ParagraphBack,
+}
+
+impl Motion {
+    /// Maps the input to its motion without the ones starting with `g` and the `0` which could
+    /// be a part of a count.
+    pub fn from_input(input: &Input) -> Option<Self> {
+        if !input.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            return None;
+        }
+
+        let motion = match input.key_code {
+            KeyCode::Char('h') | KeyCode::Left => Motion::Left,
+            KeyCode::Char('l') | KeyCode::Right => Motion::Right,
+            KeyCode::Char('k') | KeyCode::Up => Motion::Up,
+            KeyCode::Char('j') | KeyCode::Down => Motion::Down,
+            KeyCode::Char('w') => Motion::WordForward,
+            KeyCode::Char('e') => Motion::WordEnd,
+            KeyCode::Char('b') => Motion::WordBack,
+            KeyCode::Char('^') => Motion::FirstNonBlank,
+            KeyCode::Char('$') | KeyCode::End => Motion::LineEnd,
+            KeyCode::Home => Motion::LineHead,
+            KeyCode::Char('G') => Motion::Bottom,
+            KeyCode::Char('}') => Motion::ParagraphForward,
+            KeyCode::Char('{') => Motion::ParagraphBack,
+            _ => return None,
+        };
+
+        Some(motion)
+    }
+
+    /// Cursor move of the text area for the motions which are repeated with the count.
+    pub fn cursor_move(self) -> Option<CursorMove> {
+        let cursor_move = match self {
+            Motion::Left => CursorMove::Back,
+            Motion::Right => CursorMove::Forward,
+            Motion::Up => CursorMove::Up,
+            Motion::Down => CursorMove::Down,
+            Motion::WordForward => CursorMove::WordForward,
+            Motion::WordEnd => CursorMove::WordEnd,
+            Motion::WordBack => CursorMove::WordBack,
+            Motion::ParagraphForward => CursorMove::ParagraphForward,
+            Motion::ParagraphBack => CursorMove::ParagraphBack,
+            Motion::LineHead
+            | Motion::FirstNonBlank
+            | Motion::LineEnd
+            | Motion::Top
+            | Motion::Bottom => return None,
+        };
+
+        Some(cursor_move)
+    }
+
+    /// Operators apply on whole lines with line-wise motions.
+    pub fn is_linewise(self) -> bool {
+        matches!(
+            self,
+            Motion::Up | Motion::Down | Motion::Top | Motion::Bottom
+        )
+    }
+
+    /// Operators include the character under the cursor at the end of inclusive motions.
+    pub fn is_inclusive(self) -> bool {
+        self == Motion::WordEnd
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectScope {
+    /// The object only, like the text between brackets or a word without its spaces.
+    Inner,
+    /// The object with its surroundings, like the brackets or the trailing spaces of a word.
+    Around,
+}
+
+/// Range in the text to apply an operator on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextRange {
+    /// Characters from the start to the end, where the end is excluded.
+    Chars { start: Pos, end: Pos },
+    /// Whole lines from the start row to the end one, both included.
+    Lines { start: usize, end: usize },
+}
+
+impl TextRange {
+    /// Gets the text of the range with the lines joined with a new line.
+    pub fn text<S: AsRef<str>>(&self, lines: &[S]) -> String {
+        match *self {
+            TextRange::Chars { start, end } => {
+                let mut text = String::new();
+                for (row, line) in lines.iter().enumerate().take(end.0 + 1).skip(start.0) {
+                    let line = line.as_ref();
+                    let from = if row == start.0 { start.1 } else { 0 };
+                    let to = if row == end.0 { end.1 } else { usize::MAX };
+                    text.extend(line.chars().skip(from).take(to.saturating_sub(from)));
+                    if row != end.0 {
+                        text.push('\n');
+                    }
+                }
+                text
+            }
+            TextRange::Lines { start, end } => lines[start..=end]
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+/// Keys typed for a command which isn't complete yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingKeys {
+    pub count: Option<usize>,
+    /// Count typed before the operator, multiplied with the one of the motion like in `2d3w`.
+    pub operator_count: Option<usize>,
+    pub register: Option<char>,
+    pub awaiting: Option<Awaiting>,
+}
+
+/// Key awaited after a prefix key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Awaiting {
+    /// Name of the register after `"`.
+    Register,
+    /// Register to record a macro into after `q`.
+    MacroRecord,
+    /// Register of the macro to play after `@`.
+    MacroPlay,
+    /// Second key of the commands starting with `g`.
+    GoTo,
+    /// Kind of the text object after `i` or `a` in the operator-pending mode.
+    TextObject(ObjectScope),
+}
+
+impl PendingKeys {
+    pub fn is_empty(&self) -> bool {
+        *self == PendingKeys::default()
+    }
+
+    /// Adds the digit to the count returning false if it can't be a part of a count.
+    pub fn push_digit(&mut self, digit: char) -> bool {
+        let Some(value) = digit.to_digit(10) else {
+            return false;
+        };
+
+        // Zero is the motion to the line head if it isn't following other digits
+        if value == 0 && self.count.is_none() {
+            return false;
+        }
+
+        let count = self.count.unwrap_or(0) * 10 + value as usize;
+        self.count = Some(count.min(MAX_COUNT));
+
+        true
+    }
+
+    /// Gets the count of the command, taking the one of the operator into account.
+    pub fn total_count(&self) -> usize {
+        (self.operator_count.unwrap_or(1) * self.count.unwrap_or(1)).min(MAX_COUNT)
+    }
+}
+
+/// Text stored in a register with the info whether it holds whole lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Register {
+    pub text: String,
+    pub linewise: bool,
+}
+
+/// State of the vim emulation which persists between the entries.
+#[derive(Debug, Default)]
+pub struct VimState {
+    pub pending: PendingKeys,
+    /// Named registers. The unnamed register is the yank buffer of the text area.
+    pub registers: HashMap<char, Register>,
+    /// Marks whether the yank buffer of the text area holds whole lines.
+    pub yank_linewise: bool,
+    pub macros: HashMap<char, Vec<Input>>,
+    pub recording_macro: Option<(char, Vec<Input>)>,
+    pub last_macro: Option<char>,
+    /// Inputs of the last change to repeat it with `.`.
+    pub last_change: Vec<Input>,
+    /// Inputs of the command being typed, which become the last change if they modify the text.
+    pub command_inputs: Vec<Input>,
+    /// Marks that the inputs in the insert mode are a part of the current change.
+    pub recording_insert: bool,
+    /// Inputs to play after handling the current one, for macros and repeating changes.
+    pub replay: Vec<Input>,
+}
+
+impl VimState {
+    /// Stores the text in the given register. Upper case names append to their registers.
+    pub fn set_register(&mut self, name: char, text: String, linewise: bool) {
+        if name.is_ascii_uppercase() {
+            let register = self
+                .registers
+                .entry(name.to_ascii_lowercase())
+                .or_insert_with(|| Register {
+                    text: String::new(),
+                    linewise,
+                });
+            if register.linewise || linewise {
+                if !register.text.is_empty() {
+                    register.text.push('\n');
+                }
+                register.linewise = true;
+            }
+            register.text.push_str(&text);
+        } else {
+            self.registers.insert(name, Register { text, linewise });
+        }
+    }
+
+    pub fn get_register(&self, name: char) -> Option<&Register> {
+        self.registers.get(&name.to_ascii_lowercase())
+    }
+
+    /// Completes the command being typed, keeping its inputs as the last change if it modified
+    /// the text and didn't start the insert mode, which continues the change until it ends.
+    pub fn finish_command(&mut self, is_change: bool, is_insert: bool) {
+        if !self.pending.is_empty() {
+            return;
+        }
+
+        match (is_change, is_insert) {
+            (true, true) => self.recording_insert = true,
+            (true, false) => self.last_change = std::mem::take(&mut self.command_inputs),
+            (false, _) => self.command_inputs.clear(),
+        }
+    }
+
+    /// Completes the change which started the insert mode with the key leaving it.
+    pub fn finish_insert(&mut self) {
+        if self.recording_insert {
+            self.recording_insert = false;
+            self.command_inputs
+                .push(Input::new(KeyCode::Esc, KeyModifiers::NONE));
+            self.last_change = std::mem::take(&mut self.command_inputs);
+        }
+    }
+
+    /// Queues the inputs to be played after the current one the given times.
+    pub fn queue_replay(&mut self, inputs: &[Input], count: usize) {
+        for _ in 0..count {
+            self.replay.extend_from_slice(inputs);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punctuation,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Space
+    } else if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// Gets the range of the text object of the given kind at the cursor.
+pub fn text_object<S: AsRef<str>>(
+    lines: &[S],
+    cursor: Pos,
+    scope: ObjectScope,
+    kind: char,
+    count: usize,
+) -> Option<TextRange> {
+    match kind {
+        'w' => word_object(lines[cursor.0].as_ref(), cursor, scope, count),
+        'p' => paragraph_object(lines, cursor.0, scope, count),
+        '(' | ')' | 'b' => pair_object(lines, cursor, scope, '(', ')', count),
+        '[' | ']' => pair_object(lines, cursor, scope, '[', ']', count),
+        '{' | '}' | 'B' => pair_object(lines, cursor, scope, '{', '}', count),
+        '<' | '>' => pair_object(lines, cursor, scope, '<', '>', count),
+        '"' | '\'' | '`' => quote_object(lines[cursor.0].as_ref(), cursor, scope, kind),
+        _ => None,
+    }
+}
+
+/// Words are runs of chars of the same class in the cursor line, where `aw` includes the spaces
+/// after the word, or before it if there are none after.
+fn word_object(line: &str, cursor: Pos, scope: ObjectScope, count: usize) -> Option<TextRange> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return None;
+    }
+
+    let col = cursor.1.min(chars.len() - 1);
+    let run_end = |start: usize| {
+        let class = char_class(chars[start]);
+        (start..chars.len())
+            .find(|&idx| char_class(chars[idx]) != class)
+            .unwrap_or(chars.len())
+    };
+
+    let class = char_class(chars[col]);
+    let mut start = (0..col)
+        .rev()
+        .find(|&idx| char_class(chars[idx]) != class)
+        .map_or(0, |idx| idx + 1);
+    let mut end = col;
+
+    for _ in 0..count {
+        if end >= chars.len() {
+            break;
+        }
+        let run_is_space = char_class(chars[end]) == CharClass::Space;
+        end = run_end(end);
+        if scope == ObjectScope::Around && end < chars.len() {
+            // Around adds the spaces after a word or the word after the spaces
+            let next_is_space = char_class(chars[end]) == CharClass::Space;
+            if run_is_space != next_is_space {
+                end = run_end(end);
+            }
+        }
+    }
+
+    if scope == ObjectScope::Around
+        && end == chars.len()
+        && char_class(chars[end - 1]) != CharClass::Space
+    {
+        // No spaces after the word, so take the ones before it instead
+        while start > 0 && char_class(chars[start - 1]) == CharClass::Space {
+            start -= 1;
+        }
+    }
+
+    Some(TextRange::Chars {
+        start: (cursor.0, start),
+        end: (cursor.0, end),
+    })
+}
+
+/// Paragraphs are runs of blank or non-blank lines, where `ap` includes the blank lines after
+/// the paragraph.
+fn paragraph_object<S: AsRef<str>>(
+    lines: &[S],
+    row: usize,
+    scope: ObjectScope,
+    count: usize,
+) -> Option<TextRange> {
+    let is_blank = |row: usize| lines[row].as_ref().trim().is_empty();
+    let run_end = |start: usize| {
+        let blank = is_blank(start);
+        (start..lines.len())
+            .find(|&idx| is_blank(idx) != blank)
+            .unwrap_or(lines.len())
+    };
+
+    let blank = is_blank(row);
+    let start = (0..row)
+        .rev()
+        .find(|&idx| is_blank(idx) != blank)
+        .map_or(0, |idx| idx + 1);
+    let mut end = row;
+
+    for _ in 0..count {
+        if end >= lines.len() {
+            break;
+        }
+        let run_is_blank = is_blank(end);
+        end = run_end(end);
+        if scope == ObjectScope::Around && end < lines.len() && is_blank(end) != run_is_blank {
+            end = run_end(end);
+        }
+    }
+
+    Some(TextRange::Lines {
+        start,
+        end: end - 1,
+    })
+}
+
+/// Text between the brackets around the cursor, which can be on different lines. The count
+/// selects the outer brackets.
+fn pair_object<S: AsRef<str>>(
+    lines: &[S],
+    cursor: Pos,
+    scope: ObjectScope,
+    open: char,
+    close: char,
+    count: usize,
+) -> Option<TextRange> {
+    let positions: Vec<(Pos, char)> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.as_ref()
+                .chars()
+                .chain(std::iter::once('\n'))
+                .enumerate()
+                .map(move |(col, ch)| ((row, col), ch))
+        })
+        .collect();
+    let cursor_idx = positions.iter().position(|(pos, _)| *pos == cursor)?;
+
+    // The bracket under the cursor counts as enclosing it
+    let mut open_idx = if positions[cursor_idx].1 == close {
+        find_open(&positions, cursor_idx, open, close)?
+    } else if positions[cursor_idx].1 == open {
+        cursor_idx
+    } else {
+        find_open(&positions, cursor_idx, open, close)?
+    };
+    for _ in 1..count {
+        open_idx = find_open(&positions, open_idx, open, close)?;
+    }
+
+    let mut depth = 0;
+    let close_idx = (open_idx + 1..positions.len()).find(|&idx| match positions[idx].1 {
+        ch if ch == open => {
+            depth += 1;
+            false
+        }
+        ch if ch == close && depth == 0 => true,
+        ch if ch == close => {
+            depth -= 1;
+            false
+        }
+        _ => false,
+    })?;
+
+    let (start, end) = match scope {
+        ObjectScope::Inner => (positions[open_idx + 1].0, positions[close_idx].0),
+        // Each line ends with a new line, so there is always a position after the bracket.
+        ObjectScope::Around => (positions[open_idx].0, positions[close_idx + 1].0),
+    };
+
+    Some(TextRange::Chars { start, end })
+}
+
+/// Finds the index of the unmatched opening bracket before the given index.
+fn find_open(positions: &[(Pos, char)], before: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    (0..before).rev().find(|&idx| match positions[idx].1 {
+        ch if ch == close => {
+            depth += 1;
+            false
+        }
+        ch if ch == open && depth == 0 => true,
+        ch if ch == open => {
+            depth -= 1;
+            false
+        }
+        _ => false,
+    })
+}
+
+/// Text between the quotes around the cursor or after it in the same line.
+fn quote_object(line: &str, cursor: Pos, scope: ObjectScope, quote: char) -> Option<TextRange> {
+    let quotes: Vec<usize> = line
+        .chars()
+        .enumerate()
+        .filter_map(|(col, ch)| (ch == quote).then_some(col))
+        .collect();
+
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|&(_, close)| cursor.1 <= close)?;
+
+    let (start, end) = match scope {
+        ObjectScope::Inner => (open + 1, close),
+        ObjectScope::Around => (open, close + 1),
+    };
+
+    Some(TextRange::Chars {
+        start: (cursor.0, start),
+        end: (cursor.0, end),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars_text(lines: &[&str], range: Option<TextRange>) -> String {
+        range.expect("Object must be found").text(lines)
+    }
+
+    #[test]
+    fn counts_and_registers() {
+        let mut pending = PendingKeys::default();
+        assert!(!pending.push_digit('0'));
+        assert!(pending.push_digit('2'));
+        assert!(pending.push_digit('0'));
+        pending.operator_count = Some(3);
+        assert_eq!(pending.total_count(), 60);
+
+        let mut state = VimState::default();
+        state.set_register('a', String::from("first"), false);
+        state.set_register('A', String::from("second"), false);
+        assert_eq!(state.get_register('a').unwrap().text, "firstsecond");
+        state.set_register('A', String::from("line"), true);
+        assert_eq!(
+            state.get_register('a'),
+            Some(&Register {
+                text: String::from("firstsecond\nline"),
+                linewise: true
+            })
+        );
+    }
+
+    #[test]
+    fn word_objects() {
+        let lines = ["foo bar  baz"];
+        let text = |cursor, scope, count| {
+            chars_text(&lines, text_object(&lines, cursor, scope, 'w', count))
+        };
+
+        assert_eq!(text((0, 5), ObjectScope::Inner, 1), "bar");
+        assert_eq!(text((0, 5), ObjectScope::Around, 1), "bar  ");
+        assert_eq!(text((0, 0), ObjectScope::Inner, 3), "foo bar");
+        assert_eq!(text((0, 3), ObjectScope::Around, 1), " bar");
+        // Last word takes the spaces before it
+        assert_eq!(text((0, 10), ObjectScope::Around, 1), "  baz");
+    }
+
+    #[test]
+    fn paragraph_objects() {
+        let lines = ["one", "two", "", "", "three"];
+
+        assert_eq!(
+            text_object(&lines, (1, 0), ObjectScope::Inner, 'p', 1),
+            Some(TextRange::Lines { start: 0, end: 1 })
+        );
+        assert_eq!(
+            text_object(&lines, (0, 0), ObjectScope::Around, 'p', 1),
+            Some(TextRange::Lines { start: 0, end: 3 })
+        );
+        assert_eq!(
+            text_object(&lines, (4, 0), ObjectScope::Around, 'p', 1),
+            Some(TextRange::Lines { start: 4, end: 4 })
+        );
+    }
+
+    #[test]
+    fn pair_and_quote_objects() {
+        let lines = ["call(a, (b),", "  c) \"text\" 'x'"];
+        let text = |cursor, scope, kind, count| {
+            chars_text(&lines, text_object(&lines, cursor, scope, kind, count))
+        };
+
+        assert_eq!(text((0, 9), ObjectScope::Inner, '(', 1), "b");
+        assert_eq!(text((0, 9), ObjectScope::Inner, 'b', 2), "a, (b),\n  c");
+        assert_eq!(text((1, 3), ObjectScope::Around, ')', 1), "(a, (b),\n  c)");
+        assert_eq!(text((1, 0), ObjectScope::Inner, '"', 1), "text");
+        assert_eq!(text((1, 7), ObjectScope::Around, '"', 1), "\"text\"");
+        assert!(text_object(&lines, (0, 0), ObjectScope::Inner, '[', 1).is_none());
+    }
+}
@@ -91,6 +91,15 @@ impl EntryPopup<'_> {
         }
     }
 
+    /// Fills in the title of the journal to create.
+    #[must_use]
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title_txt = TextArea::new(vec![title.to_owned()]);
+        self.title_txt.move_cursor(CursorMove::End);
+        self.validate_title();
+        self
+    }
+
     pub fn from_entry(entry: &Entry) -> Self {
         let mut title_txt = TextArea::new(vec![entry.title.to_owned()]);
         title_txt.move_cursor(CursorMove::End);
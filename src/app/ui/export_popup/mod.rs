@@ -10,21 +10,37 @@ use ratatui::{
 };
 use tui_textarea::{CursorMove, TextArea};
 
-use crate::app::{App, keymap::Input};
+use crate::app::{
+    App,
+    exporter::{ExportFormat, load_templates},
+    keymap::Input,
+};
 
 use super::{PopupReturn, Styles, ui_functions::centered_rect_exact_height};
 
-type ExportPopupInputReturn = PopupReturn<(PathBuf, Option<u32>)>;
+type ExportPopupInputReturn = PopupReturn<ExportRequest>;
 
-const FOOTER_TEXT: &str = "Enter: confirm | Esc or <Ctrl-c>: Cancel";
+const FOOTER_TEXT: &str =
+    "Enter: confirm | <Tab>/<Shift-Tab>: Change format | Esc or <Ctrl-c>: Cancel";
 const FOOTER_MARGINE: u16 = 8;
 const DEFAULT_FILE_NAME: &str = "tjournal_export.json";
 
+#[derive(Debug, Clone)]
+pub struct ExportRequest {
+    pub path: PathBuf,
+    pub entry_id: Option<u32>,
+    pub format: ExportFormat,
+}
+
 pub struct ExportPopup<'a> {
     path_txt: TextArea<'a>,
     path_err_msg: String,
     entry_id: Option<u32>,
     paragraph_text: String,
+    /// The built-in formats and the user templates to choose from.
+    formats: Vec<ExportFormat>,
+    /// Index of the format chosen by the user. It's inferred from the file extension if not set.
+    format_idx: Option<usize>,
 }
 
 impl ExportPopup<'_> {
@@ -53,6 +69,8 @@ impl ExportPopup<'_> {
             path_err_msg: String::default(),
             entry_id: Some(entry.id),
             paragraph_text,
+            formats: get_formats(app)?,
+            format_idx: None,
         };
 
         export_popup.validate_path();
@@ -75,16 +93,25 @@ impl ExportPopup<'_> {
         let mut path_txt = TextArea::new(vec![default_path.to_string_lossy().to_string()]);
         path_txt.move_cursor(CursorMove::End);
 
-        let paragraph_text = format!(
-            "Export the selected {} journals",
-            app.selected_entries.len()
-        );
+        let paragraph_text = if app.selected_entries.is_empty() {
+            format!(
+                "Export the {} journals meeting the filter criteria",
+                app.get_active_entries().count()
+            )
+        } else {
+            format!(
+                "Export the selected {} journals",
+                app.selected_entries.len()
+            )
+        };
 
         let mut export_popup = ExportPopup {
             path_txt,
             path_err_msg: String::default(),
             entry_id: None,
             paragraph_text,
+            formats: get_formats(app)?,
+            format_idx: None,
         };
 
         export_popup.validate_path();
@@ -114,8 +141,49 @@ impl ExportPopup<'_> {
         self.entry_id.is_none()
     }
 
+    fn get_path(&self) -> PathBuf {
+        self.path_txt
+            .lines()
+            .first()
+            .expect("Path Textbox should always have one line")
+            .parse()
+            .expect("PathBuf from string should never fail")
+    }
+
+    fn chosen_format(&self) -> Option<&ExportFormat> {
+        self.format_idx.and_then(|idx| self.formats.get(idx))
+    }
+
+    /// Gets the chosen format or infers it from the file extension.
+    fn resolve_format(&self) -> ExportFormat {
+        let multiple = self.is_multi_select_mode();
+        self.chosen_format().cloned().unwrap_or_else(|| {
+            ExportFormat::from_path(&self.get_path(), multiple)
+                .unwrap_or(ExportFormat::default_for(multiple))
+        })
+    }
+
+    /// Cycles through the formats with the inferred format in between, changing the extension of
+    /// the path to match the chosen format.
+    fn cycle_format(&mut self, forward: bool) {
+        let formats_count = self.formats.len();
+        self.format_idx = match (self.format_idx, forward) {
+            (None, true) => Some(0),
+            (None, false) => formats_count.checked_sub(1),
+            (Some(idx), true) => (idx + 1 < formats_count).then_some(idx + 1),
+            (Some(idx), false) => idx.checked_sub(1),
+        };
+
+        if let Some(format) = self.chosen_format() {
+            let path = self.get_path().with_extension(format.extension());
+            self.path_txt = TextArea::new(vec![path.to_string_lossy().to_string()]);
+            self.path_txt.move_cursor(CursorMove::End);
+            self.validate_path();
+        }
+    }
+
     pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
-        let mut area = centered_rect_exact_height(70, 11, area);
+        let mut area = centered_rect_exact_height(70, 12, area);
 
         if area.width < FOOTER_TEXT.len() as u16 + FOOTER_MARGINE {
             area.height += 1;
@@ -141,6 +209,7 @@ impl ExportPopup<'_> {
                     Constraint::Length(2),
                     Constraint::Length(3),
                     Constraint::Length(1),
+                    Constraint::Length(1),
                     Constraint::Min(1),
                 ]
                 .as_ref(),
@@ -179,11 +248,20 @@ impl ExportPopup<'_> {
 
         frame.render_widget(&self.path_txt, chunks[1]);
 
+        let format_text = match self.chosen_format() {
+            Some(format) => format!("Format: {}", format.name()),
+            None => format!(
+                "Format: {} (from file extension)",
+                self.resolve_format().name()
+            ),
+        };
+        frame.render_widget(Paragraph::new(format_text), chunks[2]);
+
         let footer = Paragraph::new(FOOTER_TEXT)
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: false });
 
-        frame.render_widget(footer, chunks[3]);
+        frame.render_widget(footer, chunks[4]);
     }
 
     pub fn handle_input(&mut self, input: &Input) -> ExportPopupInputReturn {
@@ -192,6 +270,14 @@ impl ExportPopup<'_> {
             KeyCode::Esc => ExportPopupInputReturn::Cancel,
             KeyCode::Char('c') if has_ctrl => ExportPopupInputReturn::Cancel,
             KeyCode::Enter => self.handle_confirm(),
+            KeyCode::Tab => {
+                self.cycle_format(true);
+                ExportPopupInputReturn::KeepPopup
+            }
+            KeyCode::BackTab => {
+                self.cycle_format(false);
+                ExportPopupInputReturn::KeepPopup
+            }
             _ => {
                 if self.path_txt.input(KeyEvent::from(input)) {
                     self.validate_path();
@@ -207,14 +293,21 @@ impl ExportPopup<'_> {
             return ExportPopupInputReturn::KeepPopup;
         }
 
-        let path: PathBuf = self
-            .path_txt
-            .lines()
-            .first()
-            .expect("Path Textbox should always have one line")
-            .parse()
-            .expect("PathBuf from string should never fail");
-
-        ExportPopupInputReturn::Apply((path, self.entry_id))
+        ExportPopupInputReturn::Apply(ExportRequest {
+            path: self.get_path(),
+            entry_id: self.entry_id,
+            format: self.resolve_format(),
+        })
     }
 }
+
+/// Gets the built-in formats followed by the templates in the templates directory.
+fn get_formats<D: DataProvider>(app: &App<D>) -> anyhow::Result<Vec<ExportFormat>> {
+    let templates_dir = app.settings.export.get_templates_dir()?;
+    let templates = load_templates(&templates_dir)?;
+
+    Ok(ExportFormat::ALL
+        .into_iter()
+        .chain(templates.into_iter().map(ExportFormat::Template))
+        .collect())
+}
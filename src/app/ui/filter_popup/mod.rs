@@ -10,7 +10,12 @@ use ratatui::{
 use tui_textarea::{CursorMove, TextArea};
 
 use crate::app::{
-    filter::{CriteriaRelation, Filter, FilterCriterion, criterion::TagFilterOption},
+    filter::{
+        CriteriaRelation, Filter, FilterCriterion, FilterQuery,
+        criterion::TagFilterOption,
+        date_range::{format_date_filter, parse_date_filter},
+        pattern::{PatternMode, TextPattern},
+    },
     keymap::Input,
 };
 
@@ -18,7 +23,7 @@ use super::{PopupReturn, Styles, ui_functions::centered_rect};
 
 type FilterPopupReturn = PopupReturn<Option<Filter>>;
 
-const FOOTER_TEXT: &str = r"Tab: Change focused control | Enter or <Ctrl-m>: Confirm | Esc or <Ctrl-c>: Cancel | <Ctrl-r>: Change Matching Logic | <Space>: Tags Toggle Selected";
+const FOOTER_TEXT: &str = r"Tab: Change focused control | Enter or <Ctrl-m>: Confirm | Esc or <Ctrl-c>: Cancel | <Ctrl-r>: Change Matching Logic | <Ctrl-t>: Change Text Matching | <Space>: Tags Toggle Selected";
 const FOOTER_MARGIN: usize = 8;
 
 /// Text to show in tags list indicating that none tagged entries are included.
@@ -33,11 +38,19 @@ pub struct FilterPopup<'a> {
     tags_state: ListState,
     tags: Vec<String>,
     relation: CriteriaRelation,
+    /// Pattern mode for title and content, where `None` is for smart-case substring search.
+    text_mode: Option<PatternMode>,
     selected_tags: HashSet<String>,
     title_txt: TextArea<'a>,
+    title_err_msg: String,
     content_txt: TextArea<'a>,
+    content_err_msg: String,
     priority_txt: TextArea<'a>,
     priority_err_msg: String,
+    date_txt: TextArea<'a>,
+    date_err_msg: String,
+    query_txt: TextArea<'a>,
+    query_err_msg: String,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -45,6 +58,8 @@ enum FilterControl {
     TitleTxt,
     ContentTxt,
     PriorityTxt,
+    DateTxt,
+    QueryTxt,
     TagsList,
 }
 
@@ -57,11 +72,18 @@ impl FilterPopup<'_> {
         }
 
         let relation = filter.relation;
+        let query_text = filter
+            .query
+            .as_ref()
+            .map(|query| query.text().to_owned())
+            .unwrap_or_default();
 
         let mut selected_tags = HashSet::new();
         let mut title_text = String::default();
         let mut content_text = String::default();
         let mut priority_text = String::default();
+        let mut date_text = String::default();
+        let mut text_mode = None;
 
         filter.criteria.into_iter().for_each(|cr| match cr {
             FilterCriterion::Tag(TagFilterOption::Tag(tag)) => {
@@ -72,7 +94,19 @@ impl FilterPopup<'_> {
             }
             FilterCriterion::Title(title_search) => title_text = title_search,
             FilterCriterion::Content(content_search) => content_text = content_search,
+            FilterCriterion::TitlePattern(pattern) => {
+                text_mode = Some(pattern.mode());
+                title_text = pattern.pattern().to_owned();
+            }
+            FilterCriterion::ContentPattern(pattern) => {
+                text_mode = Some(pattern.mode());
+                content_text = pattern.pattern().to_owned();
+            }
             FilterCriterion::Priority(prio) => priority_text = prio.to_string(),
+            FilterCriterion::DateRange { from, to } => date_text = format_date_filter(from, to),
+            FilterCriterion::RelativeDate(range) => date_text = range.to_string(),
+            // Priority comparisons are only created within filter queries.
+            FilterCriterion::PriorityComparison { .. } => {}
         });
 
         let mut title_txt = TextArea::new(vec![title_text]);
@@ -84,6 +118,12 @@ impl FilterPopup<'_> {
         let mut priority_txt = TextArea::new(vec![priority_text]);
         priority_txt.move_cursor(CursorMove::End);
 
+        let mut date_txt = TextArea::new(vec![date_text]);
+        date_txt.move_cursor(CursorMove::End);
+
+        let mut query_txt = TextArea::new(vec![query_text]);
+        query_txt.move_cursor(CursorMove::End);
+
         let active_control = FilterControl::TitleTxt;
 
         let mut filter_popup = FilterPopup {
@@ -91,16 +131,26 @@ impl FilterPopup<'_> {
             tags_state: ListState::default(),
             tags,
             relation,
+            text_mode,
             selected_tags,
             title_txt,
+            title_err_msg: String::default(),
             content_txt,
+            content_err_msg: String::default(),
             priority_txt,
             priority_err_msg: String::default(),
+            date_txt,
+            date_err_msg: String::default(),
+            query_txt,
+            query_err_msg: String::default(),
         };
 
         filter_popup.cycle_next_tag();
 
+        filter_popup.validate_text_patterns();
         filter_popup.validate_priority();
+        filter_popup.validate_date();
+        filter_popup.validate_query();
 
         filter_popup
     }
@@ -126,6 +176,8 @@ impl FilterPopup<'_> {
                     Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(3),
+                    Constraint::Length(3),
+                    Constraint::Length(3),
                     Constraint::Min(4),
                     Constraint::Length(footer_height.try_into().unwrap()),
                 ]
@@ -133,17 +185,23 @@ impl FilterPopup<'_> {
             )
             .split(area);
 
-        self.render_relations(frame, chunks[0]);
+        let top_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(chunks[0]);
 
-        self.render_text_boxes(frame, chunks[1], chunks[2], chunks[3], styles);
+        self.render_relations(frame, top_chunks[0]);
+        self.render_text_mode(frame, top_chunks[1]);
+
+        self.render_text_boxes(frame, &chunks[1..6], styles);
 
         if self.tags.is_empty() {
-            self.render_tags_place_holder(frame, chunks[4], styles);
+            self.render_tags_place_holder(frame, chunks[6], styles);
         } else {
-            self.render_tags_list(frame, chunks[4], styles);
+            self.render_tags_list(frame, chunks[6], styles);
         }
 
-        self.render_footer(frame, chunks[5]);
+        self.render_footer(frame, chunks[7]);
     }
 
     fn render_relations(&mut self, frame: &mut Frame, area: Rect) {
@@ -164,21 +222,57 @@ impl FilterPopup<'_> {
         frame.render_widget(relation, area);
     }
 
-    fn render_text_boxes(
-        &mut self,
-        frame: &mut Frame,
-        title_area: Rect,
-        content_area: Rect,
-        priority_area: Rect,
-        styles: &Styles,
-    ) {
+    fn render_text_mode(&mut self, frame: &mut Frame, area: Rect) {
+        let mode_text = match self.text_mode {
+            None => String::from("Smart-case substring"),
+            Some(mode) => mode.to_string(),
+        };
+
+        let text_mode = Paragraph::new(mode_text)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title("Title & Content Matching"),
+            );
+
+        frame.render_widget(text_mode, area);
+    }
+
+    fn render_text_boxes(&mut self, frame: &mut Frame, areas: &[Rect], styles: &Styles) {
+        let [
+            title_area,
+            content_area,
+            priority_area,
+            date_area,
+            query_area,
+        ] = areas
+        else {
+            unreachable!("Areas must match the text boxes count");
+        };
+
         let gstyles = &styles.general;
         let active_cursor_style: Style = gstyles.input_cursor_active.into();
         let invalid_cursor_style: Style = gstyles.input_cursor_invalid.into();
         let deactivate_cursor_style = Style::default().bg(Color::Reset);
 
-        let mut title_txt_block = Block::default().title("Title").borders(Borders::ALL);
-        let mut content_txt_block = Block::default().title("Content").borders(Borders::ALL);
+        let mut title_txt_block = if self.title_err_msg.is_empty() {
+            Block::default().title("Title").borders(Borders::ALL)
+        } else {
+            Block::default()
+                .title(format!("Title : {}", self.title_err_msg))
+                .borders(Borders::ALL)
+                .style(gstyles.input_block_invalid)
+        };
+        let mut content_txt_block = if self.content_err_msg.is_empty() {
+            Block::default().title("Content").borders(Borders::ALL)
+        } else {
+            Block::default()
+                .title(format!("Content : {}", self.content_err_msg))
+                .borders(Borders::ALL)
+                .style(gstyles.input_block_invalid)
+        };
         let mut priority_txt_block = if self.priority_err_msg.is_empty() {
             Block::default().title("Priority").borders(Borders::ALL)
         } else {
@@ -187,19 +281,51 @@ impl FilterPopup<'_> {
                 .borders(Borders::ALL)
                 .style(gstyles.input_block_invalid)
         };
+        let mut date_txt_block = if self.date_err_msg.is_empty() {
+            Block::default()
+                .title("Date (e.g. 05-03-2024, 01-03-2024..31-03-2024, since 01-03-2024, last 7 days, this month)")
+                .borders(Borders::ALL)
+        } else {
+            Block::default()
+                .title(format!("Date : {}", self.date_err_msg))
+                .borders(Borders::ALL)
+                .style(gstyles.input_block_invalid)
+        };
+
+        let mut query_txt_block = if self.query_err_msg.is_empty() {
+            Block::default()
+                .title("Query, must be met too (e.g. (tag:work OR tag:oncall) AND NOT tag:done AND priority>=2)")
+                .borders(Borders::ALL)
+        } else {
+            Block::default()
+                .title(format!("Query : {}", self.query_err_msg))
+                .borders(Borders::ALL)
+                .style(gstyles.input_block_invalid)
+        };
+
+        self.date_txt.set_cursor_style(deactivate_cursor_style);
+        self.query_txt.set_cursor_style(deactivate_cursor_style);
 
         match self.active_control {
             FilterControl::TitleTxt => {
-                self.title_txt.set_cursor_style(active_cursor_style);
                 self.content_txt.set_cursor_style(deactivate_cursor_style);
                 self.priority_txt.set_cursor_style(deactivate_cursor_style);
-                title_txt_block = title_txt_block.style(gstyles.input_block_active);
+                if self.title_err_msg.is_empty() {
+                    self.title_txt.set_cursor_style(active_cursor_style);
+                    title_txt_block = title_txt_block.style(gstyles.input_block_active);
+                } else {
+                    self.title_txt.set_cursor_style(invalid_cursor_style);
+                }
             }
             FilterControl::ContentTxt => {
                 self.title_txt.set_cursor_style(deactivate_cursor_style);
-                self.content_txt.set_cursor_style(active_cursor_style);
                 self.priority_txt.set_cursor_style(deactivate_cursor_style);
-                content_txt_block = content_txt_block.style(gstyles.input_block_active);
+                if self.content_err_msg.is_empty() {
+                    self.content_txt.set_cursor_style(active_cursor_style);
+                    content_txt_block = content_txt_block.style(gstyles.input_block_active);
+                } else {
+                    self.content_txt.set_cursor_style(invalid_cursor_style);
+                }
             }
             FilterControl::TagsList => {
                 self.title_txt.set_cursor_style(deactivate_cursor_style);
@@ -216,19 +342,47 @@ impl FilterPopup<'_> {
                     self.priority_txt.set_cursor_style(invalid_cursor_style);
                 }
             }
+            FilterControl::DateTxt => {
+                self.title_txt.set_cursor_style(deactivate_cursor_style);
+                self.content_txt.set_cursor_style(deactivate_cursor_style);
+                self.priority_txt.set_cursor_style(deactivate_cursor_style);
+                if self.date_err_msg.is_empty() {
+                    self.date_txt.set_cursor_style(active_cursor_style);
+                    date_txt_block = date_txt_block.style(gstyles.input_block_active);
+                } else {
+                    self.date_txt.set_cursor_style(invalid_cursor_style);
+                }
+            }
+            FilterControl::QueryTxt => {
+                self.title_txt.set_cursor_style(deactivate_cursor_style);
+                self.content_txt.set_cursor_style(deactivate_cursor_style);
+                self.priority_txt.set_cursor_style(deactivate_cursor_style);
+                if self.query_err_msg.is_empty() {
+                    self.query_txt.set_cursor_style(active_cursor_style);
+                    query_txt_block = query_txt_block.style(gstyles.input_block_active);
+                } else {
+                    self.query_txt.set_cursor_style(invalid_cursor_style);
+                }
+            }
         }
 
         self.title_txt.set_cursor_line_style(Style::default());
         self.content_txt.set_cursor_line_style(Style::default());
         self.priority_txt.set_cursor_line_style(Style::default());
+        self.date_txt.set_cursor_line_style(Style::default());
+        self.query_txt.set_cursor_line_style(Style::default());
 
         self.title_txt.set_block(title_txt_block);
         self.content_txt.set_block(content_txt_block);
         self.priority_txt.set_block(priority_txt_block);
-
-        frame.render_widget(&self.title_txt, title_area);
-        frame.render_widget(&self.content_txt, content_area);
-        frame.render_widget(&self.priority_txt, priority_area);
+        self.date_txt.set_block(date_txt_block);
+        self.query_txt.set_block(query_txt_block);
+
+        frame.render_widget(&self.title_txt, *title_area);
+        frame.render_widget(&self.content_txt, *content_area);
+        frame.render_widget(&self.priority_txt, *priority_area);
+        frame.render_widget(&self.date_txt, *date_area);
+        frame.render_widget(&self.query_txt, *query_area);
     }
 
     fn render_tags_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
@@ -313,17 +467,37 @@ impl FilterPopup<'_> {
                     self.change_relation();
                     FilterPopupReturn::KeepPopup
                 }
+                KeyCode::Char('t') if has_control => {
+                    self.cycle_text_mode();
+                    FilterPopupReturn::KeepPopup
+                }
                 _ => {
                     match self.active_control {
-                        FilterControl::TitleTxt => _ = self.title_txt.input(KeyEvent::from(input)),
+                        FilterControl::TitleTxt => {
+                            if self.title_txt.input(KeyEvent::from(input)) {
+                                self.validate_text_patterns();
+                            }
+                        }
                         FilterControl::ContentTxt => {
-                            _ = self.content_txt.input(KeyEvent::from(input))
+                            if self.content_txt.input(KeyEvent::from(input)) {
+                                self.validate_text_patterns();
+                            }
                         }
                         FilterControl::PriorityTxt => {
                             if self.priority_txt.input(KeyEvent::from(input)) {
                                 self.validate_priority();
                             }
                         }
+                        FilterControl::DateTxt => {
+                            if self.date_txt.input(KeyEvent::from(input)) {
+                                self.validate_date();
+                            }
+                        }
+                        FilterControl::QueryTxt => {
+                            if self.query_txt.input(KeyEvent::from(input)) {
+                                self.validate_query();
+                            }
+                        }
                         FilterControl::TagsList => unreachable!("Tags List is unreachable here"),
                     };
                     FilterPopupReturn::KeepPopup
@@ -348,6 +522,10 @@ impl FilterPopup<'_> {
                     self.change_relation();
                     FilterPopupReturn::KeepPopup
                 }
+                KeyCode::Char('t') => {
+                    self.cycle_text_mode();
+                    FilterPopupReturn::KeepPopup
+                }
                 KeyCode::Esc | KeyCode::Char('q') => FilterPopupReturn::Cancel,
                 KeyCode::Char('c') if has_control => FilterPopupReturn::Cancel,
                 KeyCode::Enter => self.confirm(),
@@ -361,7 +539,9 @@ impl FilterPopup<'_> {
         self.active_control = match self.active_control {
             FilterControl::TitleTxt => FilterControl::ContentTxt,
             FilterControl::ContentTxt => FilterControl::PriorityTxt,
-            FilterControl::PriorityTxt => FilterControl::TagsList,
+            FilterControl::PriorityTxt => FilterControl::DateTxt,
+            FilterControl::DateTxt => FilterControl::QueryTxt,
+            FilterControl::QueryTxt => FilterControl::TagsList,
             FilterControl::TagsList => FilterControl::TitleTxt,
         };
 
@@ -405,6 +585,16 @@ impl FilterPopup<'_> {
         }
     }
 
+    fn cycle_text_mode(&mut self) {
+        self.text_mode = match self.text_mode {
+            None => Some(PatternMode::WholeWord),
+            Some(PatternMode::WholeWord) => Some(PatternMode::Regex),
+            Some(PatternMode::Regex) => None,
+        };
+
+        self.validate_text_patterns();
+    }
+
     fn toggle_selected(&mut self) {
         if let Some(idx) = self.tags_state.selected() {
             let tag = self
@@ -420,6 +610,22 @@ impl FilterPopup<'_> {
         }
     }
 
+    /// Compiles the title and content patterns if a pattern mode is selected.
+    fn validate_text_patterns(&mut self) {
+        let validate = |txt: &TextArea, mode: Option<PatternMode>| {
+            let text = txt.lines().first().expect("Text box has one line");
+            match mode {
+                Some(mode) if !text.is_empty() => TextPattern::new(text.to_owned(), mode)
+                    .err()
+                    .unwrap_or_default(),
+                _ => String::new(),
+            }
+        };
+
+        self.title_err_msg = validate(&self.title_txt, self.text_mode);
+        self.content_err_msg = validate(&self.content_txt, self.text_mode);
+    }
+
     fn validate_priority(&mut self) {
         let prio_text = self.priority_txt.lines().first().unwrap();
         if !prio_text.is_empty() && prio_text.parse::<u32>().is_err() {
@@ -429,12 +635,41 @@ impl FilterPopup<'_> {
         }
     }
 
+    fn validate_date(&mut self) {
+        let date_text = self.date_txt.lines().first().unwrap();
+        if date_text.is_empty() {
+            self.date_err_msg.clear();
+        } else if let Err(err) = parse_date_filter(date_text) {
+            self.date_err_msg = err;
+        } else {
+            self.date_err_msg.clear();
+        }
+    }
+
+    fn validate_query(&mut self) {
+        let query_text = self.query_txt.lines().first().unwrap();
+        if query_text.trim().is_empty() {
+            self.query_err_msg.clear();
+        } else if let Err(err) = query_text.parse::<FilterQuery>() {
+            self.query_err_msg = err;
+        } else {
+            self.query_err_msg.clear();
+        }
+    }
+
     fn is_valid_input(&self) -> bool {
-        self.priority_err_msg.is_empty()
+        self.title_err_msg.is_empty()
+            && self.content_err_msg.is_empty()
+            && self.priority_err_msg.is_empty()
+            && self.date_err_msg.is_empty()
+            && self.query_err_msg.is_empty()
     }
 
     fn confirm(&mut self) -> FilterPopupReturn {
+        self.validate_text_patterns();
         self.validate_priority();
+        self.validate_date();
+        self.validate_query();
         if !self.is_valid_input() {
             return FilterPopupReturn::KeepPopup;
         }
@@ -460,7 +695,14 @@ impl FilterPopup<'_> {
             .expect("Title TextBox has one line");
 
         if !title_filter.is_empty() {
-            critria.push(FilterCriterion::Title(title_filter.to_owned()));
+            let criterion = match self.text_mode {
+                Some(mode) => FilterCriterion::TitlePattern(
+                    TextPattern::new(title_filter.to_owned(), mode)
+                        .expect("Title pattern is validated at this point"),
+                ),
+                None => FilterCriterion::Title(title_filter.to_owned()),
+            };
+            critria.push(criterion);
         }
 
         let content_filter = self
@@ -470,7 +712,14 @@ impl FilterPopup<'_> {
             .expect("Content TextBox has one line");
 
         if !content_filter.is_empty() {
-            critria.push(FilterCriterion::Content(content_filter.to_owned()));
+            let criterion = match self.text_mode {
+                Some(mode) => FilterCriterion::ContentPattern(
+                    TextPattern::new(content_filter.to_owned(), mode)
+                        .expect("Content pattern is validated at this point"),
+                ),
+                None => FilterCriterion::Content(content_filter.to_owned()),
+            };
+            critria.push(criterion);
         }
 
         let priority_filter = self
@@ -485,15 +734,95 @@ impl FilterPopup<'_> {
             critria.push(FilterCriterion::Priority(prio));
         }
 
-        if critria.is_empty() {
+        let date_filter = self
+            .date_txt
+            .lines()
+            .first()
+            .expect("Date text box has one line");
+        if !date_filter.is_empty() {
+            let date_criterion =
+                parse_date_filter(date_filter).expect("Date text is validated at this point");
+            critria.push(date_criterion);
+        }
+
+        let query_text = self
+            .query_txt
+            .lines()
+            .first()
+            .expect("Query text box has one line");
+        let query = (!query_text.trim().is_empty()).then(|| {
+            query_text
+                .parse::<FilterQuery>()
+                .expect("Query text is validated at this point")
+        });
+
+        if critria.is_empty() && query.is_none() {
             FilterPopupReturn::Apply(None)
         } else {
             let filter = Filter {
                 relation: self.relation,
                 criteria: critria,
+                query,
             };
 
             FilterPopupReturn::Apply(Some(filter))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(popup: &mut FilterPopup, text: &str) {
+        for ch in text.chars() {
+            popup.handle_input(&Input::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+    }
+
+    fn toggle_text_mode(popup: &mut FilterPopup) {
+        popup.handle_input(&Input::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn invalid_regex_blocks_confirm() {
+        let mut popup = FilterPopup::new(Vec::new(), None);
+        type_text(&mut popup, "(incident");
+
+        toggle_text_mode(&mut popup);
+        assert!(popup.title_err_msg.is_empty());
+
+        toggle_text_mode(&mut popup);
+        assert_eq!(popup.text_mode, Some(PatternMode::Regex));
+        assert!(!popup.title_err_msg.is_empty());
+        assert!(matches!(popup.confirm(), PopupReturn::KeepPopup));
+
+        popup.handle_input(&Input::new(KeyCode::Home, KeyModifiers::NONE));
+        popup.handle_input(&Input::new(KeyCode::Delete, KeyModifiers::NONE));
+        assert!(popup.title_err_msg.is_empty());
+
+        let PopupReturn::Apply(Some(filter)) = popup.confirm() else {
+            panic!("Filter must be applied");
+        };
+        assert_eq!(
+            filter.criteria,
+            vec![FilterCriterion::TitlePattern(
+                TextPattern::new(String::from("incident"), PatternMode::Regex).unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn loads_text_mode_from_filter() {
+        let pattern = TextPattern::new(String::from("rust"), PatternMode::WholeWord).unwrap();
+        let filter = Filter {
+            criteria: vec![FilterCriterion::ContentPattern(pattern)],
+            ..Default::default()
+        };
+
+        let popup = FilterPopup::new(Vec::new(), Some(filter));
+
+        assert_eq!(popup.text_mode, Some(PatternMode::WholeWord));
+        assert_eq!(popup.content_txt.lines()[0], "rust");
+    }
+}
@@ -0,0 +1,545 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::app::{
+    filter::{
+        CriteriaRelation, Filter, FilterCriterion, FilterPreset,
+        criterion::TagFilterOption,
+        date_range::format_date_filter,
+        pattern::{PatternMode, TextPattern},
+    },
+    keymap::Input,
+};
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect};
+
+type FilterPresetsReturn = PopupReturn<FilterPresetsAction>;
+
+const LIST_FOOTER_TEXT: &str = "Enter or <Ctrl-m>: Apply preset | <s>: Save current filter as preset | <r>: Rename preset | <d>: Delete preset | <j/k> or <Up/Down>: Select next/previous | Esc, <q> or <Ctrl-c>: Close";
+const NAME_FOOTER_TEXT: &str = "Enter or <Ctrl-m>: Confirm | Esc or <Ctrl-c>: Cancel";
+const FOOTER_MARGIN: usize = 8;
+
+pub struct FilterPresetsPopup<'a> {
+    presets: Vec<FilterPreset>,
+    current_filter: Option<Filter>,
+    list_state: ListState,
+    name_input: Option<NameInput<'a>>,
+}
+
+/// Text box to enter the name of a new preset or to rename an existing one.
+struct NameInput<'a> {
+    target: NameTarget,
+    name_txt: TextArea<'a>,
+    err_msg: String,
+    /// Saving the new preset replaces the existing one with the same name.
+    replaces_preset: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NameTarget {
+    NewPreset,
+    Rename(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterPresetsAction {
+    Apply(usize),
+    Save { name: String, filter: Filter },
+    Rename { index: usize, name: String },
+    Delete(usize),
+}
+
+impl FilterPresetsPopup<'_> {
+    pub fn new(presets: Vec<FilterPreset>, current_filter: Option<Filter>) -> Self {
+        let mut popup = Self {
+            presets: Vec::new(),
+            current_filter,
+            list_state: ListState::default(),
+            name_input: None,
+        };
+
+        popup.set_presets(presets);
+
+        popup
+    }
+
+    /// Replaces the shown presets keeping the selection in the bounds of the new list.
+    pub fn set_presets(&mut self, presets: Vec<FilterPreset>) {
+        self.presets = presets;
+        let selected = match (self.list_state.selected(), self.presets.len()) {
+            (_, 0) => None,
+            (Some(index), len) => Some(index.min(len - 1)),
+            (None, _) => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(70, 60, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Filter Presets");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_text = if self.name_input.is_some() {
+            NAME_FOOTER_TEXT
+        } else {
+            LIST_FOOTER_TEXT
+        };
+
+        let footer_height = textwrap::fill(footer_text, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let name_height = if self.name_input.is_some() { 3 } else { 0 };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(4)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Length(name_height),
+                    Constraint::Min(4),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        if let Some(name_input) = self.name_input.as_mut() {
+            name_input.render(frame, chunks[0], styles);
+        }
+
+        if self.presets.is_empty() {
+            self.render_place_holder(frame, chunks[1]);
+        } else {
+            self.render_list(frame, chunks[1], styles);
+        }
+
+        self.render_footer(footer_text, frame, chunks[2]);
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let items: Vec<ListItem> = self
+            .presets
+            .iter()
+            .enumerate()
+            .map(|(index, preset)| {
+                ListItem::new(format!(
+                    "{}. {}: {}",
+                    index + 1,
+                    preset.name,
+                    describe_filter(&preset.filter)
+                ))
+            })
+            .collect();
+
+        let highlight_style = if self.name_input.is_some() {
+            styles.general.list_highlight_inactive
+        } else {
+            styles.general.list_highlight_active
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title("Presets"),
+            )
+            .highlight_style(highlight_style)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_place_holder(&self, frame: &mut Frame, area: Rect) {
+        let place_holder = Paragraph::new(
+            "\nNo saved presets.\nApply a filter then press <s> here to save it as a preset",
+        )
+        .wrap(Wrap { trim: false })
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title("Presets"),
+        );
+
+        frame.render_widget(place_holder, area);
+    }
+
+    fn render_footer(&self, footer_text: &str, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(footer_text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> FilterPresetsReturn {
+        if self.name_input.is_some() {
+            return self.handle_name_input(input);
+        }
+
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc | KeyCode::Char('q') => PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => PopupReturn::Cancel,
+            KeyCode::Enter => self.apply_selected(),
+            KeyCode::Char('m') if has_control => self.apply_selected(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.cycle_next();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.cycle_prev();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('s') => {
+                if self.current_filter.is_some() {
+                    self.start_name_input(NameTarget::NewPreset, String::new());
+                }
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('r') => {
+                if let Some(index) = self.list_state.selected() {
+                    let name = self.presets[index].name.to_owned();
+                    self.start_name_input(NameTarget::Rename(index), name);
+                }
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('d') | KeyCode::Delete => match self.list_state.selected() {
+                Some(index) => PopupReturn::Apply(FilterPresetsAction::Delete(index)),
+                None => PopupReturn::KeepPopup,
+            },
+            _ => PopupReturn::KeepPopup,
+        }
+    }
+
+    fn handle_name_input(&mut self, input: &Input) -> FilterPresetsReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc => self.name_input = None,
+            KeyCode::Char('c') if has_control => self.name_input = None,
+            KeyCode::Enter => return self.confirm_name(),
+            KeyCode::Char('m') if has_control => return self.confirm_name(),
+            _ => {
+                let name_input = self.name_input.as_mut().expect("Name input is active");
+                if name_input.name_txt.input(KeyEvent::from(input)) {
+                    self.validate_name();
+                }
+            }
+        }
+
+        PopupReturn::KeepPopup
+    }
+
+    fn start_name_input(&mut self, target: NameTarget, name: String) {
+        let mut name_txt = TextArea::new(vec![name]);
+        name_txt.move_cursor(CursorMove::End);
+
+        self.name_input = Some(NameInput {
+            target,
+            name_txt,
+            err_msg: String::new(),
+            replaces_preset: false,
+        });
+    }
+
+    fn validate_name(&mut self) {
+        let Some(name_input) = self.name_input.as_mut() else {
+            return;
+        };
+
+        let name = name_input.name_txt.lines()[0].trim();
+        let name_exists = |skipped_index: Option<usize>| {
+            self.presets
+                .iter()
+                .enumerate()
+                .any(|(index, preset)| Some(index) != skipped_index && preset.name == name)
+        };
+
+        // New presets replace the ones with the same name while renaming can't merge presets.
+        let (is_duplicate, replaces_preset) = match name_input.target {
+            NameTarget::NewPreset => (false, name_exists(None)),
+            NameTarget::Rename(index) => (name_exists(Some(index)), false),
+        };
+
+        name_input.replaces_preset = replaces_preset;
+        name_input.err_msg = if name.is_empty() {
+            String::from("Name can't be empty")
+        } else if is_duplicate {
+            String::from("A preset with the same name exists already")
+        } else {
+            String::new()
+        };
+    }
+
+    fn confirm_name(&mut self) -> FilterPresetsReturn {
+        self.validate_name();
+
+        let name_input = self.name_input.as_ref().expect("Name input is active");
+        if !name_input.err_msg.is_empty() {
+            return PopupReturn::KeepPopup;
+        }
+
+        let name = name_input.name_txt.lines()[0].trim().to_owned();
+        let action = match name_input.target {
+            NameTarget::NewPreset => FilterPresetsAction::Save {
+                name,
+                filter: self
+                    .current_filter
+                    .clone()
+                    .expect("Saving presets is possible with a current filter only"),
+            },
+            NameTarget::Rename(index) => FilterPresetsAction::Rename { index, name },
+        };
+
+        self.name_input = None;
+
+        PopupReturn::Apply(action)
+    }
+
+    fn apply_selected(&self) -> FilterPresetsReturn {
+        match self.list_state.selected() {
+            Some(index) => PopupReturn::Apply(FilterPresetsAction::Apply(index)),
+            None => PopupReturn::KeepPopup,
+        }
+    }
+
+    fn cycle_next(&mut self) {
+        if self.presets.is_empty() {
+            return;
+        }
+
+        let last_index = self.presets.len() - 1;
+        let new_index = self
+            .list_state
+            .selected()
+            .map(|idx| if idx >= last_index { 0 } else { idx + 1 })
+            .unwrap_or(0);
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn cycle_prev(&mut self) {
+        if self.presets.is_empty() {
+            return;
+        }
+
+        let last_index = self.presets.len() - 1;
+        let new_index = self
+            .list_state
+            .selected()
+            .map(|idx| idx.checked_sub(1).unwrap_or(last_index))
+            .unwrap_or(last_index);
+
+        self.list_state.select(Some(new_index));
+    }
+}
+
+impl NameInput<'_> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let gstyles = &styles.general;
+        let title = match self.target {
+            NameTarget::NewPreset if self.replaces_preset => {
+                "Name of the new preset - Replaces the preset with the same name"
+            }
+            NameTarget::NewPreset => "Name of the new preset",
+            NameTarget::Rename(_) => "New name of the preset",
+        };
+
+        let (block, cursor_style) = if self.err_msg.is_empty() {
+            (
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(gstyles.input_block_active),
+                Style::from(gstyles.input_cursor_active),
+            )
+        } else {
+            (
+                Block::default()
+                    .title(format!("Name : {}", self.err_msg))
+                    .borders(Borders::ALL)
+                    .style(gstyles.input_block_invalid),
+                Style::from(gstyles.input_cursor_invalid),
+            )
+        };
+
+        self.name_txt.set_cursor_style(cursor_style);
+        self.name_txt.set_cursor_line_style(Style::default());
+        self.name_txt.set_block(block);
+
+        frame.render_widget(&self.name_txt, area);
+    }
+}
+
+/// Creates a short text describing the criteria of the given filter.
+fn describe_filter(filter: &Filter) -> String {
+    let separator = match filter.relation {
+        CriteriaRelation::And => " AND ",
+        CriteriaRelation::Or => " OR ",
+    };
+
+    let criteria = filter
+        .criteria
+        .iter()
+        .map(|cr| match cr {
+            FilterCriterion::Tag(TagFilterOption::Tag(tag)) => format!("tag:\"{tag}\""),
+            FilterCriterion::Tag(TagFilterOption::NoTags) => String::from("no tags"),
+            FilterCriterion::Title(title) => format!("title:\"{title}\""),
+            FilterCriterion::Content(content) => format!("content:\"{content}\""),
+            FilterCriterion::TitlePattern(pattern) => describe_pattern("title", pattern),
+            FilterCriterion::ContentPattern(pattern) => describe_pattern("content", pattern),
+            FilterCriterion::Priority(prio) => format!("priority:{prio}"),
+            FilterCriterion::PriorityComparison { op, value } => {
+                format!("priority{op}{value}")
+            }
+            FilterCriterion::DateRange { from, to } => {
+                format!("date:{}", format_date_filter(*from, *to))
+            }
+            FilterCriterion::RelativeDate(range) => format!("date:{range}"),
+        })
+        .collect::<Vec<_>>()
+        .join(separator);
+
+    match (criteria.is_empty(), filter.query.as_ref()) {
+        (_, None) => criteria,
+        (true, Some(query)) => query.to_string(),
+        (false, Some(query)) => format!("({criteria}) AND ({query})"),
+    }
+}
+
+fn describe_pattern(field: &str, pattern: &TextPattern) -> String {
+    match pattern.mode() {
+        PatternMode::WholeWord => format!("{field}:\"{}\" (whole word)", pattern.pattern()),
+        PatternMode::Regex => format!("{field}:/{}/", pattern.pattern()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(name: &str) -> FilterPreset {
+        FilterPreset::new(
+            name.to_owned(),
+            Filter {
+                criteria: vec![FilterCriterion::Tag(TagFilterOption::Tag(name.to_owned()))],
+                ..Default::default()
+            },
+        )
+    }
+
+    fn key(code: KeyCode) -> Input {
+        Input::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(popup: &mut FilterPresetsPopup, text: &str) {
+        for ch in text.chars() {
+            popup.handle_input(&key(KeyCode::Char(ch)));
+        }
+    }
+
+    #[test]
+    fn rename_rejects_duplicate_names() {
+        let mut popup = FilterPresetsPopup::new(vec![preset("work"), preset("home")], None);
+
+        popup.handle_input(&key(KeyCode::Char('r')));
+        // Clear the current name with backspace.
+        for _ in 0.."work".len() {
+            popup.handle_input(&key(KeyCode::Backspace));
+        }
+        type_text(&mut popup, "home");
+
+        assert!(matches!(
+            popup.handle_input(&key(KeyCode::Enter)),
+            PopupReturn::KeepPopup
+        ));
+
+        popup.handle_input(&key(KeyCode::Char('2')));
+        assert!(matches!(
+            popup.handle_input(&key(KeyCode::Enter)),
+            PopupReturn::Apply(FilterPresetsAction::Rename { index: 0, name }) if name == "home2"
+        ));
+    }
+
+    #[test]
+    fn save_needs_current_filter() {
+        let mut popup = FilterPresetsPopup::new(Vec::new(), None);
+        popup.handle_input(&key(KeyCode::Char('s')));
+        assert!(popup.name_input.is_none());
+
+        let filter = preset("work").filter;
+        let mut popup = FilterPresetsPopup::new(Vec::new(), Some(filter.clone()));
+        popup.handle_input(&key(KeyCode::Char('s')));
+        type_text(&mut popup, "Work");
+
+        assert!(matches!(
+            popup.handle_input(&key(KeyCode::Enter)),
+            PopupReturn::Apply(FilterPresetsAction::Save { name, filter: saved })
+                if name == "Work" && saved == filter
+        ));
+    }
+
+    #[test]
+    fn save_replaces_preset_with_same_name() {
+        let filter = preset("home").filter;
+        let mut popup = FilterPresetsPopup::new(vec![preset("work")], Some(filter.clone()));
+        popup.handle_input(&key(KeyCode::Char('s')));
+        type_text(&mut popup, "work");
+
+        assert!(popup.name_input.as_ref().unwrap().replaces_preset);
+        assert!(matches!(
+            popup.handle_input(&key(KeyCode::Enter)),
+            PopupReturn::Apply(FilterPresetsAction::Save { name, filter: saved })
+                if name == "work" && saved == filter
+        ));
+    }
+
+    #[test]
+    fn set_presets_keeps_selection_in_bounds() {
+        let mut popup = FilterPresetsPopup::new(vec![preset("a"), preset("b")], None);
+        popup.handle_input(&key(KeyCode::Char('j')));
+        assert_eq!(popup.list_state.selected(), Some(1));
+
+        popup.set_presets(vec![preset("a")]);
+        assert_eq!(popup.list_state.selected(), Some(0));
+
+        popup.set_presets(Vec::new());
+        assert_eq!(popup.list_state.selected(), None);
+    }
+
+    #[test]
+    fn describe_filter_with_query() {
+        let filter = Filter {
+            relation: CriteriaRelation::Or,
+            criteria: vec![
+                FilterCriterion::Tag(TagFilterOption::Tag(String::from("work"))),
+                FilterCriterion::Priority(1),
+            ],
+            query: Some("NOT tag:done".parse().unwrap()),
+        };
+
+        assert_eq!(
+            describe_filter(&filter),
+            r#"(tag:"work" OR priority:1) AND (NOT tag:done)"#
+        );
+    }
+}
@@ -41,10 +41,10 @@ pub fn render_footer<D: DataProvider>(
 }
 
 fn get_footer_text<D: DataProvider>(ui_components: &UIComponents, app: &App<D>) -> String {
-    let (editor_mode, multi_select_mode) = (
-        ui_components.editor.is_insert_mode(),
-        ui_components.entries_list.multi_select_mode,
-    );
+    let editor_mode = ui_components.editor.is_insert_mode()
+        || (ui_components.editor.is_standard_mode()
+            && ui_components.active_control == ControlType::EntryContentTxt);
+    let multi_select_mode = ui_components.entries_list.multi_select_mode;
     match (editor_mode, multi_select_mode) {
         (true, false) => get_editor_mode_text(ui_components),
         (false, true) => get_multi_select_text(ui_components),
@@ -59,8 +59,14 @@ fn get_editor_mode_text(ui_components: &UIComponents) -> String {
         .filter(|keymap| keymap.command == UICommand::BackEditorNormalMode)
         .collect();
 
+    let editing_hint = if ui_components.editor.is_standard_mode() {
+        "Edit using standard keybindings"
+    } else {
+        "Edit using Emacs motions"
+    };
+
     format!(
-        "{}{} Edit using Emacs motions",
+        "{}{} {editing_hint}",
         get_keymap_text(exit_editor_mode_keymap),
         SEPARATOR
     )
@@ -121,6 +127,15 @@ fn get_standard_text<D: DataProvider>(ui_components: &UIComponents, app: &App<D>
         footer_parts.push(get_keymap_text(full_screen_keymap));
     }
 
+    if !ui_components.can_focus_editor(app) {
+        let content_pane_keymap: Vec<_> = ui_components
+            .entries_list_keymaps
+            .iter()
+            .filter(|keymap| keymap.command == UICommand::CycleContentPaneMode)
+            .collect();
+        footer_parts.push(get_keymap_text(content_pane_keymap));
+    }
+
     let help_keymap: Vec<_> = ui_components
         .global_keymaps
         .iter()
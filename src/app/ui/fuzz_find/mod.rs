@@ -1,5 +1,6 @@
-use std::{cmp::Reverse, collections::HashMap};
+use std::{cmp::Reverse, fmt::Display};
 
+use backend::Entry;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 use ratatui::{
@@ -15,12 +16,15 @@ use crate::app::keymap::Input;
 
 use super::{Styles, ui_functions::centered_rect};
 
-const FOOTER_TEXT: &str = "Esc, Enter, <Ctrl-m>, <Ctrl-c>: Close | Up, Down, <Ctrl-n>, <Ctrl-p>: cycle through filtered list";
+const FOOTER_TEXT: &str = "Esc, Enter, <Ctrl-m>, <Ctrl-c>: Close | Up, Down, <Ctrl-n>, <Ctrl-p>: cycle through filtered list | <Ctrl-t>: Cycle search mode";
 const FOOTER_MARGINE: usize = 8;
+/// Format of the dates to search in, containing the names of week days and months too.
+const DATE_FORMAT: &str = "%d-%m-%Y %A %B";
 
 pub struct FuzzFindPopup<'a> {
     query_text_box: TextArea<'a>,
-    entries: HashMap<u32, String>,
+    entries: Vec<FuzzFindEntry>,
+    mode: FuzzFindMode,
     search_query: Option<String>,
     filtered_entries: Vec<FilteredEntry>,
     list_state: ListState,
@@ -32,28 +36,91 @@ pub enum FuzzFindReturn {
     SelectEntry(Option<u32>),
 }
 
-struct FilteredEntry {
+/// The fields of the entries to search in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzFindMode {
+    All,
+    Title,
+    Content,
+    Tags,
+    Date,
+}
+
+impl FuzzFindMode {
+    fn next(self) -> Self {
+        match self {
+            FuzzFindMode::All => FuzzFindMode::Title,
+            FuzzFindMode::Title => FuzzFindMode::Content,
+            FuzzFindMode::Content => FuzzFindMode::Tags,
+            FuzzFindMode::Tags => FuzzFindMode::Date,
+            FuzzFindMode::Date => FuzzFindMode::All,
+        }
+    }
+
+    fn includes(self, mode: FuzzFindMode) -> bool {
+        self == FuzzFindMode::All || self == mode
+    }
+}
+
+impl Display for FuzzFindMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FuzzFindMode::All => write!(f, "All"),
+            FuzzFindMode::Title => write!(f, "Title"),
+            FuzzFindMode::Content => write!(f, "Content"),
+            FuzzFindMode::Tags => write!(f, "Tags"),
+            FuzzFindMode::Date => write!(f, "Date"),
+        }
+    }
+}
+
+/// The searchable texts of an entry.
+pub struct FuzzFindEntry {
     id: u32,
-    score: i64,
-    indices: Vec<usize>,
+    title: String,
+    lines: Vec<String>,
+    tags: String,
+    date: String,
 }
 
-impl FilteredEntry {
-    fn new(id: u32, score: i64, indices: Vec<usize>) -> Self {
-        Self { id, score, indices }
+impl From<&Entry> for FuzzFindEntry {
+    fn from(entry: &Entry) -> Self {
+        Self {
+            id: entry.id,
+            title: entry.title.to_owned(),
+            lines: entry.content.lines().map(String::from).collect(),
+            tags: entry.tags.join(", "),
+            date: entry.date.format(DATE_FORMAT).to_string(),
+        }
     }
 }
 
+/// Char indices of the matches in the fields of an entry.
+#[derive(Debug, Default)]
+struct FilteredEntry {
+    index: usize,
+    score: i64,
+    title_indices: Vec<usize>,
+    tags_indices: Vec<usize>,
+    date_indices: Vec<usize>,
+    content_matches: Vec<LineMatch>,
+}
+
+#[derive(Debug)]
+struct LineMatch {
+    row: usize,
+    indices: Vec<usize>,
+}
+
 impl FuzzFindPopup<'_> {
-    pub fn new(entries: HashMap<u32, String>) -> Self {
+    pub fn new(entries: Vec<FuzzFindEntry>) -> Self {
         let mut query_text_box = TextArea::default();
-        let block = Block::default().title("Search Query").borders(Borders::ALL);
         query_text_box.set_cursor_line_style(Style::default());
-        query_text_box.set_block(block);
 
         Self {
             query_text_box,
             entries,
+            mode: FuzzFindMode::All,
             search_query: None,
             filtered_entries: Vec::new(),
             list_state: ListState::default(),
@@ -62,7 +129,7 @@ impl FuzzFindPopup<'_> {
     }
 
     pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
-        let area = centered_rect(60, 60, area);
+        let area = centered_rect(80, 70, area);
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -90,41 +157,52 @@ impl FuzzFindPopup<'_> {
             )
             .split(area);
 
+        let block = Block::default()
+            .title(format!("Search Query - Mode: {}", self.mode))
+            .borders(Borders::ALL);
+        self.query_text_box.set_block(block);
         frame.render_widget(&self.query_text_box, chunks[0]);
 
-        self.render_entries_list(frame, chunks[1], styles);
+        let results_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        self.render_entries_list(frame, results_chunks[0], styles);
+
+        self.render_preview(frame, results_chunks[1], styles);
 
         self.render_footer(frame, chunks[2]);
     }
 
     fn render_entries_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let date_style: Style = styles.journals_list.date_priority.into();
+
         let items: Vec<ListItem> = self
             .filtered_entries
             .iter()
-            .map(|entry| {
-                let entry_title = self
-                    .entries
-                    .get(&entry.id)
-                    .expect("Entry must be in entries map");
-
-                let spans: Vec<_> = entry_title
-                    .chars()
-                    .enumerate()
-                    .map(|(idx, ch)| {
-                        Span::styled(
-                            ch.to_string(),
-                            if entry.indices.contains(&idx) {
-                                Style::default()
-                                    .add_modifier(Modifier::BOLD)
-                                    .fg(Color::LightBlue)
-                            } else {
-                                Style::default()
-                            },
-                        )
-                    })
-                    .collect();
-
-                ListItem::new(Line::from(spans))
+            .map(|filtered| {
+                let entry = &self.entries[filtered.index];
+
+                let mut lines = vec![Line::from(highlight_indices(
+                    &entry.title,
+                    &filtered.title_indices,
+                    Style::default(),
+                ))];
+
+                let mut details =
+                    highlight_indices(&entry.date, &filtered.date_indices, date_style);
+                if !entry.tags.is_empty() {
+                    details.push(Span::styled(" | ", date_style));
+                    details.extend(highlight_indices(
+                        &entry.tags,
+                        &filtered.tags_indices,
+                        date_style,
+                    ));
+                }
+                lines.push(Line::from(details));
+
+                ListItem::new(lines)
             })
             .collect();
 
@@ -140,6 +218,53 @@ impl FuzzFindPopup<'_> {
         frame.render_stateful_widget(list, area, &mut self.list_state);
     }
 
+    /// Renders the matching lines of the selected entry content, or its whole content if the
+    /// matches aren't in the content.
+    fn render_preview(&self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let block = Block::default().title("Preview").borders(Borders::ALL);
+
+        let Some(filtered) = self
+            .list_state
+            .selected()
+            .and_then(|idx| self.filtered_entries.get(idx))
+        else {
+            frame.render_widget(block, area);
+            return;
+        };
+
+        let entry = &self.entries[filtered.index];
+
+        let lines: Vec<Line> = if filtered.content_matches.is_empty() {
+            entry
+                .lines
+                .iter()
+                .map(|line| Line::from(line.as_str()))
+                .collect()
+        } else {
+            let row_style: Style = styles.journals_list.date_priority.into();
+            filtered
+                .content_matches
+                .iter()
+                .map(|line_match| {
+                    let mut spans =
+                        vec![Span::styled(format!("{}: ", line_match.row + 1), row_style)];
+                    spans.extend(highlight_indices(
+                        &entry.lines[line_match.row],
+                        &line_match.indices,
+                        Style::default(),
+                    ));
+                    Line::from(spans)
+                })
+                .collect()
+        };
+
+        let preview = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(preview, area);
+    }
+
     fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
         let footer = Paragraph::new(FOOTER_TEXT)
             .alignment(Alignment::Center)
@@ -163,6 +288,10 @@ impl FuzzFindPopup<'_> {
             KeyCode::Char('p') if has_control => self.cycle_prev_entry(),
             KeyCode::Down => self.cycle_next_entry(),
             KeyCode::Char('n') if has_control => self.cycle_next_entry(),
+            KeyCode::Char('t') if has_control => {
+                self.mode = self.mode.next();
+                self.update_search_query();
+            }
             _ => {
                 if self.query_text_box.input(KeyEvent::from(input)) {
                     self.update_search_query();
@@ -171,10 +300,11 @@ impl FuzzFindPopup<'_> {
         }
 
         let selected_id = self.list_state.selected().map(|idx| {
-            self.filtered_entries
+            let filtered = self
+                .filtered_entries
                 .get(idx)
-                .expect("Index must be in the list boundaries")
-                .id
+                .expect("Index must be in the list boundaries");
+            self.entries[filtered.index].id
         });
 
         FuzzFindReturn::SelectEntry(selected_id)
@@ -224,11 +354,8 @@ impl FuzzFindPopup<'_> {
             self.filtered_entries = self
                 .entries
                 .iter()
-                .filter_map(|entry| {
-                    self.matcher
-                        .fuzzy_indices(entry.1, query)
-                        .map(|(score, indices)| FilteredEntry::new(*entry.0, score, indices))
-                })
+                .enumerate()
+                .filter_map(|(index, entry)| self.match_entry(index, entry, query))
                 .collect();
 
             self.filtered_entries
@@ -242,4 +369,181 @@ impl FuzzFindPopup<'_> {
             self.list_state.select(Some(0));
         }
     }
+
+    /// Matches the fields of the entry included in the current mode, scoring it with the best
+    /// match among them.
+    fn match_entry(
+        &self,
+        index: usize,
+        entry: &FuzzFindEntry,
+        query: &str,
+    ) -> Option<FilteredEntry> {
+        let mut filtered = FilteredEntry {
+            index,
+            ..Default::default()
+        };
+        let mut best_score = None;
+
+        let mut match_field = |mode: FuzzFindMode, text: &str| -> Vec<usize> {
+            if !self.mode.includes(mode) {
+                return Vec::new();
+            }
+            match self.matcher.fuzzy_indices(text, query) {
+                Some((score, indices)) => {
+                    best_score = best_score.max(Some(score));
+                    indices
+                }
+                None => Vec::new(),
+            }
+        };
+
+        filtered.title_indices = match_field(FuzzFindMode::Title, &entry.title);
+        filtered.tags_indices = match_field(FuzzFindMode::Tags, &entry.tags);
+        filtered.date_indices = match_field(FuzzFindMode::Date, &entry.date);
+
+        if self.mode.includes(FuzzFindMode::Content) {
+            for (row, line) in entry.lines.iter().enumerate() {
+                if let Some((score, indices)) = self.matcher.fuzzy_indices(line, query) {
+                    best_score = best_score.max(Some(score));
+                    filtered.content_matches.push(LineMatch { row, indices });
+                }
+            }
+        }
+
+        best_score.map(|score| {
+            filtered.score = score;
+            filtered
+        })
+    }
+}
+
+/// Splits the text into spans highlighting the chars with the given indices.
+fn highlight_indices<'a>(text: &'a str, indices: &[usize], style: Style) -> Vec<Span<'a>> {
+    let highlight_style = style.add_modifier(Modifier::BOLD).fg(Color::LightBlue);
+
+    let mut spans = Vec::new();
+    let mut span_start = 0;
+    let mut span_highlighted = false;
+
+    for (char_idx, (byte_idx, _)) in text.char_indices().enumerate() {
+        let highlighted = indices.contains(&char_idx);
+        if highlighted != span_highlighted {
+            if byte_idx > span_start {
+                let span_style = if span_highlighted {
+                    highlight_style
+                } else {
+                    style
+                };
+                spans.push(Span::styled(&text[span_start..byte_idx], span_style));
+            }
+            span_start = byte_idx;
+            span_highlighted = highlighted;
+        }
+    }
+
+    if span_start < text.len() {
+        let span_style = if span_highlighted {
+            highlight_style
+        } else {
+            style
+        };
+        spans.push(Span::styled(&text[span_start..], span_style));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn create_popup() -> FuzzFindPopup<'static> {
+        let entries = [
+            Entry::new(
+                1,
+                Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap(),
+                String::from("Groceries"),
+                String::from("Buy milk\nCall the bakery"),
+                vec![String::from("home")],
+                None,
+            ),
+            Entry::new(
+                2,
+                Utc.with_ymd_and_hms(2024, 7, 12, 0, 0, 0).unwrap(),
+                String::from("Team meeting"),
+                String::from("Discuss the release"),
+                vec![String::from("work"), String::from("meetings")],
+                None,
+            ),
+        ];
+
+        FuzzFindPopup::new(entries.iter().map(FuzzFindEntry::from).collect())
+    }
+
+    fn set_query(popup: &mut FuzzFindPopup, query: &str) {
+        popup.query_text_box = TextArea::new(vec![query.to_owned()]);
+        popup.update_search_query();
+    }
+
+    fn filtered_ids(popup: &FuzzFindPopup) -> Vec<u32> {
+        popup
+            .filtered_entries
+            .iter()
+            .map(|filtered| popup.entries[filtered.index].id)
+            .collect()
+    }
+
+    #[test]
+    fn matches_all_fields() {
+        let mut popup = create_popup();
+
+        set_query(&mut popup, "bakery");
+        assert_eq!(filtered_ids(&popup), vec![1]);
+        let filtered = &popup.filtered_entries[0];
+        assert_eq!(filtered.content_matches.len(), 1);
+        assert_eq!(filtered.content_matches[0].row, 1);
+
+        set_query(&mut popup, "work");
+        assert_eq!(filtered_ids(&popup), vec![2]);
+        assert!(!popup.filtered_entries[0].tags_indices.is_empty());
+
+        set_query(&mut popup, "july");
+        assert_eq!(filtered_ids(&popup), vec![2]);
+        assert!(!popup.filtered_entries[0].date_indices.is_empty());
+    }
+
+    #[test]
+    fn mode_limits_matched_fields() {
+        let mut popup = create_popup();
+        let ctrl_t = Input::new(KeyCode::Char('t'), KeyModifiers::CONTROL);
+
+        set_query(&mut popup, "milk");
+        assert_eq!(filtered_ids(&popup), vec![1]);
+
+        // Title mode
+        popup.handle_input(&ctrl_t);
+        assert_eq!(popup.mode, FuzzFindMode::Title);
+        assert!(filtered_ids(&popup).is_empty());
+
+        // Content mode
+        popup.handle_input(&ctrl_t);
+        assert_eq!(filtered_ids(&popup), vec![1]);
+
+        set_query(&mut popup, "home");
+        assert!(filtered_ids(&popup).is_empty());
+
+        // Tags mode
+        popup.handle_input(&ctrl_t);
+        assert_eq!(filtered_ids(&popup), vec![1]);
+    }
+
+    #[test]
+    fn highlight_multi_byte_text() {
+        let spans = highlight_indices("ünï code", &[1, 2, 4], Style::default());
+        let texts: Vec<_> = spans.iter().map(|span| span.content.as_ref()).collect();
+
+        assert_eq!(texts, vec!["ü", "nï", " ", "c", "ode"]);
+    }
 }
@@ -12,7 +12,7 @@ use ratatui::{
     },
 };
 
-use crate::app::keymap::Input;
+use crate::{app::keymap::Input, settings::EditorProfile};
 
 use self::{
     global_bindings::GlobalBindings, keybindings_table::KeybindingsTable,
@@ -32,17 +32,59 @@ const MARGIN: u16 = 8;
 
 const TAB_LETTER_HIGHLIGHT_COLOR: Color = Color::LightGreen;
 
-const EDITOR_HINT_TEXT: &str = r"The editor has three modes:
+const EDITOR_HINT_TEXT: &str = r#"The editor has three modes:
  - Normal-Mode: In this mode Vim keybindings are used to navigate the text and to enter edit mode via (i, I, a , A, o, O).
  - Edit-Mode: In this mode Emacs keybindings are used to edit and navigate the text.
  - Visual-Mode: Like the visual mode in Vim to select, delete and yank text with extra vim keybindings (d, y, c).
 
+ Vim Commands in Normal-Mode:
+ - Counts before motions and commands: 5j, 3x, 2p
+ - Operators with motions and text objects: d, c, y with w, e, b, 0, ^, $, gg, G, {, } or iw, aw, ip, ap, i(, a[, i{, i", ...
+ - Doubled operators for lines: dd, cc, yy
+ - Named registers with "a to "z (upper case appends) and "+ for the system clipboard: "ayy, "ap
+ - .: Repeat the last change
+ - q{a-z} ... q: Record a macro, @{a-z}: Play it, @@: Play the last one again
+ Leave the editor to quit with <q> since it records macros in Normal-Mode.
+
+ Search and Replace in Normal-Mode:
+ - / or ?: Search forward / backward with smart-case regex and highlight the matches
+ - n / N: Go to the next / previous match
+ - :s/foo/bar/g: Replace in the current line (:%s for all lines, flags: g for all matches, i to ignore case)
+ - :noh: Clear the highlights
+
+ The profile can be switched to standard keybindings without modes in the settings with `editor_profile`.
+
+ Markdown syntax is highlighted in the editor. Press <m> in the journals list to toggle rendering it in the read-only preview.
+
+ Misspelled words are underlined when spell checking is enabled in the settings with `spell_check`. Ctrl-g jumps to the next one, Ctrl-o shows suggestions for the word under the cursor and Alt-g adds it to the personal dictionary.
+
  Basic Emacs Keybindings:
  - Ctrl-f / Ctrl-b: Move forward / backward one character
  - Alt-f / Alt-b: Move forward / backward one word
  - Ctrl-n / Ctrl-p: Move to the next / previous line
  - Ctrl-d / Ctrl-h: Delete the next / previous character
  - Alt-d / Alt-Backspace: Delete the next / previous word
+"#;
+
+const STANDARD_EDITOR_HINT_TEXT: &str = r"The editor uses the standard profile, editing the text directly without modes:
+ - Shift with arrows, Home and End: Select text
+ - Ctrl-Left / Ctrl-Right: Move backward / forward one word
+ - Ctrl-a / Ctrl-e: Move to the start / end of the line
+ - Ctrl-z: Undo
+ - Ctrl-y or Ctrl-Shift-z: Redo
+ - Esc: Leave the editor
+
+ The profile can be switched to vim in the settings with `editor_profile`.
+
+ Markdown syntax is highlighted in the editor. Press <m> in the journals list to toggle rendering it in the read-only preview.
+
+ Misspelled words are underlined when spell checking is enabled in the settings with `spell_check`. Ctrl-g jumps to the next one, Ctrl-o shows suggestions for the word under the cursor and Alt-g adds it to the personal dictionary.
+
+ Emacs Keybindings:
+ - Ctrl-f / Ctrl-b: Move forward / backward one character
+ - Ctrl-n / Ctrl-p: Move to the next / previous line
+ - Ctrl-d / Ctrl-h: Delete the next / previous character
+ - Alt-d / Alt-Backspace: Delete the next / previous word
 ";
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -102,16 +144,22 @@ pub enum HelpInputInputReturn {
 #[derive(Debug)]
 pub struct HelpPopup {
     selected_tab: KeybindingsTabs,
+    editor_hint: &'static str,
     global_bindings: GlobalBindings,
     multi_select_bindings: MultiSelectBindings,
 }
 
 impl HelpPopup {
-    pub fn new(selected_tab: KeybindingsTabs) -> Self {
+    pub fn new(selected_tab: KeybindingsTabs, editor_profile: EditorProfile) -> Self {
         let global_bindings = GlobalBindings::new();
         let multi_select_bindings = MultiSelectBindings::new();
+        let editor_hint = match editor_profile {
+            EditorProfile::Vim => EDITOR_HINT_TEXT,
+            EditorProfile::Standard => STANDARD_EDITOR_HINT_TEXT,
+        };
         Self {
             selected_tab,
+            editor_hint,
             global_bindings,
             multi_select_bindings,
         }
@@ -143,7 +191,7 @@ impl HelpPopup {
             KeybindingsTabs::Global => {
                 render_keybindings(frame, chunks[1], &mut self.global_bindings)
             }
-            KeybindingsTabs::Editor => render_editor_hint(frame, chunks[1]),
+            KeybindingsTabs::Editor => render_editor_hint(frame, chunks[1], self.editor_hint),
             KeybindingsTabs::MultiSelect => {
                 render_keybindings(frame, chunks[1], &mut self.multi_select_bindings)
             }
@@ -292,8 +340,8 @@ fn render_scrollbar(frame: &mut Frame, area: Rect, pos: usize, items_count: usiz
     frame.render_stateful_widget(scrollbar, scroll_area, &mut state);
 }
 
-pub fn render_editor_hint(frame: &mut Frame, area: Rect) {
-    let paragraph = Paragraph::new(EDITOR_HINT_TEXT)
+pub fn render_editor_hint(frame: &mut Frame, area: Rect, hint_text: &str) {
+    let paragraph = Paragraph::new(hint_text)
         .block(
             Block::default()
                 .title("Editor Keybindings")
@@ -1,29 +1,39 @@
-use std::path::PathBuf;
+use std::collections::VecDeque;
 
 use backend::DataProvider;
 pub use themes::Styles;
 
 use self::{
+    bulk_edit_popup::BulkEditPopup,
+    calendar_popup::{CalendarAction, CalendarPopup},
+    drafts_popup::{DraftsAction, DraftsPopup, RecoverableDraft},
     editor::{Editor, EditorMode},
     entries_list::EntriesList,
     entry_popup::{EntryPopup, EntryPopupInputReturn},
-    export_popup::ExportPopup,
+    export_popup::{ExportPopup, ExportRequest},
     filter_popup::FilterPopup,
+    filter_presets_popup::{FilterPresetsAction, FilterPresetsPopup},
     footer::{get_footer_height, render_footer},
-    fuzz_find::FuzzFindPopup,
+    fuzz_find::{FuzzFindEntry, FuzzFindPopup},
     help_popup::{HelpInputInputReturn, HelpPopup},
     msg_box::{MsgBox, MsgBoxActions, MsgBoxType},
+    search_popup::{SearchEntry, SearchMatch, SearchPopup},
     sort_popup::SortPopup,
+    spelling_popup::SpellingPopup,
+    statistics_popup::StatisticsPopup,
 };
 
 use super::{
     App,
+    filter::{CriteriaRelation, Filter, FilterCriterion},
     keymap::{
         Input, Keymap, get_editor_mode_keymaps, get_entries_list_keymaps, get_global_keymaps,
         get_multi_select_keymaps,
     },
     runner::HandleInputReturnType,
+    state::ContentPaneMode,
 };
+use crate::settings::{EditorProfile, layout::PanesOrientation};
 use anyhow::Result;
 
 use ratatui::{
@@ -31,23 +41,33 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
 };
 
+mod bulk_edit_popup;
+mod calendar_popup;
 mod commands;
+mod drafts_popup;
 mod editor;
 mod entries_list;
 mod entry_popup;
 mod export_popup;
 mod filter_popup;
+mod filter_presets_popup;
 mod footer;
 mod fuzz_find;
 mod help_popup;
 mod msg_box;
+mod search_popup;
 mod sort_popup;
+mod spelling_popup;
+mod statistics_popup;
 pub mod themes;
 pub mod ui_functions;
 
 pub use commands::UICommand;
 pub use msg_box::MsgBoxResult;
 
+/// Limits the inputs played from macros and repeated changes to stop macros calling themselves.
+const MAX_REPLAYED_INPUTS: usize = 100_000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ControlType {
     EntriesList,
@@ -62,6 +82,13 @@ pub enum Popup<'a> {
     Filter(Box<FilterPopup<'a>>),
     FuzzFind(Box<FuzzFindPopup<'a>>),
     Sort(Box<SortPopup>),
+    Calendar(Box<CalendarPopup>),
+    FilterPresets(Box<FilterPresetsPopup<'a>>),
+    Search(Box<SearchPopup<'a>>),
+    Spelling(Box<SpellingPopup>),
+    Statistics(Box<StatisticsPopup>),
+    Drafts(Box<DraftsPopup>),
+    BulkEdit(Box<BulkEditPopup<'a>>),
 }
 
 #[derive(Debug, Clone)]
@@ -85,13 +112,14 @@ pub struct UIComponents<'a> {
 }
 
 impl UIComponents<'_> {
-    pub fn new(styles: Styles) -> Self {
+    pub fn new(styles: Styles, editor_profile: EditorProfile) -> Self {
         let global_keymaps = get_global_keymaps();
         let entries_list_keymaps = get_entries_list_keymaps();
         let editor_keymaps = get_editor_mode_keymaps();
         let multi_select_keymaps = get_multi_select_keymaps();
         let mut entries_list = EntriesList::new();
-        let editor = Editor::new();
+        let mut editor = Editor::new();
+        editor.set_profile(editor_profile);
 
         let active_control = ControlType::EntriesList;
         entries_list.set_active(true);
@@ -148,13 +176,37 @@ impl UIComponents<'_> {
                     );
                 }
                 ControlType::EntryContentTxt => {
-                    self.editor.render_widget(f, chunks[0], &self.styles);
+                    self.editor.render_widget(
+                        f,
+                        chunks[0],
+                        &self.styles,
+                        app.spell_checker.as_ref(),
+                    );
                 }
             }
+        } else if app.get_content_pane_mode() == ContentPaneMode::Hidden {
+            self.entries_list.render_widget(
+                f,
+                chunks[0],
+                app,
+                &self.entries_list_keymaps,
+                &self.styles,
+            );
         } else {
+            let direction = match app.get_panes_orientation() {
+                PanesOrientation::Horizontal => Direction::Horizontal,
+                PanesOrientation::Vertical => Direction::Vertical,
+            };
+            let list_percentage = app.get_entries_list_percentage();
             let entries_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+                .direction(direction)
+                .constraints(
+                    [
+                        Constraint::Percentage(list_percentage),
+                        Constraint::Percentage(100 - list_percentage),
+                    ]
+                    .as_ref(),
+                )
                 .split(chunks[0]);
             self.entries_list.render_widget(
                 f,
@@ -163,8 +215,21 @@ impl UIComponents<'_> {
                 &self.entries_list_keymaps,
                 &self.styles,
             );
-            self.editor
-                .render_widget(f, entries_chunks[1], &self.styles);
+            match app.get_content_pane_mode() {
+                ContentPaneMode::Editor => self.editor.render_widget(
+                    f,
+                    entries_chunks[1],
+                    &self.styles,
+                    app.spell_checker.as_ref(),
+                ),
+                ContentPaneMode::Preview => self.editor.render_preview(
+                    f,
+                    entries_chunks[1],
+                    &self.styles,
+                    app.is_markdown_rendered(),
+                ),
+                ContentPaneMode::Hidden => unreachable!("Hidden content pane is handled above"),
+            }
         }
 
         self.render_popup(f);
@@ -184,6 +249,27 @@ impl UIComponents<'_> {
                 }
                 Popup::FuzzFind(fuzz_find) => fuzz_find.render_widget(f, f.area(), &self.styles),
                 Popup::Sort(sort_popup) => sort_popup.render_widget(f, f.area(), &self.styles),
+                Popup::FilterPresets(presets_popup) => {
+                    presets_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::Search(search_popup) => {
+                    search_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::Calendar(calendar_popup) => {
+                    calendar_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::Spelling(spelling_popup) => {
+                    spelling_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::Statistics(statistics_popup) => {
+                    statistics_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::Drafts(drafts_popup) => {
+                    drafts_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::BulkEdit(bulk_edit_popup) => {
+                    bulk_edit_popup.render_widget(f, f.area(), &self.styles)
+                }
             }
         }
     }
@@ -192,13 +278,52 @@ impl UIComponents<'_> {
         &mut self,
         input: &Input,
         app: &mut App<D>,
+    ) -> Result<HandleInputReturnType> {
+        self.editor.record_macro_input(input);
+
+        let mut result = self.handle_single_input(input, app).await?;
+
+        // Play the inputs of the editor macros and repeated changes as if they were typed.
+        let mut replay: VecDeque<Input> = self.editor.take_replay_inputs().into();
+        let mut replayed_count = 0;
+        while let Some(input) = replay.pop_front() {
+            if matches!(result, HandleInputReturnType::ExitApp) {
+                break;
+            }
+
+            replayed_count += 1;
+            if replayed_count > MAX_REPLAYED_INPUTS {
+                self.editor.set_status_msg(format!(
+                    "Replaying stopped after {MAX_REPLAYED_INPUTS} inputs"
+                ));
+                break;
+            }
+
+            result = self.handle_single_input(&input, app).await?;
+
+            // Nested macros are played before the rest of the current one.
+            for (idx, input) in self.editor.take_replay_inputs().into_iter().enumerate() {
+                replay.insert(idx, input);
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn handle_single_input<D: DataProvider>(
+        &mut self,
+        input: &Input,
+        app: &mut App<D>,
     ) -> Result<HandleInputReturnType> {
         if self.has_popup() {
             return self.handle_popup_input(input, app).await;
         }
 
         if self.editor.is_prioritized() {
-            if let Some(key) = self.editor_keymaps.iter().find(|c| &c.key == input) {
+            // The command line handles all the keys itself, including cancelling with Esc.
+            if !self.editor.is_command_mode()
+                && let Some(key) = self.editor_keymaps.iter().find(|c| &c.key == input)
+            {
                 let command_result = key.command.clone().execute(self, app).await?;
                 if matches!(command_result, HandleInputReturnType::Handled) {
                     return Ok(command_result);
@@ -217,6 +342,12 @@ impl UIComponents<'_> {
             return Ok(HandleInputReturnType::Handled);
         }
 
+        if self.active_control == ControlType::EntryContentTxt
+            && self.editor.handles_before_keymaps(input)
+        {
+            return self.editor.handle_input(input, app);
+        }
+
         if let Some(cmd) = self
             .global_keymaps
             .iter()
@@ -290,8 +421,8 @@ impl UIComponents<'_> {
                             PopupReturn::Cancel => {
                                 self.popup_stack.pop().expect("popup stack isn't empty");
                             }
-                            PopupReturn::Apply((path, entry_id)) => {
-                                self.handle_export_popup_return(path, entry_id, app).await;
+                            PopupReturn::Apply(request) => {
+                                self.handle_export_popup_return(request, app).await;
                             }
                         };
                     }
@@ -338,6 +469,82 @@ impl UIComponents<'_> {
                             self.set_current_entry(current_entry_id, app);
                         }
                     },
+                    Popup::Calendar(calendar_popup) => match calendar_popup.handle_input(input) {
+                        PopupReturn::KeepPopup => {}
+                        PopupReturn::Cancel => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        PopupReturn::Apply(action) => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                            self.handle_calendar_action(action, app);
+                        }
+                    },
+                    Popup::FilterPresets(presets_popup) => {
+                        match presets_popup.handle_input(input) {
+                            PopupReturn::KeepPopup => {}
+                            PopupReturn::Cancel => {
+                                self.popup_stack.pop().expect("popup stack isn't empty");
+                            }
+                            PopupReturn::Apply(action) => self.handle_presets_action(action, app),
+                        }
+                    }
+                    Popup::Search(search_popup) => match search_popup.handle_input(input) {
+                        PopupReturn::KeepPopup => {}
+                        PopupReturn::Cancel => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        PopupReturn::Apply(search_match) => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                            self.jump_to_search_match(search_match, app);
+                        }
+                    },
+                    Popup::Spelling(spelling_popup) => match spelling_popup.handle_input(input) {
+                        PopupReturn::KeepPopup => {}
+                        PopupReturn::Cancel => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        PopupReturn::Apply(replacement) => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                            self.editor.replace_text(
+                                replacement.row,
+                                replacement.range,
+                                &replacement.text,
+                                app,
+                            );
+                        }
+                    },
+                    Popup::Statistics(statistics_popup) => {
+                        match statistics_popup.handle_input(input) {
+                            PopupReturn::KeepPopup => {}
+                            PopupReturn::Cancel | PopupReturn::Apply(()) => {
+                                self.popup_stack.pop().expect("popup stack isn't empty");
+                            }
+                        }
+                    }
+                    Popup::Drafts(drafts_popup) => match drafts_popup.handle_input(input) {
+                        PopupReturn::KeepPopup => {}
+                        PopupReturn::Cancel => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        PopupReturn::Apply(action) => {
+                            if drafts_popup.is_empty() {
+                                self.popup_stack.pop().expect("popup stack isn't empty");
+                            }
+                            self.handle_drafts_action(action, app)?;
+                        }
+                    },
+                    Popup::BulkEdit(bulk_edit_popup) => match bulk_edit_popup.handle_input(input) {
+                        PopupReturn::KeepPopup => {}
+                        PopupReturn::Cancel => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        PopupReturn::Apply(edit) => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                            let changed_count = app.apply_bulk_edit(&edit).await?;
+                            self.select_first_if_current_filtered(app);
+                            self.editor.set_status_msg(edit.describe(changed_count));
+                        }
+                    },
                 }
                 Ok(HandleInputReturnType::Handled)
             }
@@ -345,20 +552,169 @@ impl UIComponents<'_> {
         }
     }
 
+    /// Opens the popup to create a new journal with the title filled in if provided.
+    pub fn show_new_entry_popup<D: DataProvider>(&mut self, app: &App<D>, title: Option<String>) {
+        let mut popup = EntryPopup::new_entry(&app.settings);
+        if let Some(title) = title {
+            popup = popup.with_title(&title);
+        }
+
+        self.popup_stack.push(Popup::Entry(Box::new(popup)));
+    }
+
+    /// Offers to recover the drafts left from previous sessions if there are any.
+    pub fn show_leftover_drafts<D: DataProvider>(&mut self, app: &mut App<D>) {
+        let drafts: Vec<_> = app
+            .load_drafts(self)
+            .into_iter()
+            .filter_map(|draft| {
+                let entry = app
+                    .entries
+                    .iter()
+                    .find(|entry| entry.id == draft.entry_id)?;
+                Some(RecoverableDraft {
+                    title: entry.title.to_owned(),
+                    saved_content: entry.content.to_owned(),
+                    draft,
+                })
+            })
+            .collect();
+
+        if !drafts.is_empty() {
+            self.popup_stack
+                .push(Popup::Drafts(Box::new(DraftsPopup::new(drafts))));
+        }
+    }
+
+    fn handle_drafts_action<D: DataProvider>(
+        &mut self,
+        action: DraftsAction,
+        app: &mut App<D>,
+    ) -> Result<()> {
+        match action {
+            DraftsAction::Recover(draft) => {
+                // Recovered drafts replace the popup to edit them right away.
+                self.popup_stack
+                    .retain(|popup| !matches!(popup, Popup::Drafts(_)));
+
+                if app.get_entry(draft.entry_id).is_none() {
+                    anyhow::bail!(
+                        "Journal of the draft is hidden by the current filter. Remove the filter and restart the app to recover it"
+                    );
+                }
+
+                self.set_current_entry(Some(draft.entry_id), app);
+                self.editor.set_entry_content(&draft.content, app);
+                self.editor
+                    .set_status_msg(String::from("Draft recovered. Save to keep the changes"));
+            }
+            DraftsAction::Discard(entry_id) => app.remove_draft(entry_id)?,
+        }
+
+        Ok(())
+    }
+
+    fn handle_calendar_action<D: DataProvider>(
+        &mut self,
+        action: CalendarAction,
+        app: &mut App<D>,
+    ) {
+        match action {
+            CalendarAction::FilterDate(date) => {
+                let filter = Filter {
+                    relation: CriteriaRelation::And,
+                    criteria: vec![FilterCriterion::DateRange {
+                        from: Some(date),
+                        to: Some(date),
+                    }],
+                    query: None,
+                };
+                app.apply_filter(Some(filter));
+                self.select_first_if_current_filtered(app);
+            }
+            CalendarAction::JumpToDate(date) => {
+                let Some(entry_id) = app.get_closest_entry_id(date) else {
+                    return;
+                };
+
+                // Reset the filter if the closest journal is filtered out.
+                if app.filtered_out_entries.contains(&entry_id) {
+                    app.apply_filter(None);
+                }
+
+                self.set_current_entry(Some(entry_id), app);
+            }
+        }
+    }
+
+    fn handle_presets_action<D: DataProvider>(
+        &mut self,
+        action: FilterPresetsAction,
+        app: &mut App<D>,
+    ) {
+        match action {
+            FilterPresetsAction::Apply(index) => {
+                self.popup_stack.pop().expect("popup stack isn't empty");
+                app.apply_filter_preset(index);
+                self.select_first_if_current_filtered(app);
+                return;
+            }
+            FilterPresetsAction::Save { name, filter } => app.save_filter_preset(name, filter),
+            FilterPresetsAction::Rename { index, name } => app.rename_filter_preset(index, name),
+            FilterPresetsAction::Delete(index) => app.delete_filter_preset(index),
+        }
+
+        if let Some(Popup::FilterPresets(presets_popup)) = self.popup_stack.last_mut() {
+            presets_popup.set_presets(app.get_filter_presets().to_vec());
+        }
+    }
+
+    /// Opens the entry of the match in the editor with the cursor on the matched text.
+    fn jump_to_search_match<D: DataProvider>(
+        &mut self,
+        search_match: SearchMatch,
+        app: &mut App<D>,
+    ) {
+        self.set_current_entry(Some(search_match.entry_id), app);
+
+        if !self.can_focus_editor(app) {
+            app.set_content_pane_mode(ContentPaneMode::Editor);
+        }
+        self.change_active_control(ControlType::EntryContentTxt);
+
+        self.editor
+            .jump_to_match(search_match.row, search_match.col, search_match.search_text);
+    }
+
+    /// Selects the first active entry if the current one is filtered out.
+    pub fn select_first_if_current_filtered<D: DataProvider>(&mut self, app: &mut App<D>) {
+        if app.get_current_entry().is_none() {
+            let first_id = app.get_active_entries().next().map(|entry| entry.id);
+            self.set_current_entry(first_id, app);
+        }
+    }
+
     async fn handle_export_popup_return<D: DataProvider>(
         &mut self,
-        path: PathBuf,
-        entry_id: Option<u32>,
+        request: ExportRequest,
         app: &mut App<D>,
     ) {
+        let ExportRequest {
+            path,
+            entry_id,
+            format,
+        } = request;
+
         let (result, confirmation_msg) = if self.entries_list.multi_select_mode {
-            let result = app.export_entries(path.clone()).await;
+            let result = app.export_entries(path.clone(), format).await;
             let msg = format!("Journal(s)  exported to file {}", path.display());
 
             (result, msg)
         } else {
             let entry_id = entry_id.expect("entry id must have a value in normal mode");
-            let result = app.export_entry_content(entry_id, path.clone()).await;
+            let result = app
+                .export_entry_content(entry_id, path.clone(), format)
+                .await;
             let msg = format!("Journal content exported to file {}", path.display());
 
             (result, msg)
@@ -396,11 +752,24 @@ impl UIComponents<'_> {
         self.set_control_is_active(control, true);
     }
 
-    fn start_edit_current_entry(&mut self) -> Result<HandleInputReturnType> {
+    /// Checks if the editor can get the focus, which is only the case when it's visible.
+    pub fn can_focus_editor<D: DataProvider>(&self, app: &App<D>) -> bool {
+        app.get_content_pane_mode() == ContentPaneMode::Editor
+    }
+
+    fn start_edit_current_entry<D: DataProvider>(
+        &mut self,
+        app: &mut App<D>,
+    ) -> Result<HandleInputReturnType> {
         if self.entries_list.state.selected().is_none() {
             return Ok(HandleInputReturnType::Handled);
         }
 
+        // Editing the content brings the editor back if it's hidden or in preview mode.
+        if !self.can_focus_editor(app) {
+            app.set_content_pane_mode(ContentPaneMode::Editor);
+        }
+
         self.change_active_control(ControlType::EntryContentTxt);
 
         assert!(!self.editor.is_insert_mode());
@@ -435,10 +804,37 @@ impl UIComponents<'_> {
         self.editor.has_unsaved()
     }
 
+    /// Persists the draft of the unsaved changes in the editor if drafts are enabled.
+    pub fn sync_draft<D: DataProvider>(&self, app: &mut App<D>) -> Result<()> {
+        let unsaved_content = self.has_unsaved().then(|| self.editor.get_content());
+        app.sync_draft(unsaved_content.as_deref())
+    }
+
+    /// Saves the unsaved changes in the editor to the back-end unless a popup is open. Returns
+    /// whether the changes are saved.
+    pub async fn auto_save<D: DataProvider>(&mut self, app: &mut App<D>) -> Result<bool> {
+        if !self.has_unsaved() || self.has_popup() || app.current_entry_id.is_none() {
+            return Ok(false);
+        }
+
+        app.update_current_entry_content(self.editor.get_content())
+            .await?;
+        self.editor.refresh_has_unsaved(app);
+        self.sync_draft(app)?;
+        self.editor
+            .set_status_msg(String::from("Changes saved automatically"));
+
+        Ok(true)
+    }
+
     pub fn show_err_msg(&mut self, err_txt: String) {
         self.show_msg_box(MsgBoxType::Error(err_txt), MsgBoxActions::Ok, None);
     }
 
+    pub fn show_info_msg(&mut self, info_txt: String) {
+        self.show_msg_box(MsgBoxType::Info(info_txt), MsgBoxActions::Ok, None);
+    }
+
     pub fn update_current_entry<D: DataProvider>(&mut self, app: &mut App<D>) {
         if app.get_current_entry().is_none() {
             let first_entry = app.get_active_entries().next().map(|entry| entry.id);
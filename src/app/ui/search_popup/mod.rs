@@ -0,0 +1,376 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use regex::Regex;
+use tui_textarea::TextArea;
+
+use crate::app::keymap::Input;
+
+use super::{PopupReturn, Styles, editor::build_search_regex, ui_functions::centered_rect};
+
+type SearchReturn = PopupReturn<SearchMatch>;
+
+const FOOTER_TEXT: &str = "Enter or <Ctrl-m>: Jump to match | Up, Down, <Ctrl-n>, <Ctrl-p>: Cycle through matches | Esc or <Ctrl-c>: Close";
+const FOOTER_MARGIN: usize = 8;
+/// Limits the shown matches to keep the search responsive with big journals.
+const MAX_MATCHES: usize = 1000;
+
+/// Popup to search the content of all the active entries with a smart-case regex.
+pub struct SearchPopup<'a> {
+    query_txt: TextArea<'a>,
+    entries: Vec<SearchEntry>,
+    matches: Vec<ContentMatch>,
+    regex: Option<Regex>,
+    err_msg: String,
+    list_state: ListState,
+}
+
+pub struct SearchEntry {
+    id: u32,
+    title: String,
+    lines: Vec<String>,
+}
+
+impl SearchEntry {
+    pub fn new(id: u32, title: String, content: &str) -> Self {
+        Self {
+            id,
+            title,
+            lines: content.lines().map(String::from).collect(),
+        }
+    }
+}
+
+/// Match position inside the entries list, where the column is in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ContentMatch {
+    entry_index: usize,
+    row: usize,
+    col: usize,
+}
+
+/// The selected match with the search text to highlight it in the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub entry_id: u32,
+    pub row: usize,
+    pub col: usize,
+    pub search_text: String,
+}
+
+impl SearchPopup<'_> {
+    pub fn new(entries: Vec<SearchEntry>) -> Self {
+        let mut query_txt = TextArea::default();
+        query_txt.set_cursor_line_style(Style::default());
+
+        Self {
+            query_txt,
+            entries,
+            matches: Vec::new(),
+            regex: None,
+            err_msg: String::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(70, 70, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Search in Journals");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(2)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Length(3),
+                    Constraint::Min(4),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        self.render_query(frame, chunks[0], styles);
+        self.render_matches(frame, chunks[1], styles);
+        self.render_footer(frame, chunks[2]);
+    }
+
+    fn render_query(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let gstyles = &styles.general;
+        let block = if self.err_msg.is_empty() {
+            Block::default()
+                .title("Search Pattern")
+                .borders(Borders::ALL)
+        } else {
+            Block::default()
+                .title(format!("Search Pattern : {}", self.err_msg))
+                .borders(Borders::ALL)
+                .style(gstyles.input_block_invalid)
+        };
+
+        let cursor_style = if self.err_msg.is_empty() {
+            gstyles.input_cursor_active
+        } else {
+            gstyles.input_cursor_invalid
+        };
+
+        self.query_txt.set_block(block);
+        self.query_txt.set_cursor_style(cursor_style.into());
+
+        frame.render_widget(&self.query_txt, area);
+    }
+
+    fn render_matches(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let title_style = Style::default().add_modifier(Modifier::BOLD);
+        let match_style = styles.editor.search_match;
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|content_match| {
+                let entry = &self.entries[content_match.entry_index];
+                let line = &entry.lines[content_match.row];
+
+                let mut spans = vec![
+                    Span::styled(entry.title.as_str(), title_style),
+                    Span::raw(format!(":{}: ", content_match.row + 1)),
+                ];
+
+                let regex = self
+                    .regex
+                    .as_ref()
+                    .expect("Matches exist for valid regex only");
+                let mut last_end = 0;
+                for found in regex.find_iter(line).filter(|m| !m.is_empty()) {
+                    spans.push(Span::raw(&line[last_end..found.start()]));
+                    spans.push(Span::styled(found.as_str(), match_style));
+                    last_end = found.end();
+                }
+                spans.push(Span::raw(&line[last_end..]));
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let block_title = if self.matches.len() >= MAX_MATCHES {
+            format!("Matches: first {MAX_MATCHES}")
+        } else {
+            format!("Matches: {}", self.matches.len())
+        };
+
+        let list = List::new(items)
+            .block(Block::default().title(block_title).borders(Borders::ALL))
+            .highlight_style(styles.general.list_highlight_active)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> SearchReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc => return PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => return PopupReturn::Cancel,
+            KeyCode::Enter => return self.confirm(),
+            KeyCode::Char('m') if has_control => return self.confirm(),
+            KeyCode::Up => self.cycle_prev(),
+            KeyCode::Char('p') if has_control => self.cycle_prev(),
+            KeyCode::Down => self.cycle_next(),
+            KeyCode::Char('n') if has_control => self.cycle_next(),
+            _ => {
+                if self.query_txt.input(KeyEvent::from(input)) {
+                    self.update_matches();
+                }
+            }
+        }
+
+        PopupReturn::KeepPopup
+    }
+
+    fn confirm(&self) -> SearchReturn {
+        let Some(content_match) = self
+            .list_state
+            .selected()
+            .and_then(|index| self.matches.get(index))
+        else {
+            return PopupReturn::KeepPopup;
+        };
+
+        PopupReturn::Apply(SearchMatch {
+            entry_id: self.entries[content_match.entry_index].id,
+            row: content_match.row,
+            col: content_match.col,
+            search_text: self.query_txt.lines()[0].to_owned(),
+        })
+    }
+
+    fn cycle_next(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+
+        let new_index = self
+            .list_state
+            .selected()
+            .map_or(0, |idx| (idx + 1).min(self.matches.len() - 1));
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn cycle_prev(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+
+        let new_index = self
+            .list_state
+            .selected()
+            .map_or(0, |idx| idx.saturating_sub(1));
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn update_matches(&mut self) {
+        self.matches.clear();
+        self.err_msg.clear();
+        self.regex = None;
+
+        let query = &self.query_txt.lines()[0];
+        if !query.is_empty() {
+            match build_search_regex(query) {
+                Ok(regex) => {
+                    self.matches = find_matches(&self.entries, &regex);
+                    self.regex = Some(regex);
+                }
+                Err(err) => self.err_msg = err,
+            }
+        }
+
+        let selected = if self.matches.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.list_state.select(selected);
+    }
+}
+
+/// Finds the first match in each line of the entries up to the maximum count of shown matches.
+fn find_matches(entries: &[SearchEntry], regex: &Regex) -> Vec<ContentMatch> {
+    entries
+        .iter()
+        .enumerate()
+        .flat_map(|(entry_index, entry)| {
+            entry
+                .lines
+                .iter()
+                .enumerate()
+                .filter_map(move |(row, line)| {
+                    regex
+                        .find_iter(line)
+                        .find(|found| !found.is_empty())
+                        .map(|found| ContentMatch {
+                            entry_index,
+                            row,
+                            col: line[..found.start()].chars().count(),
+                        })
+                })
+        })
+        .take(MAX_MATCHES)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_text(popup: &mut SearchPopup, text: &str) {
+        for c in text.chars() {
+            popup.handle_input(&Input::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn matches_lines_in_all_entries() {
+        let entries = vec![
+            SearchEntry::new(1, String::from("First"), "Nothing here\nSome ünïcode todo"),
+            SearchEntry::new(2, String::from("Second"), "TODO: first line"),
+        ];
+        let mut popup = SearchPopup::new(entries);
+
+        input_text(&mut popup, "todo");
+
+        assert_eq!(
+            popup.matches,
+            vec![
+                ContentMatch {
+                    entry_index: 0,
+                    row: 1,
+                    col: 13,
+                },
+                ContentMatch {
+                    entry_index: 1,
+                    row: 0,
+                    col: 0,
+                },
+            ]
+        );
+
+        popup.handle_input(&Input::new(KeyCode::Down, KeyModifiers::NONE));
+        let result = popup.handle_input(&Input::new(KeyCode::Enter, KeyModifiers::NONE));
+        let PopupReturn::Apply(search_match) = result else {
+            panic!("Selected match must be applied");
+        };
+        assert_eq!(
+            search_match,
+            SearchMatch {
+                entry_id: 2,
+                row: 0,
+                col: 0,
+                search_text: String::from("todo"),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_shows_error() {
+        let entries = vec![SearchEntry::new(1, String::from("Title"), "(content")];
+        let mut popup = SearchPopup::new(entries);
+
+        input_text(&mut popup, "(");
+
+        assert!(popup.matches.is_empty());
+        assert!(!popup.err_msg.is_empty());
+        assert!(matches!(
+            popup.handle_input(&Input::new(KeyCode::Enter, KeyModifiers::NONE)),
+            PopupReturn::KeepPopup
+        ));
+    }
+}
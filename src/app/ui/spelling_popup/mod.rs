@@ -0,0 +1,241 @@
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::keymap::Input;
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect};
+
+type SpellingPopupReturn = PopupReturn<SpellingReplacement>;
+
+const FOOTER_TEXT: &str = "Enter or <Ctrl-m>: Replace word | <j/k> or <Up/Down>: Select next/previous | <1-9>: Replace with suggestion | Esc, <q> or <Ctrl-c>: Cancel";
+const FOOTER_MARGIN: usize = 8;
+
+/// Replacement of a misspelled word in the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellingReplacement {
+    pub row: usize,
+    /// Byte range of the word in its line.
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// Shows the suggestions for a misspelled word to replace it with.
+pub struct SpellingPopup {
+    word: String,
+    row: usize,
+    range: Range<usize>,
+    suggestions: Vec<String>,
+    list_state: ListState,
+}
+
+impl SpellingPopup {
+    pub fn new(word: String, row: usize, range: Range<usize>, suggestions: Vec<String>) -> Self {
+        let mut list_state = ListState::default();
+        if !suggestions.is_empty() {
+            list_state.select(Some(0));
+        }
+
+        Self {
+            word,
+            row,
+            range,
+            suggestions,
+            list_state,
+        }
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(50, 50, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(format!("Suggestions for '{}'", self.word));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(4)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Min(3),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        if self.suggestions.is_empty() {
+            self.render_place_holder(frame, chunks[0]);
+        } else {
+            self.render_list(frame, chunks[0], styles);
+        }
+
+        self.render_footer(frame, chunks[1]);
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let items: Vec<ListItem> = self
+            .suggestions
+            .iter()
+            .enumerate()
+            .map(|(index, suggestion)| ListItem::new(format!("{}. {suggestion}", index + 1)))
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(styles.general.list_highlight_active)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_place_holder(&self, frame: &mut Frame, area: Rect) {
+        let place_holder = Paragraph::new("\nNo suggestions found")
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            );
+
+        frame.render_widget(place_holder, area);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> SpellingPopupReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc | KeyCode::Char('q') => PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => PopupReturn::Cancel,
+            KeyCode::Enter => self.replace_with(self.list_state.selected()),
+            KeyCode::Char('m') if has_control => self.replace_with(self.list_state.selected()),
+            KeyCode::Char(ch @ '1'..='9') => {
+                let index = ch.to_digit(10).expect("Char is a digit") as usize - 1;
+                self.replace_with(Some(index))
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.cycle_next();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.cycle_prev();
+                PopupReturn::KeepPopup
+            }
+            _ => PopupReturn::KeepPopup,
+        }
+    }
+
+    fn replace_with(&self, index: Option<usize>) -> SpellingPopupReturn {
+        match index.and_then(|index| self.suggestions.get(index)) {
+            Some(suggestion) => PopupReturn::Apply(SpellingReplacement {
+                row: self.row,
+                range: self.range.clone(),
+                text: suggestion.to_owned(),
+            }),
+            None => PopupReturn::KeepPopup,
+        }
+    }
+
+    fn cycle_next(&mut self) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+
+        let last_index = self.suggestions.len() - 1;
+        let new_index = self
+            .list_state
+            .selected()
+            .map(|idx| if idx >= last_index { 0 } else { idx + 1 })
+            .unwrap_or(0);
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn cycle_prev(&mut self) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+
+        let last_index = self.suggestions.len() - 1;
+        let new_index = self
+            .list_state
+            .selected()
+            .map(|idx| idx.checked_sub(1).unwrap_or(last_index))
+            .unwrap_or(last_index);
+
+        self.list_state.select(Some(new_index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn popup() -> SpellingPopup {
+        SpellingPopup::new(
+            String::from("wrod"),
+            2,
+            4..8,
+            vec![String::from("word"), String::from("wood")],
+        )
+    }
+
+    #[test]
+    fn replace_with_selected_suggestion() {
+        let mut popup = popup();
+
+        popup.handle_input(&Input::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        let result = popup.handle_input(&Input::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        let PopupReturn::Apply(replacement) = result else {
+            panic!("Suggestion should be applied");
+        };
+        assert_eq!(
+            replacement,
+            SpellingReplacement {
+                row: 2,
+                range: 4..8,
+                text: String::from("wood"),
+            }
+        );
+    }
+
+    #[test]
+    fn replace_with_numbered_suggestion() {
+        let mut popup = popup();
+
+        let result = popup.handle_input(&Input::new(KeyCode::Char('1'), KeyModifiers::NONE));
+        assert!(matches!(result, PopupReturn::Apply(replacement) if replacement.text == "word"));
+
+        let result = popup.handle_input(&Input::new(KeyCode::Char('5'), KeyModifiers::NONE));
+        assert!(matches!(result, PopupReturn::KeepPopup));
+    }
+}
@@ -0,0 +1,472 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use backend::Entry;
+use chrono::{Datelike, Days, NaiveDate};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{
+        Bar, BarChart, BarGroup, Block, BorderType, Borders, Clear, Paragraph, Sparkline, Wrap,
+    },
+};
+
+use crate::app::keymap::Input;
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect};
+
+const FOOTER_TEXT: &str = "Esc, <q> or <Ctrl-c>: Close";
+const FOOTER_MARGIN: usize = 8;
+/// Count of the weeks shown in the journals per week chart.
+const WEEKS_COUNT: usize = 12;
+/// Count of the days shown in the words per day chart.
+const DAYS_COUNT: usize = 30;
+/// Maximum count of the most used tags shown in the tags chart.
+const MAX_TAGS: usize = 10;
+
+/// Writing statistics of a set of journals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Statistics {
+    entries_count: usize,
+    words_count: usize,
+    /// Count of journals in each of the last weeks from the oldest one, with the first day of
+    /// each week.
+    entries_per_week: Vec<(NaiveDate, u64)>,
+    /// Count of words written in each of the last days from the oldest one.
+    words_per_day: Vec<u64>,
+    /// Count of consecutive days with journals until today, or until yesterday if there is no
+    /// journal today yet.
+    current_streak: usize,
+    longest_streak: usize,
+    /// The most used tags with their counts in descending order.
+    tags: Vec<(String, u64)>,
+    /// Count of journals for each priority, with `None` for journals without priority.
+    priorities: BTreeMap<Option<u32>, u64>,
+}
+
+impl Statistics {
+    fn new<'a>(entries: impl Iterator<Item = &'a Entry>, today: NaiveDate) -> Self {
+        let mut entries_count = 0;
+        let mut words_count = 0;
+        let mut entries_per_date: HashMap<NaiveDate, u64> = HashMap::new();
+        let mut words_per_date: HashMap<NaiveDate, u64> = HashMap::new();
+        let mut tags: HashMap<&str, u64> = HashMap::new();
+        let mut priorities = BTreeMap::new();
+
+        for entry in entries {
+            let words = entry.content.split_whitespace().count();
+            entries_count += 1;
+            words_count += words;
+            let date = entry.date.date_naive();
+            *entries_per_date.entry(date).or_default() += 1;
+            *words_per_date.entry(date).or_default() += words as u64;
+            for tag in &entry.tags {
+                *tags.entry(tag.as_str()).or_default() += 1;
+            }
+            *priorities.entry(entry.priority).or_default() += 1;
+        }
+
+        let dates: BTreeSet<NaiveDate> = entries_per_date.keys().copied().collect();
+
+        let week_start = today - Days::new(today.weekday().num_days_from_monday().into());
+        let entries_per_week = (0..WEEKS_COUNT)
+            .rev()
+            .map(|weeks_ago| {
+                let start = week_start - Days::new(7 * weeks_ago as u64);
+                let end = start + Days::new(7);
+                let count = dates
+                    .range(start..end)
+                    .map(|date| entries_per_date[date])
+                    .sum();
+                (start, count)
+            })
+            .collect();
+
+        let words_per_day = (0..DAYS_COUNT)
+            .rev()
+            .map(|days_ago| {
+                let date = today - Days::new(days_ago as u64);
+                words_per_date.get(&date).copied().unwrap_or_default()
+            })
+            .collect();
+
+        let mut tags: Vec<(String, u64)> = tags
+            .into_iter()
+            .map(|(tag, count)| (tag.to_owned(), count))
+            .collect();
+        tags.sort_by(|(tag_a, count_a), (tag_b, count_b)| {
+            count_b.cmp(count_a).then_with(|| tag_a.cmp(tag_b))
+        });
+        tags.truncate(MAX_TAGS);
+
+        Self {
+            entries_count,
+            words_count,
+            entries_per_week,
+            words_per_day,
+            current_streak: current_streak(&dates, today),
+            longest_streak: longest_streak(&dates),
+            tags,
+            priorities,
+        }
+    }
+}
+
+fn current_streak(dates: &BTreeSet<NaiveDate>, today: NaiveDate) -> usize {
+    let yesterday = today - Days::new(1);
+    let Some(mut date) = [today, yesterday]
+        .into_iter()
+        .find(|date| dates.contains(date))
+    else {
+        return 0;
+    };
+
+    let mut streak = 0;
+    while dates.contains(&date) {
+        streak += 1;
+        date = date - Days::new(1);
+    }
+
+    streak
+}
+
+fn longest_streak(dates: &BTreeSet<NaiveDate>) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for date in dates {
+        current = match previous {
+            Some(prev) if prev.succ_opt() == Some(*date) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(*date);
+    }
+
+    longest
+}
+
+pub struct StatisticsPopup {
+    statistics: Statistics,
+}
+
+impl StatisticsPopup {
+    pub fn new<'a>(entries: impl Iterator<Item = &'a Entry>, today: NaiveDate) -> Self {
+        Self {
+            statistics: Statistics::new(entries, today),
+        }
+    }
+
+    pub fn render_widget(&self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(80, 85, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Statistics");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let summary = self.summary_text();
+        // The summary is inside the borders of its block.
+        let summary_width = (area.width as usize)
+            .saturating_sub(FOOTER_MARGIN + 2)
+            .max(1);
+        let summary_height = textwrap::fill(&summary, summary_width).lines().count() + 2;
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(4)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Length(summary_height.try_into().unwrap()),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(25),
+                    Constraint::Min(6),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        let distribution_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+            .split(chunks[3]);
+
+        let chart_style = Style::from(styles.general.chart);
+
+        Self::render_summary(frame, chunks[0], summary);
+        self.render_entries_per_week(frame, chunks[1], chart_style);
+        self.render_words_per_day(frame, chunks[2], chart_style);
+        self.render_tags(frame, distribution_chunks[0], chart_style);
+        self.render_priorities(frame, distribution_chunks[1], chart_style);
+        self.render_footer(frame, chunks[4]);
+    }
+
+    fn summary_text(&self) -> String {
+        let stats = &self.statistics;
+        let average_words = stats
+            .words_count
+            .checked_div(stats.entries_count)
+            .unwrap_or_default();
+        format!(
+            "Journals: {} | Words: {} | Average words: {average_words} | Current streak: {} | Longest streak: {}",
+            stats.entries_count,
+            stats.words_count,
+            days_text(stats.current_streak),
+            days_text(stats.longest_streak),
+        )
+    }
+
+    fn render_summary(frame: &mut Frame, area: Rect, summary: String) {
+        let paragraph = Paragraph::new(summary)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(chart_block("Summary"));
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_entries_per_week(&self, frame: &mut Frame, area: Rect, style: Style) {
+        let bars: Vec<Bar> = self
+            .statistics
+            .entries_per_week
+            .iter()
+            .map(|(week_start, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(week_start.format("%d.%m").to_string()))
+            })
+            .collect();
+
+        let chart = BarChart::default()
+            .block(chart_block(format!(
+                "Journals per week (last {WEEKS_COUNT} weeks)"
+            )))
+            .bar_width(bar_width(area, bars.len()))
+            .bar_gap(1)
+            .bar_style(style)
+            .data(BarGroup::default().bars(&bars));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn render_words_per_day(&self, frame: &mut Frame, area: Rect, style: Style) {
+        let words = &self.statistics.words_per_day;
+        let max = words.iter().max().copied().unwrap_or_default();
+        let total: u64 = words.iter().sum();
+
+        let sparkline = Sparkline::default()
+            .block(chart_block(format!(
+                "Words per day (last {DAYS_COUNT} days: {total} words, max: {max})"
+            )))
+            .style(style)
+            .data(words);
+
+        frame.render_widget(sparkline, area);
+    }
+
+    fn render_tags(&self, frame: &mut Frame, area: Rect, style: Style) {
+        let bars: Vec<Bar> = self
+            .statistics
+            .tags
+            .iter()
+            .map(|(tag, count)| Bar::default().value(*count).label(Line::from(tag.as_str())))
+            .collect();
+
+        let chart = BarChart::default()
+            .block(chart_block("Tags"))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(style)
+            .data(BarGroup::default().bars(&bars));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn render_priorities(&self, frame: &mut Frame, area: Rect, style: Style) {
+        let bars: Vec<Bar> = self
+            .statistics
+            .priorities
+            .iter()
+            .map(|(priority, count)| {
+                let label = match priority {
+                    Some(priority) => format!("P{priority}"),
+                    None => String::from("None"),
+                };
+                Bar::default().value(*count).label(Line::from(label))
+            })
+            .collect();
+
+        let chart = BarChart::default()
+            .block(chart_block("Priorities"))
+            .bar_width(bar_width(area, bars.len()).min(6))
+            .bar_gap(1)
+            .bar_style(style)
+            .data(BarGroup::default().bars(&bars));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&self, input: &Input) -> PopupReturn<()> {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc | KeyCode::Char('q') => PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => PopupReturn::Cancel,
+            _ => PopupReturn::KeepPopup,
+        }
+    }
+}
+
+fn chart_block<'a>(title: impl Into<Line<'a>>) -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(title)
+}
+
+/// Calculates the width of the bars to fill the area with the given count of bars having one
+/// cell gaps between them.
+fn bar_width(area: Rect, bars_count: usize) -> u16 {
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let width = inner_width.saturating_sub(bars_count) / bars_count.max(1);
+
+    width.max(1) as u16
+}
+
+fn days_text(days: usize) -> String {
+    match days {
+        1 => String::from("1 day"),
+        days => format!("{days} days"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn entry(
+        id: u32,
+        date: NaiveDate,
+        content: &str,
+        tags: &[&str],
+        priority: Option<u32>,
+    ) -> Entry {
+        Entry::new(
+            id,
+            Utc.from_utc_datetime(&date.and_hms_opt(10, 0, 0).unwrap()),
+            format!("Title {id}"),
+            content.to_owned(),
+            tags.iter().map(|tag| tag.to_string()).collect(),
+            priority,
+        )
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    #[test]
+    fn compute_statistics() {
+        // 2024-05-15 is a Wednesday
+        let today = date(15);
+        let entries = [
+            entry(1, date(1), "one two three", &["work"], Some(1)),
+            entry(2, date(2), "four", &["work", "home"], None),
+            entry(3, date(3), "five six", &[], Some(1)),
+            entry(4, date(13), "seven", &["home"], Some(2)),
+            entry(5, date(14), "eight nine", &["work"], None),
+            entry(6, date(14), "ten", &[], None),
+        ];
+
+        let stats = Statistics::new(entries.iter(), today);
+
+        assert_eq!(stats.entries_count, 6);
+        assert_eq!(stats.words_count, 10);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.longest_streak, 3);
+
+        assert_eq!(stats.entries_per_week.len(), WEEKS_COUNT);
+        assert_eq!(
+            stats.entries_per_week[WEEKS_COUNT - 1],
+            (date(13), 3),
+            "Current week starts on Monday"
+        );
+        assert_eq!(stats.entries_per_week[WEEKS_COUNT - 2], (date(6), 0));
+        assert_eq!(
+            stats.entries_per_week[WEEKS_COUNT - 3],
+            (NaiveDate::from_ymd_opt(2024, 4, 29).unwrap(), 3)
+        );
+
+        assert_eq!(stats.words_per_day.len(), DAYS_COUNT);
+        assert_eq!(stats.words_per_day[DAYS_COUNT - 1], 0);
+        assert_eq!(stats.words_per_day[DAYS_COUNT - 2], 3);
+        assert_eq!(stats.words_per_day[DAYS_COUNT - 3], 1);
+
+        assert_eq!(
+            stats.tags,
+            [(String::from("work"), 3), (String::from("home"), 2)]
+        );
+        assert_eq!(
+            stats.priorities,
+            BTreeMap::from([(None, 3), (Some(1), 2), (Some(2), 1)])
+        );
+    }
+
+    #[test]
+    fn streaks_without_recent_journals() {
+        let dates = BTreeSet::from([date(1), date(3), date(4)]);
+
+        assert_eq!(current_streak(&dates, date(10)), 0);
+        assert_eq!(current_streak(&dates, date(5)), 2);
+        assert_eq!(current_streak(&dates, date(4)), 2);
+        assert_eq!(longest_streak(&dates), 2);
+        assert_eq!(longest_streak(&BTreeSet::new()), 0);
+    }
+
+    #[test]
+    fn render_without_journals() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let popup = StatisticsPopup::new(std::iter::empty(), date(15));
+        let styles = Styles::default();
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+
+        terminal
+            .draw(|frame| popup.render_widget(frame, frame.area(), &styles))
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("Current streak: 0 days"));
+        assert!(content.contains("Longest streak: 0 days"));
+    }
+}
@@ -21,6 +21,27 @@ pub struct EditorStyles {
     pub cursor_visual: Style,
     #[serde(default = "selection_style")]
     pub selection_style: Style,
+    #[serde(default = "search_match")]
+    pub search_match: Style,
+    #[serde(default = "markdown_heading")]
+    pub markdown_heading: Style,
+    #[serde(default = "markdown_strong")]
+    pub markdown_strong: Style,
+    #[serde(default = "markdown_emphasis")]
+    pub markdown_emphasis: Style,
+    #[serde(default = "markdown_code")]
+    pub markdown_code: Style,
+    #[serde(default = "markdown_list_marker")]
+    pub markdown_list_marker: Style,
+    #[serde(default = "markdown_link")]
+    pub markdown_link: Style,
+    #[serde(default = "markdown_task")]
+    pub markdown_task: Style,
+    /// Name of the color scheme to highlight the code blocks with.
+    #[serde(default = "code_theme")]
+    pub code_theme: String,
+    #[serde(default = "spelling_error")]
+    pub spelling_error: Style,
 }
 
 impl Default for EditorStyles {
@@ -34,6 +55,16 @@ impl Default for EditorStyles {
             cursor_insert: cursor_insert(),
             cursor_visual: cursor_visual(),
             selection_style: selection_style(),
+            search_match: search_match(),
+            markdown_heading: markdown_heading(),
+            markdown_strong: markdown_strong(),
+            markdown_emphasis: markdown_emphasis(),
+            markdown_code: markdown_code(),
+            markdown_list_marker: markdown_list_marker(),
+            markdown_link: markdown_link(),
+            markdown_task: markdown_task(),
+            code_theme: code_theme(),
+            spelling_error: spelling_error(),
         }
     }
 }
@@ -108,3 +139,86 @@ fn selection_style() -> Style {
         ..Default::default()
     }
 }
+
+#[inline]
+fn search_match() -> Style {
+    Style {
+        fg: Some(Color::Black),
+        bg: Some(Color::Yellow),
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_heading() -> Style {
+    Style {
+        fg: Some(Color::LightCyan),
+        modifiers: Modifier::BOLD,
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_strong() -> Style {
+    Style {
+        modifiers: Modifier::BOLD,
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_emphasis() -> Style {
+    Style {
+        modifiers: Modifier::ITALIC,
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_code() -> Style {
+    Style {
+        fg: Some(Color::LightYellow),
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_list_marker() -> Style {
+    Style {
+        fg: Some(Color::LightBlue),
+        modifiers: Modifier::BOLD,
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_link() -> Style {
+    Style {
+        fg: Some(Color::LightMagenta),
+        modifiers: Modifier::UNDERLINED,
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn markdown_task() -> Style {
+    Style {
+        fg: Some(Color::LightGreen),
+        modifiers: Modifier::BOLD,
+        ..Default::default()
+    }
+}
+
+#[inline]
+fn code_theme() -> String {
+    String::from("base16-ocean.dark")
+}
+
+#[inline]
+fn spelling_error() -> Style {
+    Style {
+        modifiers: Modifier::UNDERLINED,
+        underline_color: Some(Color::LightRed),
+        ..Default::default()
+    }
+}
@@ -27,6 +27,10 @@ pub struct GeneralStyles {
     pub list_highlight_active: Style,
     #[serde(default = "list_highlight_inactive")]
     pub list_highlight_inactive: Style,
+
+    // Bars and lines of the statistics charts
+    #[serde(default = "chart")]
+    pub chart: Style,
 }
 
 impl Default for GeneralStyles {
@@ -39,6 +43,7 @@ impl Default for GeneralStyles {
             list_item_selected: list_item_selected(),
             list_highlight_active: list_highlight_active(),
             list_highlight_inactive: list_highlight_inactive(),
+            chart: chart(),
         }
     }
 }
@@ -100,3 +105,11 @@ fn list_highlight_inactive() -> Style {
         ..Default::default()
     }
 }
+
+#[inline]
+fn chart() -> Style {
+    Style {
+        fg: Some(Color::LightCyan),
+        ..Default::default()
+    }
+}
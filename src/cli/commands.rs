@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
 use anyhow::{Context, ensure};
-use clap::Subcommand;
+use chrono::NaiveDate;
+use clap::{ArgGroup, Args, Subcommand, ValueEnum};
 
 use crate::{app::ui::Styles, settings::Settings};
 
@@ -12,12 +13,32 @@ pub enum CliCommand {
     /// Print the current settings including the paths for the back-end files.
     #[clap(visible_alias = "pc")]
     PrintConfig,
-    /// Import journals from the given transfer JSON file to the current back-end file.
+    /// Import journals from the given transfer JSON file or the files of other journaling tools
+    /// to the current back-end file.
     #[clap(visible_alias = "imj")]
     ImportJournals {
-        /// Path of the JSON file to import from.
+        /// Path of the file to import from, or of the directory for Markdown files.
         #[arg(short = 'p', long = "path", required = true, value_name = "FILE PATH")]
         file_path: PathBuf,
+        /// Format of the imported journals.
+        #[arg(long = "format", value_enum, default_value_t = CliImportFormat::Json)]
+        format: CliImportFormat,
+        /// Maps a journal field to a CSV column, e.g. 'title=Subject'.
+        /// Fields: id, title, date, content, tags, priority.
+        #[arg(
+            verbatim_doc_comment,
+            long = "column",
+            value_name = "FIELD=COLUMN",
+            value_parser = parse_column_mapping
+        )]
+        columns: Vec<(String, String)>,
+        /// Updates the journals with the IDs in the CSV id column instead of adding them again.
+        /// Rows without ID are added as new journals. Attributes without a column are kept.
+        #[arg(long = "update")]
+        update: bool,
+        /// Prints the journals to import and the skipped records without importing them.
+        #[arg(long = "dry-run")]
+        dry_run: bool,
     },
     /// Assign priority for all the entries with empty priority field
     #[clap(visible_alias = "ap")]
@@ -26,6 +47,126 @@ pub enum CliCommand {
         #[arg(required = true, value_name = "PRIORITY", index = 1)]
         priority: u32,
     },
+    /// Export journals with a built-in format or a template from the templates directory.
+    #[clap(visible_alias = "ex")]
+    Export {
+        /// Name of the template to export with (`weekly` for the file `weekly.md.jinja`).
+        #[arg(
+            short = 't',
+            long = "template",
+            value_name = "NAME",
+            conflicts_with = "format"
+        )]
+        template: Option<String>,
+        /// Built-in format to export with. It's inferred from the output file extension if not set.
+        #[arg(long = "format", value_enum)]
+        format: Option<CliExportFormat>,
+        /// Path of the exported file. The export is printed to the standard output if not set.
+        #[arg(short = 'o', long = "output", value_name = "FILE PATH")]
+        output: Option<PathBuf>,
+        /// Exports only the journals matching the given filter query.
+        #[arg(
+            short = 'f',
+            long = "filter",
+            value_name = "QUERY",
+            value_parser = parse_filter_query
+        )]
+        filter: Option<FilterQuery>,
+    },
+    /// Add a journal without starting the TUI, printing its ID.
+    ///
+    /// The content is read from `--content`, the standard input if it's piped, or the external
+    /// editor otherwise.
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Title of the journal.
+        #[arg(short = 't', long = "title", required = true)]
+        title: String,
+        /// Tag of the journal. Can be used multiple times.
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Priority of the journal. Defaults to the configured default priority.
+        #[arg(short = 'p', long = "priority")]
+        priority: Option<u32>,
+        /// Date of the journal (DD-MM-YYYY or YYYY-MM-DD). Defaults to today.
+        #[arg(short = 'd', long = "date", value_parser = parse_date)]
+        date: Option<NaiveDate>,
+        /// Content of the journal.
+        #[arg(long = "content")]
+        content: Option<String>,
+    },
+    /// List the journals without starting the TUI.
+    #[clap(visible_alias = "ls")]
+    List {
+        #[command(flatten)]
+        args: ListArgs,
+    },
+    /// Print the journal with the given ID including its content.
+    Show {
+        /// ID of the journal.
+        #[arg(required = true, value_name = "ID", index = 1)]
+        id: u32,
+        /// Prints the journal as JSON.
+        #[arg(long = "json")]
+        json: bool,
+    },
+    /// List the journals matching the given filter query.
+    Search {
+        /// Filter query. Free text is searched in the titles and the content of the journals.
+        /// Example: 'deploy AND tag:work' or 'title:standup AND date>=2024-01-01'
+        #[arg(
+            verbatim_doc_comment,
+            required = true,
+            value_name = "QUERY",
+            index = 1,
+            value_parser = parse_filter_query
+        )]
+        query: FilterQuery,
+        #[command(flatten)]
+        args: ListArgs,
+    },
+    /// Edit the content of the journal with the given ID in the external editor.
+    Edit {
+        /// ID of the journal.
+        #[arg(required = true, value_name = "ID", index = 1)]
+        id: u32,
+    },
+    /// Change the attributes of the journal with the given ID.
+    #[command(group(
+        ArgGroup::new("changes")
+            .required(true)
+            .multiple(true)
+            .args(["title", "tags_add", "tags_remove", "priority", "clear_priority"])
+    ))]
+    Set {
+        /// ID of the journal.
+        #[arg(required = true, value_name = "ID", index = 1)]
+        id: u32,
+        /// New title of the journal.
+        #[arg(short = 't', long = "title")]
+        title: Option<String>,
+        /// Tag to add to the journal. Can be used multiple times.
+        #[arg(long = "tag-add", value_name = "TAG")]
+        tags_add: Vec<String>,
+        /// Tag to remove from the journal. Can be used multiple times.
+        #[arg(long = "tag-remove", value_name = "TAG")]
+        tags_remove: Vec<String>,
+        /// New priority of the journal.
+        #[arg(short = 'p', long = "priority", conflicts_with = "clear_priority")]
+        priority: Option<u32>,
+        /// Removes the priority of the journal.
+        #[arg(long = "clear-priority")]
+        clear_priority: bool,
+    },
+    /// Delete the journals with the given IDs.
+    Rm {
+        /// IDs of the journals.
+        #[arg(required = true, value_name = "ID", num_args = 1..)]
+        ids: Vec<u32>,
+        /// Deletes the journals without asking for confirmation.
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+    },
     /// Provides commands regarding changing themes and styles of the app.
     #[clap(visible_alias = "style")]
     #[command(subcommand)]
@@ -46,12 +187,164 @@ pub enum Themes {
     WriteDefaults,
 }
 
+/// Filter, sort and output options of the commands listing journals.
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListArgs {
+    /// Lists only the journals with the tag. Can be used multiple times to require all the tags.
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+    /// Lists only the journals with the priority.
+    #[arg(short = 'p', long = "priority")]
+    pub priority: Option<u32>,
+    /// Lists only the journals since the date (DD-MM-YYYY or YYYY-MM-DD).
+    #[arg(long = "since", value_name = "DATE", value_parser = parse_date)]
+    pub since: Option<NaiveDate>,
+    /// Sorts the journals by the given criteria, separated with commas. Defaults to the date
+    /// then the priority.
+    #[arg(
+        long = "sort",
+        value_enum,
+        value_delimiter = ',',
+        value_name = "CRITERIA"
+    )]
+    pub sort: Vec<CliSortCriteria>,
+    /// Sorts the journals in ascending order instead of the descending one.
+    #[arg(long = "ascending")]
+    pub ascending: bool,
+    /// Prints the journals as JSON.
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+/// Criteria to sort the listed journals with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliSortCriteria {
+    Date,
+    Priority,
+    Title,
+}
+
+/// Built-in formats of the export command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliExportFormat {
+    /// Transfer JSON file which can be imported to other back-end files.
+    Json,
+    /// Markdown with the journal attributes in front matter.
+    Markdown,
+    /// Standalone HTML with a table of contents.
+    Html,
+    /// Plain-text digest.
+    Text,
+    /// CSV rows with the journal IDs which can be imported again.
+    Csv,
+    /// The content of the journals only.
+    Content,
+}
+
+/// Formats of the import command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliImportFormat {
+    /// Transfer JSON file exported from the app.
+    Json,
+    /// Directory of Markdown files with the journal attributes in front matter.
+    Markdown,
+    /// JSON export of jrnl.
+    Jrnl,
+    /// JSON export of Day One.
+    DayOne,
+    /// CSV file with a header row, like the CSV export of the app.
+    Csv,
+}
+
+/// Source of journals to import with an importer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportSource {
+    pub path: PathBuf,
+    pub format: CliImportFormat,
+    /// Mapping of the journal fields to CSV columns.
+    pub columns: Vec<(String, String)>,
+    /// Updates the existing journals with the IDs of the CSV rows.
+    pub update: bool,
+}
+
+fn parse_column_mapping(text: &str) -> Result<(String, String), String> {
+    match text.split_once('=') {
+        Some((field, column)) if !field.trim().is_empty() && !column.trim().is_empty() => {
+            Ok((field.trim().to_owned(), column.trim().to_owned()))
+        }
+        _ => Err(format!(
+            "Invalid column mapping '{text}'. Expected format: FIELD=COLUMN"
+        )),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PendingCliCommand {
     ImportJournals(PathBuf),
+    /// Imports journals with the importer of their format, reporting the skipped records.
+    ImportFrom(ImportSource),
     AssignPriority(u32),
 }
 
+/// Context the TUI starts in, set with the top-level CLI options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartupContext {
+    /// Filter query applied to the journals on start.
+    pub filter: Option<FilterQuery>,
+    pub action: Option<StartupAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupAction {
+    /// Selects the journal with the given ID.
+    OpenEntry(u32),
+    /// Opens the popup to create a journal with the title filled in if provided.
+    NewEntry { title: Option<String> },
+}
+
+/// Commands using the back-end which are executed without starting the TUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadlessCliCommand {
+    Export {
+        template: Option<String>,
+        format: Option<CliExportFormat>,
+        output: Option<PathBuf>,
+        filter: Option<FilterQuery>,
+    },
+    /// Prints the journals which would be imported from the source.
+    PreviewImport(ImportSource),
+    AddEntry {
+        title: String,
+        tags: Vec<String>,
+        priority: Option<u32>,
+        date: Option<NaiveDate>,
+        content: Option<String>,
+    },
+    /// Prints the journals matching the query and the list options.
+    List {
+        query: Option<FilterQuery>,
+        args: ListArgs,
+    },
+    Show {
+        id: u32,
+        json: bool,
+    },
+    EditEntry(u32),
+    /// Changes the attributes of the journal. The priority is removed if it's set to `Some(None)`.
+    SetAttributes {
+        id: u32,
+        title: Option<String>,
+        tags_add: Vec<String>,
+        tags_remove: Vec<String>,
+        priority: Option<Option<u32>>,
+    },
+    RemoveEntries {
+        ids: Vec<u32>,
+        /// Skips asking for confirmation.
+        confirmed: bool,
+    },
+}
+
 impl CliCommand {
     pub fn exec(
         self,
@@ -60,12 +353,122 @@ impl CliCommand {
     ) -> anyhow::Result<CliResult> {
         match self {
             CliCommand::PrintConfig => exec_print_config(settings),
-            CliCommand::ImportJournals { file_path: path } => Ok(CliResult::PendingCommand(
+            CliCommand::ImportJournals {
+                file_path: path,
+                format: CliImportFormat::Json,
+                columns,
+                update: false,
+                dry_run: false,
+            } if columns.is_empty() => Ok(CliResult::PendingCommand(
                 PendingCliCommand::ImportJournals(path),
             )),
+            CliCommand::ImportJournals {
+                file_path: path,
+                format,
+                columns,
+                update,
+                dry_run,
+            } => {
+                ensure!(
+                    !update || format == CliImportFormat::Csv,
+                    "Updating journals is supported for CSV imports only"
+                );
+                ensure!(
+                    columns.is_empty() || format == CliImportFormat::Csv,
+                    "Column mappings are supported for CSV imports only"
+                );
+
+                let source = ImportSource {
+                    path,
+                    format,
+                    columns,
+                    update,
+                };
+                if dry_run {
+                    Ok(CliResult::HeadlessCommand(
+                        HeadlessCliCommand::PreviewImport(source),
+                    ))
+                } else {
+                    Ok(CliResult::PendingCommand(PendingCliCommand::ImportFrom(
+                        source,
+                    )))
+                }
+            }
             CliCommand::AssignPriority { priority } => Ok(CliResult::PendingCommand(
                 PendingCliCommand::AssignPriority(priority),
             )),
+            CliCommand::Export {
+                template,
+                format,
+                output,
+                filter,
+            } => Ok(CliResult::HeadlessCommand(HeadlessCliCommand::Export {
+                template,
+                format,
+                output,
+                filter,
+            })),
+            CliCommand::Add {
+                title,
+                tags,
+                priority,
+                date,
+                content,
+            } => Ok(CliResult::HeadlessCommand(HeadlessCliCommand::AddEntry {
+                title,
+                tags,
+                priority,
+                date,
+                content,
+            })),
+            CliCommand::List { args } => Ok(CliResult::HeadlessCommand(HeadlessCliCommand::List {
+                query: None,
+                args,
+            })),
+            CliCommand::Show { id, json } => {
+                Ok(CliResult::HeadlessCommand(HeadlessCliCommand::Show {
+                    id,
+                    json,
+                }))
+            }
+            CliCommand::Search { query, args } => {
+                Ok(CliResult::HeadlessCommand(HeadlessCliCommand::List {
+                    query: Some(query),
+                    args,
+                }))
+            }
+            CliCommand::Edit { id } => Ok(CliResult::HeadlessCommand(
+                HeadlessCliCommand::EditEntry(id),
+            )),
+            CliCommand::Set {
+                id,
+                title,
+                tags_add,
+                tags_remove,
+                priority,
+                clear_priority,
+            } => {
+                let priority = if clear_priority {
+                    Some(None)
+                } else {
+                    priority.map(Some)
+                };
+                Ok(CliResult::HeadlessCommand(
+                    HeadlessCliCommand::SetAttributes {
+                        id,
+                        title,
+                        tags_add,
+                        tags_remove,
+                        priority,
+                    },
+                ))
+            }
+            CliCommand::Rm { ids, yes } => Ok(CliResult::HeadlessCommand(
+                HeadlessCliCommand::RemoveEntries {
+                    ids,
+                    confirmed: yes,
+                },
+            )),
             CliCommand::Theme(cmd) => match cmd {
                 Themes::PrintPath => exec_print_themes_path(custom_config_dir),
                 Themes::DumpDefaults => exec_print_themes_defaults(),
@@ -139,6 +542,10 @@ mod tests {
             import.command,
             Some(CliCommand::ImportJournals {
                 file_path: PathBuf::from("/tmp/in.json"),
+                format: CliImportFormat::Json,
+                columns: Vec::new(),
+                update: false,
+                dry_run: false,
             })
         );
         assert_eq!(
@@ -154,6 +561,10 @@ mod tests {
 
         let result = CliCommand::ImportJournals {
             file_path: PathBuf::from("/tmp/import.json"),
+            format: CliImportFormat::Json,
+            columns: Vec::new(),
+            update: false,
+            dry_run: false,
         }
         .exec(&mut settings, None)
         .unwrap();
@@ -166,6 +577,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn import_formats_and_dry_run() {
+        let mut settings = Settings::default();
+        let source = ImportSource {
+            path: PathBuf::from("/tmp/notes.csv"),
+            format: CliImportFormat::Csv,
+            columns: vec![(String::from("title"), String::from("Subject"))],
+            update: false,
+        };
+
+        let cli = Cli::parse_from([
+            "tjournal",
+            "imj",
+            "-p",
+            "/tmp/notes.csv",
+            "--format",
+            "csv",
+            "--column",
+            "title=Subject",
+        ]);
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+        assert_eq!(
+            result,
+            CliResult::PendingCommand(PendingCliCommand::ImportFrom(source.clone()))
+        );
+
+        let cli = Cli::parse_from([
+            "tjournal",
+            "imj",
+            "-p",
+            "/tmp/notes.csv",
+            "--format",
+            "csv",
+            "--column",
+            "title=Subject",
+            "--dry-run",
+        ]);
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+        assert_eq!(
+            result,
+            CliResult::HeadlessCommand(HeadlessCliCommand::PreviewImport(source))
+        );
+
+        let err = Cli::try_parse_from(["tjournal", "imj", "-p", "a.csv", "--column", "title"])
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected format: FIELD=COLUMN"));
+    }
+
+    #[test]
+    fn import_update_and_columns_only_for_csv() {
+        let mut settings = Settings::default();
+
+        let cli = Cli::parse_from([
+            "tjournal", "imj", "-p", "a.csv", "--format", "csv", "--update",
+        ]);
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+        assert_eq!(
+            result,
+            CliResult::PendingCommand(PendingCliCommand::ImportFrom(ImportSource {
+                path: PathBuf::from("a.csv"),
+                format: CliImportFormat::Csv,
+                columns: Vec::new(),
+                update: true,
+            }))
+        );
+
+        let cli = Cli::parse_from(["tjournal", "imj", "-p", "a.json", "--update"]);
+        let err = cli.command.unwrap().exec(&mut settings, None).unwrap_err();
+        assert!(err.to_string().contains("CSV imports only"));
+
+        let cli = Cli::parse_from(["tjournal", "imj", "-p", "a.json", "--column", "title=Name"]);
+        let err = cli.command.unwrap().exec(&mut settings, None).unwrap_err();
+        assert!(err.to_string().contains("Column mappings"));
+    }
+
     #[test]
     fn assign_exec_returns_pending() {
         let mut settings = Settings::default();
@@ -180,6 +666,180 @@ mod tests {
         );
     }
 
+    #[test]
+    fn add_parses_to_headless() {
+        let mut settings = Settings::default();
+        let cli = Cli::parse_from([
+            "tjournal",
+            "add",
+            "--title",
+            "Deploy",
+            "--tag",
+            "work",
+            "--tag",
+            "ops",
+            "-p",
+            "2",
+            "--date",
+            "2024-03-05",
+        ]);
+
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+
+        assert_eq!(
+            result,
+            CliResult::HeadlessCommand(HeadlessCliCommand::AddEntry {
+                title: String::from("Deploy"),
+                tags: vec![String::from("work"), String::from("ops")],
+                priority: Some(2),
+                date: NaiveDate::from_ymd_opt(2024, 3, 5),
+                content: None,
+            })
+        );
+
+        let err =
+            Cli::try_parse_from(["tjournal", "add", "-t", "A", "--date", "tomorrow"]).unwrap_err();
+        assert!(err.to_string().contains("Invalid date 'tomorrow'"));
+    }
+
+    #[test]
+    fn list_and_search_parse_to_headless() {
+        let mut settings = Settings::default();
+
+        let cli = Cli::parse_from([
+            "tjournal",
+            "ls",
+            "--tag",
+            "work",
+            "--since",
+            "01-03-2024",
+            "--sort",
+            "priority,title",
+            "--json",
+        ]);
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+        let expected_args = ListArgs {
+            tags: vec![String::from("work")],
+            priority: None,
+            since: NaiveDate::from_ymd_opt(2024, 3, 1),
+            sort: vec![CliSortCriteria::Priority, CliSortCriteria::Title],
+            ascending: false,
+            json: true,
+        };
+        assert_eq!(
+            result,
+            CliResult::HeadlessCommand(HeadlessCliCommand::List {
+                query: None,
+                args: expected_args,
+            })
+        );
+
+        let cli = Cli::parse_from(["tjournal", "search", "deploy AND tag:ops", "-p", "2"]);
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+        let CliResult::HeadlessCommand(HeadlessCliCommand::List {
+            query: Some(query),
+            args,
+        }) = result
+        else {
+            panic!("Search must be a headless list command");
+        };
+        assert_eq!(query.text(), "deploy AND tag:ops");
+        assert_eq!(args.priority, Some(2));
+
+        let cli = Cli::parse_from(["tjournal", "show", "4", "--json"]);
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+        assert_eq!(
+            result,
+            CliResult::HeadlessCommand(HeadlessCliCommand::Show { id: 4, json: true })
+        );
+    }
+
+    #[test]
+    fn modify_commands_parse_to_headless() {
+        let mut settings = Settings::default();
+        let mut exec = |args: &[&str]| {
+            let cli = Cli::try_parse_from(args)?;
+            anyhow::Ok(cli.command.unwrap().exec(&mut settings, None)?)
+        };
+
+        assert_eq!(
+            exec(&["tjournal", "edit", "3"]).unwrap(),
+            CliResult::HeadlessCommand(HeadlessCliCommand::EditEntry(3))
+        );
+
+        assert_eq!(
+            exec(&[
+                "tjournal",
+                "set",
+                "3",
+                "--tag-add",
+                "done",
+                "--tag-remove",
+                "todo",
+                "--clear-priority",
+            ])
+            .unwrap(),
+            CliResult::HeadlessCommand(HeadlessCliCommand::SetAttributes {
+                id: 3,
+                title: None,
+                tags_add: vec![String::from("done")],
+                tags_remove: vec![String::from("todo")],
+                priority: Some(None),
+            })
+        );
+
+        assert_eq!(
+            exec(&["tjournal", "rm", "1", "2", "--yes"]).unwrap(),
+            CliResult::HeadlessCommand(HeadlessCliCommand::RemoveEntries {
+                ids: vec![1, 2],
+                confirmed: true,
+            })
+        );
+
+        // Set requires at least one change and can't set and clear the priority together.
+        assert!(exec(&["tjournal", "set", "3"]).is_err());
+        assert!(exec(&["tjournal", "set", "3", "-p", "1", "--clear-priority"]).is_err());
+    }
+
+    #[test]
+    fn export_parses_to_headless() {
+        let mut settings = Settings::default();
+        let cli = Cli::parse_from([
+            "tjournal",
+            "export",
+            "--template",
+            "weekly",
+            "-o",
+            "/tmp/weekly.md",
+            "--filter",
+            "tag:work",
+        ]);
+
+        let result = cli.command.unwrap().exec(&mut settings, None).unwrap();
+
+        let CliResult::HeadlessCommand(HeadlessCliCommand::Export {
+            template,
+            format,
+            output,
+            filter,
+        }) = result
+        else {
+            panic!("Export should be executed without the TUI");
+        };
+        assert_eq!(template.as_deref(), Some("weekly"));
+        assert_eq!(format, None);
+        assert_eq!(output, Some(PathBuf::from("/tmp/weekly.md")));
+        assert_eq!(
+            filter.map(|f| f.text().to_owned()).as_deref(),
+            Some("tag:work")
+        );
+
+        let err =
+            Cli::try_parse_from(["tjournal", "ex", "--template", "weekly", "--format", "html"])
+                .unwrap_err();
+        assert!(err.to_string().contains("cannot be used with"));
+    }
+
     #[test]
     fn theme_commands_return() {
         let mut settings = Settings::default();
@@ -5,13 +5,22 @@ use std::{
 };
 
 use crate::{
+    app::filter::{FilterQuery, date_range::parse_date, query::parse_filter_query},
     logging::{get_default_path as default_log_path, setup_logging},
     settings::{BackendType, Settings, settings_default_dir_path},
 };
 
 pub mod commands;
 pub use commands::CliCommand;
+pub use commands::CliExportFormat;
+pub use commands::CliImportFormat;
+pub use commands::CliSortCriteria;
+pub use commands::HeadlessCliCommand;
+pub use commands::ImportSource;
+pub use commands::ListArgs;
 pub use commands::PendingCliCommand;
+pub use commands::StartupAction;
+pub use commands::StartupContext;
 use path_absolutize::Absolutize;
 
 #[derive(Parser, Debug)]
@@ -41,6 +50,29 @@ pub struct Cli {
     #[arg(short = 'l', long = "log", value_name = "FILE PATH", help = log_help())]
     log_file: Option<PathBuf>,
 
+    /// Starts the app with the journals filtered by the given query.
+    /// Example: '(tag:work OR tag:oncall) AND NOT tag:done AND priority>=2'
+    #[arg(
+        verbatim_doc_comment,
+        short = 'f',
+        long = "filter",
+        value_name = "QUERY",
+        value_parser = parse_filter_query
+    )]
+    pub filter: Option<FilterQuery>,
+
+    /// Starts the app with the journal of the given ID selected.
+    #[arg(long = "open", value_name = "ID", conflicts_with = "new")]
+    open: Option<u32>,
+
+    /// Starts the app in the popup to create a new journal.
+    #[arg(long = "new")]
+    new: bool,
+
+    /// Fills in the title of the new journal created with `--new`.
+    #[arg(long = "title", value_name = "TITLE", requires = "new")]
+    title: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<CliCommand>,
 }
@@ -50,9 +82,28 @@ pub enum CliResult {
     Return,
     Continue,
     PendingCommand(PendingCliCommand),
+    HeadlessCommand(HeadlessCliCommand),
 }
 
 impl Cli {
+    /// Gets the context to start the TUI in from the top-level options.
+    pub fn startup_context(&self) -> StartupContext {
+        let action = if let Some(id) = self.open {
+            Some(StartupAction::OpenEntry(id))
+        } else if self.new {
+            Some(StartupAction::NewEntry {
+                title: self.title.clone(),
+            })
+        } else {
+            None
+        };
+
+        StartupContext {
+            filter: self.filter.clone(),
+            action,
+        }
+    }
+
     pub async fn handle_cli(mut self, settings: &mut Settings) -> anyhow::Result<CliResult> {
         #[cfg(feature = "json")]
         if let Some(json_path) = self.json_file_path.take() {
@@ -167,6 +218,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_filter_query() {
+        let cli = Cli::parse_from(["tjournal", "--filter", "tag:work AND priority>=2"]);
+
+        assert_eq!(
+            cli.filter.map(|query| query.text().to_owned()),
+            Some(String::from("tag:work AND priority>=2"))
+        );
+
+        let err = Cli::try_parse_from(["tjournal", "-f", "(tag:work"]).unwrap_err();
+        assert!(err.to_string().contains("Missing closing parenthesis"));
+    }
+
+    #[test]
+    fn parse_startup_context() {
+        let cli = Cli::parse_from(["tjournal", "--open", "4", "-f", "tag:work"]);
+        let context = cli.startup_context();
+        assert_eq!(context.action, Some(StartupAction::OpenEntry(4)));
+        assert_eq!(
+            context.filter.map(|query| query.text().to_owned()),
+            Some(String::from("tag:work"))
+        );
+
+        let cli = Cli::parse_from(["tjournal", "--new", "--title", "Standup"]);
+        assert_eq!(
+            cli.startup_context(),
+            StartupContext {
+                filter: None,
+                action: Some(StartupAction::NewEntry {
+                    title: Some(String::from("Standup"))
+                }),
+            }
+        );
+
+        assert_eq!(
+            Cli::parse_from(["tjournal"]).startup_context(),
+            StartupContext::default()
+        );
+
+        assert!(Cli::try_parse_from(["tjournal", "--title", "Standup"]).is_err());
+        assert!(Cli::try_parse_from(["tjournal", "--open", "1", "--new"]).is_err());
+    }
+
     #[test]
     fn parse_rejects_bad_backend() {
         let err = Cli::try_parse_from(["tjournal", "--backend-type", "bogus"]).unwrap_err();
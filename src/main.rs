@@ -23,11 +23,13 @@ async fn main() -> Result<()> {
     let mut settings = Settings::new(custom_config.clone()).await?;
 
     let mut pending_cmd = None;
+    let startup_context = cli.startup_context();
 
     match cli.handle_cli(&mut settings).await? {
         cli::CliResult::Return => return Ok(()),
         cli::CliResult::Continue => {}
         cli::CliResult::PendingCommand(cmd) => pending_cmd = Some(cmd),
+        cli::CliResult::HeadlessCommand(cmd) => return app::run_headless(settings, cmd).await,
     }
 
     let styles =
@@ -40,11 +42,17 @@ async fn main() -> Result<()> {
 
     chain_panic_hook();
 
-    app::run(&mut terminal, settings, styles, pending_cmd)
-        .await
-        .inspect_err(|err| {
-            log::error!("[PANIC] {err:?}");
-        })?;
+    app::run(
+        &mut terminal,
+        settings,
+        styles,
+        pending_cmd,
+        startup_context,
+    )
+    .await
+    .inspect_err(|err| {
+        log::error!("[PANIC] {err:?}");
+    })?;
 
     // restore terminal
     disable_raw_mode()?;
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+/// Sets how the unsaved changes in the built-in editor are kept.
+pub struct AutoSaveSettings {
+    #[serde(default = "return_true")]
+    /// Persists drafts of the unsaved changes in the state directory to recover them on the next
+    /// start if the app didn't exit normally.
+    pub drafts: bool,
+    #[serde(default)]
+    /// Saves the changes of the current journal to the back-end after the given seconds without
+    /// any input. Disabled if not set.
+    pub idle_seconds: Option<u64>,
+}
+
+fn return_true() -> bool {
+    true
+}
+
+impl Default for AutoSaveSettings {
+    fn default() -> Self {
+        Self {
+            drafts: true,
+            idle_seconds: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_defaults_match_default() {
+        let auto_save: AutoSaveSettings = toml::from_str("").unwrap();
+
+        assert_eq!(auto_save.drafts, AutoSaveSettings::default().drafts);
+        assert_eq!(auto_save.idle_seconds, None);
+    }
+
+    #[test]
+    fn reads_idle_seconds() {
+        let auto_save: AutoSaveSettings =
+            toml::from_str("drafts = false\nidle_seconds = 30").unwrap();
+
+        assert!(!auto_save.drafts);
+        assert_eq!(auto_save.idle_seconds, Some(30));
+    }
+}
@@ -2,12 +2,20 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use super::settings_default_dir_path;
+
+const TEMPLATES_DIR_NAME: &str = "templates";
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ExportSettings {
     #[serde(default)]
     pub default_path: Option<PathBuf>,
     #[serde(default = "return_true")]
     pub show_confirmation: bool,
+    /// Directory of the user export templates. Defaults to the `templates` directory inside the
+    /// configurations directory.
+    #[serde(default)]
+    pub templates_dir: Option<PathBuf>,
 }
 
 fn return_true() -> bool {
@@ -19,8 +27,30 @@ impl Default for ExportSettings {
         Self {
             default_path: None,
             show_confirmation: true,
+            templates_dir: None,
+        }
+    }
+}
+
+impl ExportSettings {
+    pub fn get_templates_dir(&self) -> anyhow::Result<PathBuf> {
+        match self.templates_dir.as_ref() {
+            Some(dir) => Ok(dir.clone()),
+            None => Self::default_templates_dir(None),
         }
     }
+
+    /// Gets the templates directory inside the custom configurations directory if provided, or
+    /// inside the default one otherwise.
+    pub fn default_templates_dir(custom_config_dir: Option<&PathBuf>) -> anyhow::Result<PathBuf> {
+        let config_dir = match custom_config_dir {
+            Some(dir) if dir.is_dir() => dir.clone(),
+            // Paths of configuration files are still accepted for backward compatibility.
+            Some(_) | None => settings_default_dir_path()?,
+        };
+
+        Ok(config_dir.join(TEMPLATES_DIR_NAME))
+    }
 }
 
 #[cfg(test)]
@@ -45,4 +75,27 @@ mod tests {
         assert!(export.show_confirmation);
         assert_eq!(export.default_path, Some(PathBuf::from("/tmp/out.txt")));
     }
+
+    #[test]
+    fn templates_dir_in_custom_config_dir() {
+        let config_dir = tempfile::Builder::new()
+            .prefix("export-config")
+            .tempdir()
+            .unwrap();
+        let config_path = config_dir.path().to_path_buf();
+
+        assert_eq!(
+            ExportSettings::default_templates_dir(Some(&config_path)).unwrap(),
+            config_path.join(TEMPLATES_DIR_NAME)
+        );
+
+        let export = ExportSettings {
+            templates_dir: Some(PathBuf::from("/tmp/templates")),
+            ..Default::default()
+        };
+        assert_eq!(
+            export.get_templates_dir().unwrap(),
+            PathBuf::from("/tmp/templates")
+        );
+    }
 }
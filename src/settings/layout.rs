@@ -0,0 +1,73 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Minimum percentage of the screen the entries list can be resized to.
+pub const MIN_ENTRIES_LIST_PERCENTAGE: u16 = 10;
+/// Maximum percentage of the screen the entries list can be resized to.
+pub const MAX_ENTRIES_LIST_PERCENTAGE: u16 = 90;
+
+#[derive(Debug, Deserialize, Serialize)]
+/// Sets the initial layout of the entries list and the editor panes.
+/// Changes on the layout at runtime are persisted in the app state and take precedence.
+pub struct LayoutSettings {
+    #[serde(default)]
+    pub orientation: PanesOrientation,
+    #[serde(default = "default_entries_list_percentage")]
+    /// The percentage of the screen taken by the entries list.
+    pub entries_list_percentage: u16,
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        Self {
+            orientation: Default::default(),
+            entries_list_percentage: default_entries_list_percentage(),
+        }
+    }
+}
+
+const fn default_entries_list_percentage() -> u16 {
+    30
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, ValueEnum, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+/// Represents how the entries list and the editor panes are split.
+pub enum PanesOrientation {
+    #[default]
+    /// Entries list and editor are placed side by side.
+    Horizontal,
+    /// Entries list is placed above the editor.
+    Vertical,
+}
+
+impl PanesOrientation {
+    pub fn toggle(self) -> Self {
+        match self {
+            PanesOrientation::Horizontal => PanesOrientation::Vertical,
+            PanesOrientation::Vertical => PanesOrientation::Horizontal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_defaults_match_default() {
+        let layout: LayoutSettings = toml::from_str("").unwrap();
+
+        assert_eq!(layout.orientation, LayoutSettings::default().orientation);
+        assert_eq!(layout.entries_list_percentage, 30);
+    }
+
+    #[test]
+    fn reads_vertical_orientation() {
+        let layout: LayoutSettings =
+            toml::from_str("orientation = 'vertical'\nentries_list_percentage = 40").unwrap();
+
+        assert_eq!(layout.orientation, PanesOrientation::Vertical);
+        assert_eq!(layout.entries_list_percentage, 40);
+    }
+}
@@ -20,15 +20,21 @@ use crate::app::state::AppState;
 use self::json_backend::{JsonBackend, get_default_json_path};
 #[cfg(feature = "sqlite")]
 use self::sqlite_backend::{SqliteBackend, get_default_sqlite_path};
-use self::{export::ExportSettings, external_editor::ExternalEditor};
+use self::{
+    auto_save::AutoSaveSettings, export::ExportSettings, external_editor::ExternalEditor,
+    layout::LayoutSettings, spell_check::SpellCheckSettings,
+};
 
 #[cfg(feature = "json")]
 pub mod json_backend;
 #[cfg(feature = "sqlite")]
 pub mod sqlite_backend;
 
+mod auto_save;
 mod export;
 mod external_editor;
+pub mod layout;
+pub mod spell_check;
 
 const DEFAULT_SCROLL_PER_PAGE: usize = 5;
 
@@ -52,6 +58,9 @@ pub struct Settings {
     pub scroll_per_page: Option<usize>,
     #[serde(default)]
     pub sync_os_clipboard: bool,
+    #[serde(default)]
+    /// Sets the keybindings of the built-in editor.
+    pub editor_profile: EditorProfile,
     #[serde(default = "default_history_limit")]
     /// Set the maximum size of the history stacks (undo & redo) size.
     pub history_limit: usize,
@@ -62,6 +71,15 @@ pub struct Settings {
     pub datum_visibility: DatumVisibility,
     /// Overwrite the path for the directory used to persist the app state.
     pub app_state_dir: Option<PathBuf>,
+    #[serde(default)]
+    /// Sets the initial layout of the panes in the main window.
+    pub layout: LayoutSettings,
+    #[serde(default)]
+    /// Sets the spell checking of the journals content in the editor.
+    pub spell_check: SpellCheckSettings,
+    #[serde(default)]
+    /// Sets the drafts and the automatic saving of the changes in the editor.
+    pub auto_save: AutoSaveSettings,
 }
 
 impl Default for Settings {
@@ -77,10 +95,14 @@ impl Default for Settings {
             default_journal_priority: Default::default(),
             scroll_per_page: Default::default(),
             sync_os_clipboard: Default::default(),
+            editor_profile: Default::default(),
             history_limit: default_history_limit(),
             colored_tags: default_colored_tags(),
             datum_visibility: Default::default(),
             app_state_dir: Default::default(),
+            layout: Default::default(),
+            spell_check: Default::default(),
+            auto_save: Default::default(),
         }
     }
 }
@@ -99,6 +121,18 @@ pub enum DatumVisibility {
     EmptyLine,
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, ValueEnum, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+/// Represents the keybindings profiles of the built-in editor.
+pub enum EditorProfile {
+    #[default]
+    /// Modal editing with vim keybindings in normal and visual modes and emacs ones in insert mode.
+    Vim,
+    /// Conventional editing without modes, with shift-arrows selection and Ctrl-z/Ctrl-y for
+    /// undo and redo.
+    Standard,
+}
+
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, ValueEnum, Clone, Copy, Default)]
 pub enum BackendType {
     #[cfg_attr(all(feature = "json", not(feature = "sqlite")), default)]
@@ -117,7 +151,7 @@ const fn default_colored_tags() -> bool {
 
 impl Settings {
     pub async fn new(custom_config_dir: Option<PathBuf>) -> anyhow::Result<Self> {
-        let config_file = if let Some(path) = custom_config_dir {
+        let config_file = if let Some(path) = custom_config_dir.clone() {
             ensure!(
                 path.exists(),
                 "Provided custom directory doesn't exit. Path: {}",
@@ -137,7 +171,7 @@ impl Settings {
             settings_file_path(&default_dir)
         };
 
-        let settings = if config_file.exists() {
+        let mut settings: Settings = if config_file.exists() {
             let file_content = tokio::fs::read_to_string(config_file)
                 .await
                 .map_err(|err| anyhow!("Failed to load configuration file. Error infos: {err}"))?;
@@ -147,6 +181,12 @@ impl Settings {
             Settings::default()
         };
 
+        if settings.export.templates_dir.is_none() {
+            settings.export.templates_dir = Some(ExportSettings::default_templates_dir(
+                custom_config_dir.as_ref(),
+            )?);
+        }
+
         Ok(settings)
     }
 
@@ -169,10 +209,14 @@ impl Settings {
             default_journal_priority: _,
             scroll_per_page: _,
             sync_os_clipboard: _,
+            editor_profile: _,
             history_limit: _,
             colored_tags: _,
             datum_visibility: _,
             app_state_dir: _,
+            layout: _,
+            spell_check: _,
+            auto_save: _,
         } = self;
 
         if self.backend_type.is_none() {
@@ -197,6 +241,15 @@ impl Settings {
             self.app_state_dir = Some(AppState::default_persist_dir()?);
         }
 
+        if self.export.templates_dir.is_none() {
+            self.export.templates_dir = Some(ExportSettings::default_templates_dir(None)?);
+        }
+
+        if self.spell_check.personal_dictionary.is_none() {
+            self.spell_check.personal_dictionary =
+                Some(spell_check::default_personal_dictionary_path()?);
+        }
+
         Ok(())
     }
 
@@ -295,6 +348,10 @@ mod tests {
         assert_eq!(settings.scroll_per_page, None);
         assert_eq!(settings.history_limit, 10);
         assert!(settings.colored_tags);
+        assert_eq!(
+            settings.export.templates_dir,
+            Some(dir.path().join("templates"))
+        );
     }
 
     #[tokio::test]
@@ -315,6 +372,7 @@ mod tests {
             r#"
 scroll_per_page = 7
 datum_visibility = "empty_line"
+editor_profile = "standard"
 "#,
         );
 
@@ -322,6 +380,7 @@ datum_visibility = "empty_line"
 
         assert_eq!(settings.scroll_per_page, Some(7));
         assert_eq!(settings.datum_visibility, DatumVisibility::EmptyLine);
+        assert_eq!(settings.editor_profile, EditorProfile::Standard);
     }
 
     #[tokio::test]
@@ -411,5 +470,6 @@ temp_file_extension = "md"
         assert!(text.contains("scroll_per_page = 5"));
         assert!(text.contains("history_limit = 10"));
         assert!(text.contains("colored_tags = true"));
+        assert!(text.contains("templates_dir = "));
     }
 }
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::settings_default_dir_path;
+
+const PERSONAL_DICTIONARY_FILE_NAME: &str = "dictionary.txt";
+const DICTIONARIES_DIR_NAME: &str = "dictionaries";
+
+/// Directories searched for Hunspell dictionaries when none are configured.
+const SYSTEM_DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/usr/local/share/hunspell",
+    "/Library/Spelling",
+];
+
+#[derive(Debug, Deserialize, Serialize)]
+/// Sets the spell checking of the journals content against local Hunspell dictionaries.
+pub struct SpellCheckSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_language")]
+    /// The name of the dictionary files without extensions (`en_US` for `en_US.aff` and
+    /// `en_US.dic`).
+    pub language: String,
+    #[serde(default)]
+    /// Directories to search for the dictionary files. The `dictionaries` directory inside the
+    /// configurations directory and the common system directories are used if empty.
+    pub dictionary_dirs: Vec<PathBuf>,
+    #[serde(default)]
+    /// Path of the file containing the words added to the personal dictionary.
+    pub personal_dictionary: Option<PathBuf>,
+}
+
+impl Default for SpellCheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language: default_language(),
+            dictionary_dirs: Vec::new(),
+            personal_dictionary: None,
+        }
+    }
+}
+
+fn default_language() -> String {
+    String::from("en_US")
+}
+
+impl SpellCheckSettings {
+    /// Returns the directories to search for the dictionaries in.
+    pub fn get_dictionary_dirs(&self) -> Vec<PathBuf> {
+        if !self.dictionary_dirs.is_empty() {
+            return self.dictionary_dirs.clone();
+        }
+
+        settings_default_dir_path()
+            .map(|dir| dir.join(DICTIONARIES_DIR_NAME))
+            .into_iter()
+            .chain(SYSTEM_DICTIONARY_DIRS.iter().map(PathBuf::from))
+            .collect()
+    }
+
+    pub fn get_personal_dictionary_path(&self) -> anyhow::Result<PathBuf> {
+        match self.personal_dictionary.as_ref() {
+            Some(path) => Ok(path.clone()),
+            None => default_personal_dictionary_path(),
+        }
+    }
+}
+
+pub fn default_personal_dictionary_path() -> anyhow::Result<PathBuf> {
+    settings_default_dir_path().map(|dir| dir.join(PERSONAL_DICTIONARY_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_defaults_match_default() {
+        let spell_check: SpellCheckSettings = toml::from_str("").unwrap();
+
+        assert!(!spell_check.enabled);
+        assert_eq!(spell_check.language, SpellCheckSettings::default().language);
+        assert!(spell_check.dictionary_dirs.is_empty());
+        assert!(spell_check.personal_dictionary.is_none());
+    }
+
+    #[test]
+    fn configured_dirs_replace_defaults() {
+        let spell_check: SpellCheckSettings =
+            toml::from_str("enabled = true\nlanguage = 'de_DE'\ndictionary_dirs = ['/tmp/dicts']")
+                .unwrap();
+
+        assert!(spell_check.enabled);
+        assert_eq!(spell_check.language, "de_DE");
+        assert_eq!(
+            spell_check.get_dictionary_dirs(),
+            vec![PathBuf::from("/tmp/dicts")]
+        );
+    }
+}